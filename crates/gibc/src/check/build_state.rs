@@ -5,15 +5,15 @@ use std::{
 
 use async_lsp::lsp_types::Position;
 use rustc_hash::FxHasher;
-use salsa::plumbing::AsId;
 
 use crate::{
     db::{
-        decl::Project,
+        decl::{Decl, Project},
         input::{Db, SourceFile},
+        path::ModulePath,
     },
     range::offset_to_position_str,
-    ty::Ty,
+    ty::{FuncTy, Generic, Named, Ty},
     util::Span,
 };
 
@@ -25,16 +25,23 @@ pub struct BuildState<'db> {
     pub vtables: HashMap<u64, VTable>,
     pub vtable_map: HashMap<Ty<'db>, u64>,
     pub project: Project<'db>,
-    pub hasher: FxHasher,
     pub file: SourceFile,
     pub block_scopes: Vec<usize>,
+    pub type_names: HashMap<u32, String>,
+    pub func_names: HashMap<u32, String>,
+    pub table_names: HashMap<u64, String>,
+    pub entry: Option<u32>,
+    /// The symbols of functions in impls, which are named after the impl's types
+    pub impl_symbols: HashMap<Decl<'db>, String>,
+    /// Descriptions of symbols whose ids clashed with another symbol's
+    pub collisions: Vec<String>,
 }
 
 pub type VTable = HashMap<u32, u32>;
 
 impl<'db> BuildState<'db> {
     pub fn new(db: &'db dyn Db, project: Project<'db>, file: SourceFile) -> Self {
-        BuildState {
+        let mut state = BuildState {
             vars: vec![],
             params: HashMap::new(),
             var_count: 0,
@@ -42,10 +49,27 @@ impl<'db> BuildState<'db> {
             vtables: HashMap::new(),
             vtable_map: HashMap::new(),
             project,
-            hasher: FxHasher::default(),
             file,
             block_scopes: Vec::new(),
+            type_names: HashMap::new(),
+            func_names: HashMap::new(),
+            table_names: HashMap::new(),
+            entry: None,
+            impl_symbols: HashMap::new(),
+            collisions: vec![],
+        };
+        for impl_ in project.impl_map(db).values().flatten() {
+            let from = state.ty_symbol(&impl_.from_ty(db));
+            let prefix = match impl_.to_ty(db) {
+                Some(trait_) => format!("<{from} as {}>", state.ty_symbol(&trait_)),
+                None => from,
+            };
+            for func in impl_.functions(db) {
+                let symbol = format!("{prefix}::{}", func.name(db));
+                state.impl_symbols.insert(*func, symbol);
+            }
         }
+        state
     }
 
     pub fn add_var(&mut self, name: String) -> u32 {
@@ -91,13 +115,13 @@ impl<'db> BuildState<'db> {
         if let Some(existing) = self.vtable_map.get(ty) {
             return *existing;
         }
-        let funcs = ty
-            .get_trait_func_decls(self)
-            .iter()
-            .map(|(trait_, impl_)| (trait_.as_id().as_u32(), impl_.as_id().as_u32()))
-            .collect::<HashMap<_, _>>();
-        ty.hash(&mut self.hasher);
-        let hash = self.hasher.finish();
+        let mut funcs = HashMap::new();
+        for (trait_, impl_) in ty.get_trait_func_decls(self) {
+            funcs.insert(self.get_func_id(trait_), self.get_func_id(impl_));
+        }
+        let symbol = self.ty_symbol(ty);
+        let hash = stable_hash(&symbol);
+        self.table_names.insert(hash, symbol);
         self.vtable_map.insert(ty.clone(), hash);
         self.vtables.insert(hash, funcs);
         hash
@@ -109,7 +133,156 @@ impl<'db> BuildState<'db> {
         (line as u16, character as u16)
     }
 
+    pub fn get_std_decl(&self, path: &[&str]) -> Decl<'db> {
+        let mut full = vec!["std".to_string()];
+        full.extend(path.iter().map(ToString::to_string));
+        self.project
+            .get_decl(self.db, ModulePath::new(self.db, full))
+            .unwrap_or_else(|| panic!("Missing std decl: {}", path.join("::")))
+    }
+
+    pub fn get_std_type_id(&mut self, path: &[&str]) -> u32 {
+        let decl = self.get_std_decl(path);
+        self.get_type_id(decl)
+    }
+
+    /// Gets the id used to construct `decl` and records its name for the VM
+    pub fn get_type_id(&mut self, decl: Decl<'db>) -> u32 {
+        let symbol = self.decl_symbol(decl);
+        let id = stable_id(&symbol);
+        add_symbol(&mut self.type_names, id, symbol, &mut self.collisions);
+        id
+    }
+
+    /// Gets the id of a function in the emitted bytecode and records its name for the linker
+    pub fn get_func_id(&mut self, decl: Decl<'db>) -> u32 {
+        let symbol = self.decl_symbol(decl);
+        let id = stable_id(&symbol);
+        add_symbol(&mut self.func_names, id, symbol, &mut self.collisions);
+        id
+    }
+
+    /// The fully qualified name of a decl.
+    ///
+    /// Ids are derived from this rather than salsa ids,
+    /// so the same project always builds to the same bytes
+    fn decl_symbol(&self, decl: Decl<'db>) -> String {
+        let path = decl.path(self.db).name(self.db);
+        let name = decl.name(self.db);
+        if path.last() == Some(&name) {
+            path.join("::")
+        } else if let Some(symbol) = self.impl_symbols.get(&decl) {
+            // Functions in an impl share the module's path, so the impl's types tell them apart
+            symbol.clone()
+        } else {
+            format!("{}::{name}", path.join("::"))
+        }
+    }
+
+    /// The path of a file relative to the project root
+    pub fn get_file_name(&self, file: SourceFile) -> String {
+        let path = file.path(self.db).to_string_lossy().to_string();
+        path.strip_prefix(&self.db.root())
+            .map_or(path.as_str(), |path| path.trim_start_matches('/'))
+            .to_string()
+    }
+
+    pub fn get_file_id(&self, file: SourceFile) -> u32 {
+        stable_id(&self.get_file_name(file))
+    }
+
+    fn ty_symbol(&self, ty: &Ty<'db>) -> String {
+        let list = |tys: &[Ty<'db>]| {
+            tys.iter()
+                .map(|ty| self.ty_symbol(ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match ty {
+            Ty::Named(Named { name, args }) if args.is_empty() => name.name(self.db).join("::"),
+            Ty::Named(Named { name, args }) => {
+                format!("{}[{}]", name.name(self.db).join("::"), list(args))
+            }
+            Ty::Tuple(tys) => format!("({})", list(tys)),
+            Ty::Sum(tys) => tys
+                .iter()
+                .map(|ty| self.ty_symbol(ty))
+                .collect::<Vec<_>>()
+                .join(" + "),
+            Ty::Function(FuncTy {
+                receiver,
+                args,
+                ret,
+            }) => {
+                let receiver = receiver.as_ref().map_or(String::new(), |receiver| {
+                    format!("{}.", self.ty_symbol(receiver))
+                });
+                format!("fn {receiver}({}): {}", list(args), self.ty_symbol(ret))
+            }
+            Ty::Generic(Generic { name, .. }) => name.0.clone(),
+            Ty::Meta(ty) => format!("type {}", self.ty_symbol(ty)),
+            Ty::TypeVar { id } => format!("?{id}"),
+            Ty::Any => "Any".to_string(),
+            Ty::Unknown => "?".to_string(),
+            Ty::Nothing => "Nothing".to_string(),
+        }
+    }
+
     pub fn inc_index(&mut self, diff: usize) {
         *self.block_scopes.last_mut().unwrap() += diff;
     }
 }
+
+fn stable_hash(symbol: &str) -> u64 {
+    // A fresh hasher per symbol keeps the result independent of what was hashed before
+    let mut hasher = FxHasher::default();
+    symbol.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Records the symbol an id was made from, noting a collision if it was made from another one
+pub fn add_symbol(
+    names: &mut HashMap<u32, String>,
+    id: u32,
+    symbol: String,
+    collisions: &mut Vec<String>,
+) {
+    match names.get(&id) {
+        Some(existing) if *existing != symbol => collisions.push(format!(
+            "'{existing}' and '{symbol}' have the same id ({id}), one of them needs to be renamed"
+        )),
+        Some(_) => {}
+        None => {
+            names.insert(id, symbol);
+        }
+    }
+}
+
+fn stable_id(symbol: &str) -> u32 {
+    let hash = stable_hash(symbol);
+    (hash ^ (hash >> 32)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::add_symbol;
+
+    #[test]
+    fn test_add_symbol() {
+        let mut names = HashMap::new();
+        let mut collisions = vec![];
+        add_symbol(&mut names, 1, "main::Foo".to_string(), &mut collisions);
+        add_symbol(&mut names, 1, "main::Foo".to_string(), &mut collisions);
+        assert!(collisions.is_empty());
+        add_symbol(&mut names, 1, "main::Bar".to_string(), &mut collisions);
+        assert_eq!(names[&1], "main::Foo");
+        assert_eq!(
+            collisions,
+            vec![
+                "'main::Foo' and 'main::Bar' have the same id (1), one of them needs to be renamed"
+            ]
+        );
+    }
+}
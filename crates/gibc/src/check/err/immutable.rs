@@ -0,0 +1,55 @@
+use crate::{
+    check::state::Mutability,
+    db::{
+        err::{Diagnostic, Fix, Level},
+        input::{Db, SourceFile},
+    },
+    util::Span,
+};
+
+use super::IntoWithDb;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImmutableAssign {
+    pub name: String,
+    pub span: Span,
+    /// Where the variable is declared
+    pub decl: Span,
+    pub mutability: Mutability,
+    pub file: SourceFile,
+}
+
+impl ImmutableAssign {
+    pub fn message(&self) -> String {
+        match self.mutability {
+            Mutability::Let(_) => format!(
+                "Can't assign to '{}', as it isn't declared with 'let mut'",
+                self.name
+            ),
+            _ => format!("Can't assign to '{}', as it isn't mutable", self.name),
+        }
+    }
+}
+
+impl IntoWithDb<Diagnostic> for ImmutableAssign {
+    fn into_with_db(self, db: &dyn Db) -> Diagnostic {
+        let fix = if let Mutability::Let(pattern) = self.mutability {
+            Some(Fix {
+                title: format!("Make '{}' mutable", self.name),
+                span: Span::splat(pattern.start),
+                text: "mut ".to_string(),
+            })
+        } else {
+            None
+        };
+        Diagnostic {
+            message: self.message(),
+            span: self.span,
+            level: Level::Error,
+            path: self.file.path(db),
+            file: self.file,
+            related: vec![(format!("'{}' is declared here", self.name), self.decl)],
+            fix,
+        }
+    }
+}
@@ -32,6 +32,8 @@ impl IntoWithDb<Diagnostic> for ImplTypeMismatch {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
@@ -30,6 +30,8 @@ impl IntoWithDb<Diagnostic> for IsNotInstance {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
@@ -1,3 +1,4 @@
+use immutable::ImmutableAssign;
 use impl_type::ImplTypeMismatch;
 use is_not_instance::IsNotInstance;
 use missing_receiver::MissingReceiver;
@@ -10,6 +11,7 @@ use crate::{
     db::{err::Diagnostic, input::Db},
 };
 
+pub mod immutable;
 pub mod impl_type;
 pub mod is_not_instance;
 pub mod missing_receiver;
@@ -36,6 +38,7 @@ pub enum CheckError {
     UnexpectedWildcard(UnexpectedWildcard),
     #[allow(dead_code)]
     ImplTypeMismatch(ImplTypeMismatch),
+    ImmutableAssign(ImmutableAssign),
 }
 
 pub trait IntoWithDb<T> {
@@ -53,6 +56,7 @@ impl IntoWithDb<Diagnostic> for Error {
             CheckError::MissingReceiver(err) => err.into_with_db(db),
             CheckError::UnexpectedWildcard(err) => err.into_with_db(db),
             CheckError::ImplTypeMismatch(err) => err.into_with_db(db),
+            CheckError::ImmutableAssign(err) => err.into_with_db(db),
         }
     }
 }
@@ -23,6 +23,8 @@ impl IntoWithDb<Diagnostic> for Simple {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
@@ -34,6 +34,8 @@ impl IntoWithDb<Diagnostic> for UnexpectedArgs {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
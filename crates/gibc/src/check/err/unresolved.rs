@@ -28,6 +28,8 @@ impl IntoWithDb<Diagnostic> for Unresolved {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
@@ -22,6 +22,8 @@ impl IntoWithDb<Diagnostic> for UnexpectedWildcard {
             level: Level::Error,
             path: self.file.path(db),
             file: self.file,
+            related: vec![],
+            fix: None,
         }
     }
 }
@@ -35,6 +35,8 @@ pub enum TokenKind {
     Member,
     Trait,
     Property,
+    TypeAlias,
+    Const,
 }
 
 pub struct SemanticToken {
@@ -92,3 +94,64 @@ pub fn check_project<'db>(db: &'db dyn Db, vfs: Vfs) {
     let project = resolve_project(db, vfs);
     check_vfs(db, vfs, project);
 }
+
+#[cfg(test)]
+pub mod tests {
+    use std::path::Path;
+
+    use salsa::{AsDynDatabase as _, Setter as _};
+
+    use gvm::{format::ByteCodeFile, vm::state::ProgramState};
+
+    use crate::db::{
+        err::Diagnostic,
+        input::{Db as _, SourceDatabase},
+    };
+
+    use super::{check_project, resolve_project};
+
+    /// A project with `src` as its only file, the root module
+    pub fn source_db(src: &str) -> SourceDatabase {
+        let mut db = SourceDatabase::default();
+        db.init("/test".to_string());
+        let file = db.input(Path::new("/test/main.gib"));
+        file.set_text(db.as_dyn_database_mut()).to(src.to_string());
+        db
+    }
+
+    /// Checks `src` as the only file of a project, returning the messages it's reported
+    pub fn check_src(src: &str) -> Vec<String> {
+        let db = source_db(src);
+        check_project::accumulated::<Diagnostic>(&db, db.vfs.unwrap())
+            .into_iter()
+            .map(|diag| diag.message)
+            .collect()
+    }
+
+    /// Builds `src` as the only file of a project, which must have no errors
+    pub fn build_src(src: &str) -> ByteCodeFile {
+        assert_eq!(check_src(src), Vec::<String>::new());
+        let db = source_db(src);
+        let vfs = db.vfs.unwrap();
+        vfs.build(&db, resolve_project(&db, vfs)).unwrap()
+    }
+
+    /// Runs `file` on the VM, returning what it printed
+    pub fn run_file(file: &ByteCodeFile) -> String {
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables.clone(),
+            file.file_names.clone(),
+            file.type_names.clone(),
+        );
+        prog.captured = Some(String::new());
+        prog.run();
+        prog.captured.unwrap()
+    }
+
+    /// Builds and runs `src` as the only file of a project, returning what it printed
+    pub fn run_src(src: &str) -> String {
+        run_file(&build_src(src))
+    }
+}
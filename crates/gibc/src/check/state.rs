@@ -9,7 +9,7 @@ use crate::{
     },
     parser::{common::variance::Variance, expr::qualified_name::SpannedQualifiedName, parse_file},
     ty::{Generic, Ty},
-    util::Span,
+    util::{Span, Spanned},
 };
 
 use super::{
@@ -26,6 +26,18 @@ pub struct VarDecl<'db> {
     pub ty: Ty<'db>,
     pub kind: TokenKind,
     pub span: Span,
+    pub mutability: Mutability,
+}
+
+/// Whether a variable can be assigned to
+#[derive(Debug, PartialEq, Clone, Copy, Update, Eq)]
+pub enum Mutability {
+    /// Bound by a `let mut`
+    Mutable,
+    /// Bound by a `let`, which could be made mutable by adding `mut` at the span
+    Let(Span),
+    /// Bound by a parameter or a pattern outside of a `let`
+    Immutable,
 }
 
 pub struct CheckState<'db> {
@@ -36,6 +48,21 @@ pub struct CheckState<'db> {
     pub should_error: bool,
     pub decl_stack: Vec<Decl<'db>>,
     pub scope_state: ScopedState<'db>,
+    /// The loops around the code being checked, innermost last
+    pub loops: Vec<LoopState<'db>>,
+    /// The mutability of the variables bound by the pattern being checked
+    pub binding: Mutability,
+    /// Whether the code being checked is in a lambda's body
+    pub in_lambda: bool,
+}
+
+/// A loop that `break` and `continue` can refer to
+pub struct LoopState<'db> {
+    pub label: Option<String>,
+    /// Whether `break` can give a value, which it can only for a `loop`
+    pub has_value: bool,
+    /// The type of the values given to `break` so far, or the one that's expected
+    pub ty: Option<Ty<'db>>,
 }
 
 impl<'db> IsScoped<'db> for CheckState<'db> {
@@ -76,6 +103,9 @@ impl<'ty, 'db: 'ty> CheckState<'db> {
             should_error: true,
             decl_stack: vec![decl],
             scope_state: ScopedState::new(db, project, file_data),
+            loops: vec![],
+            binding: Mutability::Immutable,
+            in_lambda: false,
         };
         let tops = parse_file(db, file_data).tops(db);
         for top in tops {
@@ -130,6 +160,37 @@ impl<'ty, 'db: 'ty> CheckState<'db> {
         })
     }
 
+    pub fn enter_loop(&mut self, label: Option<&Spanned<String>>, has_value: bool) {
+        self.loops.push(LoopState {
+            label: label.map(|(label, _)| label.clone()),
+            has_value,
+            ty: None,
+        });
+    }
+
+    /// Returns the type of the values given to `break` in the loop
+    pub fn exit_loop(&mut self) -> Option<Ty<'db>> {
+        self.loops.pop().expect("Loop stack underflow").ty
+    }
+
+    /// The index of the loop a `break` or `continue` refers to, which is the innermost one
+    /// without a label
+    pub fn find_loop(&mut self, label: Option<&Spanned<String>>, span: Span) -> Option<usize> {
+        let found = self.loops.iter().rposition(|loop_| match label {
+            Some((label, _)) => loop_.label.as_ref() == Some(label),
+            None => true,
+        });
+        if found.is_none() {
+            match label {
+                Some((label, span)) => {
+                    self.simple_error(&format!("Couldn't find a loop labelled '{label}"), *span);
+                }
+                None => self.simple_error("Expected to be in a loop", span),
+            }
+        }
+        found
+    }
+
     pub fn current_decl(&self) -> Decl<'db> {
         self.decl_stack.last().copied().unwrap()
     }
@@ -188,6 +249,7 @@ impl<'ty, 'db: 'ty> CheckState<'db> {
                 ty: ty.clone(),
                 kind: TokenKind::Param,
                 span,
+                mutability: Mutability::Immutable,
             },
         );
     }
@@ -1,15 +1,18 @@
+use std::env::{self, consts::EXE_SUFFIX};
 use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use ariadne::{Color, Source};
 use ariadne::{Label, Report, ReportKind};
 use gvm::binary::encode::encode_program;
+use gvm::bundle::bundle;
 
 use crate::check::{check_project, check_vfs, resolve_project};
 use crate::db::err::Diagnostic;
 use crate::db::input::{Db, SourceDatabase};
 
-pub fn build() {
+pub fn build(bundled: bool, runtime: Option<&Path>) {
     let pwd = std::env::current_dir().unwrap();
     let mut db = SourceDatabase::default();
     db.init(pwd.to_string_lossy().to_string());
@@ -27,7 +30,15 @@ pub fn build() {
                 fs::File::create(out_file)
             })
             .unwrap();
-        let file = db.vfs.unwrap().build(&db, project);
+        let file = match db.vfs.unwrap().build(&db, project) {
+            Ok(file) => file,
+            Err(collisions) => {
+                for collision in collisions {
+                    eprintln!("{collision}");
+                }
+                return;
+            }
+        };
         writeln!(out, "{file}").unwrap();
         let out_file = pwd.join("out");
         let bytes = encode_program(&file);
@@ -38,9 +49,61 @@ pub fn build() {
             })
             .unwrap();
         out.write_all(&bytes).unwrap();
+        if bundled {
+            write_bundle(&pwd, &bytes, runtime);
+        }
     }
 }
 
+/// Writes the program and the VM into a single executable named after the project directory
+fn write_bundle(pwd: &Path, program: &[u8], runtime: Option<&Path>) {
+    let Some(runtime) = runtime.map(Path::to_path_buf).or_else(find_runtime) else {
+        eprintln!("Could not find the gvm executable, pass its path with --runtime");
+        return;
+    };
+    let runtime = match fs::read(&runtime) {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            eprintln!("Could not read {}: {err}", runtime.display());
+            return;
+        }
+    };
+    let name = pwd
+        .file_name()
+        .map_or("out".into(), |name| name.to_string_lossy());
+    let out_file = pwd.join(format!("{name}{EXE_SUFFIX}"));
+    // Written under a new name so the result doesn't overwrite `out` when the project is called that
+    let out_file = if out_file == pwd.join("out") {
+        pwd.join(format!("out-bundle{EXE_SUFFIX}"))
+    } else {
+        out_file
+    };
+    let _ = fs::remove_file(&out_file);
+    fs::write(&out_file, bundle(&runtime, program)).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&out_file, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
+
+/// Looks for `gvm` next to the running executable, then on the `PATH`
+fn find_runtime() -> Option<PathBuf> {
+    let name = format!("gvm{EXE_SUFFIX}");
+    let beside = env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join(&name)));
+    beside
+        .into_iter()
+        .chain(
+            env::var_os("PATH")
+                .iter()
+                .flat_map(env::split_paths)
+                .map(|dir| dir.join(&name)),
+        )
+        .find(|path| path.is_file())
+}
+
 pub fn print_error(db: &dyn Db, error: &Diagnostic) {
     let source = Source::from(error.file.text(db));
     let red = Color::Red;
@@ -62,6 +125,13 @@ pub fn print_error(db: &dyn Db, error: &Diagnostic) {
             .with_color(red)
             .with_message(error.message.to_string()),
     );
+    for (message, span) in &error.related {
+        builder = builder.with_label(
+            Label::new((name, span.into_range()))
+                .with_color(Color::Blue)
+                .with_message(message),
+        );
+    }
     let report = builder.finish();
     report.print((name, source)).unwrap();
 }
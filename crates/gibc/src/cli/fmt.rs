@@ -4,17 +4,30 @@ use chumsky::input::Input as _;
 use chumsky::Parser as _;
 use pretty::BoxAllocator;
 
-use crate::{item::pretty_format, lexer::parser::lexer, parser::file_parser, util::Span};
+use crate::{
+    item::{item_docs, pretty_format},
+    lexer::parser::{lexer, split_docs},
+    parser::file_parser,
+    util::Span,
+};
 
 pub fn fmt(path: &Path) {
     let text = std::fs::read_to_string(path).unwrap();
-    let tokens = lexer().parse(&text).unwrap();
+    let (tokens, docs) = split_docs(lexer().parse(&text).unwrap());
     let len = text.len();
     let eoi = Span::splat(len);
     let input = tokens.spanned(eoi);
     let ast = file_parser().parse(input).unwrap();
+    let Some(docs) = item_docs(&text, &ast, &docs) else {
+        eprintln!(
+            "{} has doc comments that aren't above anything, leaving it as it is",
+            path.display()
+        );
+        print!("{text}");
+        return;
+    };
     let mut out = std::io::stdout();
-    pretty_format::<_, ()>(&ast, &BoxAllocator)
+    pretty_format::<_, ()>(&ast, &docs, &BoxAllocator)
         .1
         .render(70, &mut out)
         .unwrap();
@@ -38,14 +38,19 @@ pub fn lex(path: &str) {
             let color = match token {
                 Token::Keyword(_) => keyword,
                 Token::Punct(_) => punct,
-                Token::Ident(_) => ident,
+                Token::Ident(_) | Token::Label(_) => ident,
                 Token::Op(_) => op,
                 Token::Newline => newline,
-                Token::Literal(Literal::String(_)) => string,
+                Token::Literal(Literal::String(_))
+                | Token::InterpStart(_)
+                | Token::InterpMid(_)
+                | Token::InterpEnd(_) => string,
                 Token::Literal(Literal::Int(_)) => int,
                 Token::Literal(Literal::Float(_)) => float,
                 Token::Literal(Literal::Char(_)) => char,
                 Token::Literal(Literal::Bool(_)) => bool,
+                // Doc comments are printed as part of the newline they're in
+                Token::Doc(_) => continue,
             };
             if let Token::Newline = token {
                 print!("{}", s[0..s.len() - 1].fg(color));
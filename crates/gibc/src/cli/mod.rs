@@ -19,7 +19,15 @@ use crate::{dap::start_dap, lsp::main_loop};
 #[derive(Debug, clap::Parser)]
 pub enum Command {
     /// Builds the project
-    Build,
+    Build {
+        /// Also write a standalone executable containing the program and the VM
+        #[clap(long)]
+        bundle: bool,
+
+        /// The VM executable to bundle, instead of the `gvm` next to this one or on the PATH
+        #[clap(long, requires = "bundle")]
+        runtime: Option<PathBuf>,
+    },
 
     /// Runs the project
     Run,
@@ -60,7 +68,7 @@ impl Command {
     pub async fn run(&self) {
         match self {
             Command::Parse { path } => parse(path),
-            Command::Build => build(),
+            Command::Build { bundle, runtime } => build(*bundle, runtime.as_deref()),
             Command::Lsp => main_loop().await,
             Command::Lex { path } => lex(path),
             Command::Fmt { path } => fmt(path),
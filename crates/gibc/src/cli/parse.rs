@@ -1,4 +1,8 @@
-use crate::{lexer::parser::lexer, parser::file_parser, util::Span};
+use crate::{
+    lexer::parser::{lexer, split_docs},
+    parser::file_parser,
+    util::Span,
+};
 use ariadne::{Color, Label, Report, ReportKind, Source};
 use chumsky::error::Rich;
 use chumsky::input::Input;
@@ -14,6 +18,7 @@ pub fn parse(path: &Path) {
         print(path.to_str().unwrap(), &text, &error, "lex");
     }
     if let Some(tokens) = tokens {
+        let (tokens, _) = split_docs(tokens);
         let eoi = Span::splat(len);
         let input = tokens.spanned(eoi);
         let (ast, errors) = file_parser().parse(input).into_output_errors();
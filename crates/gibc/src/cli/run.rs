@@ -1,7 +1,10 @@
 use gvm::{format::ByteCodeFile, vm::state::ProgramState};
 
 use crate::{
-    check::{build_state::BuildState, check_file, check_project, check_vfs, resolve_project},
+    check::{
+        build_state::{add_symbol, BuildState},
+        check_file, check_project, check_vfs, resolve_project,
+    },
     db::{
         decl::Project,
         err::Diagnostic,
@@ -22,30 +25,199 @@ pub fn run() {
         print_error(&db, diag);
     }
     if diags.is_empty() {
-        let file = db.vfs.unwrap().build(&db, project);
-        let mut prog = ProgramState::new(&file.funcs, file.tables, file.file_names);
+        let file = match db.vfs.unwrap().build(&db, project) {
+            Ok(file) => file,
+            Err(collisions) => {
+                for collision in collisions {
+                    eprintln!("{collision}");
+                }
+                return;
+            }
+        };
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
         prog.run();
     }
 }
 
 impl<'db> Vfs {
-    pub fn build(self, db: &'db dyn Db, project: Project<'db>) -> ByteCodeFile {
+    /// Builds every file into one program, or describes the symbols whose ids collided
+    pub fn build(
+        self,
+        db: &'db dyn Db,
+        project: Project<'db>,
+    ) -> Result<ByteCodeFile, Vec<String>> {
         match self.inner(db) {
             VfsInner::Dir(files) => {
                 let mut code = ByteCodeFile::default();
+                let mut collisions = vec![];
                 for file in files {
-                    let file_code = file.build(db, project);
+                    let file_code = match file.build(db, project) {
+                        Ok(file_code) => file_code,
+                        Err(errors) => {
+                            collisions.extend(errors);
+                            continue;
+                        }
+                    };
+                    code.entry = code.entry.or(file_code.entry);
                     code.funcs.extend(file_code.funcs);
                     code.tables.extend(file_code.tables);
                     code.file_names.extend(file_code.file_names);
+                    for (id, name) in file_code.type_names {
+                        add_symbol(&mut code.type_names, id, name, &mut collisions);
+                    }
+                    for (id, name) in file_code.func_names {
+                        add_symbol(&mut code.func_names, id, name, &mut collisions);
+                    }
+                    code.table_names.extend(file_code.table_names);
+                }
+                if collisions.is_empty() {
+                    Ok(code)
+                } else {
+                    Err(collisions)
                 }
-                code
             }
             VfsInner::File(file) => {
                 let ir = check_file(db, *file, project);
                 let mut state = BuildState::new(db, project, *file);
-                ir.build(&mut state)
+                let code = ir.build(&mut state);
+                if state.collisions.is_empty() {
+                    Ok(code)
+                } else {
+                    Err(state.collisions)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        panic::{catch_unwind, AssertUnwindSafe},
+        process,
+        process::Command,
+    };
+
+    use gvm::{
+        aot::{compile_c, CompileError},
+        vm::state::ProgramState,
+    };
+
+    use crate::check::tests::{build_src, check_src};
+
+    /// The examples that run to the end without reading any input, printing their `.out` file
+    const EXAMPLES: [&str; 13] = [
+        "alias",
+        "assign",
+        "const",
+        "generator",
+        "guards",
+        "if_let",
+        "interpolation",
+        "literals",
+        "loop",
+        "main",
+        "range",
+        "try",
+        "vec",
+    ];
+
+    /// The examples that fail on purpose after printing their `.out` file, with the failure
+    const FAILING: [(&str, &str); 1] = [("vec", "Index 5 is out of bounds for a vec of length 3")];
+
+    /// The examples without a `main`, which only have to check
+    const LIBRARIES: [&str; 2] = ["iter", "ops"];
+
+    fn example(name: &str, ext: &str) -> String {
+        let path = format!("{}/../../examples/{name}.{ext}", env!("CARGO_MANIFEST_DIR"));
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn expected_failure(name: &str) -> Option<&'static str> {
+        FAILING
+            .iter()
+            .find(|(failing, _)| *failing == name)
+            .map(|(_, message)| *message)
+    }
+
+    #[test]
+    fn test_libraries_check() {
+        for name in LIBRARIES {
+            assert_eq!(
+                check_src(&example(name, "gib")),
+                Vec::<String>::new(),
+                "{name} has errors"
+            );
+        }
+    }
+
+    #[test]
+    fn test_examples_print_expected_output() {
+        for name in EXAMPLES {
+            let file = build_src(&example(name, "gib"));
+            let mut prog = ProgramState::new(
+                &file.funcs,
+                file.entry,
+                file.tables.clone(),
+                file.file_names.clone(),
+                file.type_names.clone(),
+            );
+            prog.captured = Some(String::new());
+            let res = catch_unwind(AssertUnwindSafe(|| prog.run()));
+            match (res, expected_failure(name)) {
+                (Ok(()), None) => {}
+                (Err(err), Some(message)) => {
+                    let err = err.downcast::<String>().unwrap();
+                    assert_eq!(
+                        err.lines().next(),
+                        Some(message),
+                        "{name} failed differently"
+                    );
+                }
+                (Ok(()), Some(_)) => panic!("{name} should have failed"),
+                (Err(_), None) => panic!("{name} failed"),
+            }
+            assert_eq!(
+                prog.captured.unwrap(),
+                example(name, "out"),
+                "{name} printed something else"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compiled_examples_print_expected_output() {
+        for name in EXAMPLES {
+            let file = build_src(&example(name, "gib"));
+            let exe = env::temp_dir().join(format!("gib-{name}-{}", process::id()));
+            match compile_c(&file, &exe) {
+                Err(CompileError::Spawn { cc, .. }) => {
+                    eprintln!("Skipping, no C compiler ('{cc}')");
+                    return;
+                }
+                // Threads and generators only run on the VM
+                Err(CompileError::Unsupported { .. }) => continue,
+                res => res.unwrap(),
             }
+            let output = Command::new(&exe).output().unwrap();
+            fs::remove_file(&exe).unwrap();
+            assert_eq!(
+                output.status.success(),
+                expected_failure(name).is_none(),
+                "{name} exited differently when compiled"
+            );
+            assert_eq!(
+                String::from_utf8(output.stdout).unwrap(),
+                example(name, "out"),
+                "{name} printed something else when compiled"
+            );
         }
     }
 }
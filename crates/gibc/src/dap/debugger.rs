@@ -12,7 +12,7 @@ use dap::{
 };
 use gvm::{
     format::{func::FuncDef, ByteCodeFile},
-    vm::{scope::Scope, state::ProgramState},
+    vm::state::ProgramState,
 };
 
 pub struct Debugger<'code> {
@@ -36,8 +36,8 @@ impl<'code> Debugger<'code> {
             breakpoints: HashMap::new(),
             paused: true,
         };
-        let main = res.state.funcs.get(&0).expect("No main function");
-        res.state.scopes.push(Scope::from_code(&main.body, 0));
+        let main = res.state.entry_scope();
+        res.state.scopes.push(main);
         res
     }
 
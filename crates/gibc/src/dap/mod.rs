@@ -36,8 +36,10 @@ pub fn start_dap(path: &Path) -> DynResult<()> {
     let output = server.output.clone();
     let prog = ProgramState::new(
         &bytecode.funcs,
+        bytecode.entry,
         bytecode.tables.clone(),
         bytecode.file_names.clone(),
+        bytecode.type_names.clone(),
     );
     let mut dbg = Debugger::new(prog, output);
     loop {
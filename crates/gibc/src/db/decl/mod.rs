@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use func::Function;
+use gvm::format::literal::Literal;
 use impl_::ImplForDecl;
 use salsa::Update;
 use struct_::StructDecl;
@@ -48,6 +49,12 @@ pub struct Decl<'db> {
     pub kind: DeclKind<'db>,
     pub maybe_file: Option<SourceFile>,
     pub path: ModulePath<'db>,
+    /// The text of the `///` comments above the declaration
+    #[return_ref]
+    pub doc: Option<String>,
+    /// The docs of the struct's or member's fields, by field name
+    #[return_ref]
+    pub field_docs: HashMap<String, String>,
 }
 
 #[derive(Update, Debug, Clone, PartialEq)]
@@ -69,6 +76,16 @@ pub enum DeclKind<'db> {
     },
     Function(Function<'db>),
     Module(Vec<Decl<'db>>),
+    /// A `type` alias, with the type it expands to
+    TypeAlias {
+        generics: Vec<Generic<'db>>,
+        ty: Ty<'db>,
+    },
+    /// A `const`, with its folded value, or `None` if it couldn't be folded
+    Const {
+        ty: Ty<'db>,
+        value: Option<Literal>,
+    },
 }
 
 impl<'db> Vfs {
@@ -88,7 +105,9 @@ impl<'db> Decl<'db> {
             DeclKind::Struct { generics, .. }
             | DeclKind::Trait { generics, .. }
             | DeclKind::Enum { generics, .. }
+            | DeclKind::TypeAlias { generics, .. }
             | DeclKind::Function(Function { generics, .. }) => generics.clone(),
+            DeclKind::Const { .. } => vec![],
             DeclKind::Member { .. } | DeclKind::Module(_) => {
                 panic!("Generics not supported for this decl kind")
             }
@@ -117,6 +136,8 @@ impl<'db> Decl<'db> {
             }
             DeclKind::Function(f) => Ty::Function(f.get_ty()),
             DeclKind::Module(_) => Ty::unit(),
+            DeclKind::TypeAlias { ty, .. } => Ty::Meta(Box::new(ty.clone())),
+            DeclKind::Const { ty, .. } => ty.clone(),
         }
     }
 
@@ -250,6 +271,8 @@ impl<'db> Decl<'db> {
             DeclKind::Member { .. } => TokenKind::Member,
             DeclKind::Struct { .. } => TokenKind::Struct,
             DeclKind::Module(_) => TokenKind::Module,
+            DeclKind::TypeAlias { .. } => TokenKind::TypeAlias,
+            DeclKind::Const { .. } => TokenKind::Const,
         }
     }
 }
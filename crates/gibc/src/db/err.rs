@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::util::Span;
+use crate::util::{Span, Spanned};
 
 use super::input::SourceFile;
 
@@ -12,6 +12,17 @@ pub struct Diagnostic {
     pub level: Level,
     pub path: PathBuf,
     pub file: SourceFile,
+    /// Other places in the file the diagnostic refers to, like a declaration
+    pub related: Vec<Spanned<String>>,
+    /// An edit that fixes the problem, offered by the LSP as a quick-fix
+    pub fix: Option<Fix>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Fix {
+    pub title: String,
+    pub span: Span,
+    pub text: String,
 }
 
 #[derive(Clone, Debug)]
@@ -124,6 +124,7 @@ impl Vfs {
                 struct Int
                 struct Float
                 struct Bool
+                struct Char
                 struct String
                 struct Any
 
@@ -134,6 +135,71 @@ impl Vfs {
                     print("\n")
                 }
 
+                fn parse_int(text: String): Option[Int]
+                fn parse_float(text: String): Option[Float]
+
+                fn read_line(): Option[String]
+                fn time(): Int
+                fn random(): Int
+                fn args(): Vec[String]
+
+                impl Int {
+                    fn Self.abs(): Int
+                    fn Self.pow(exp: Int): Int
+                    fn Self.min(other: Int): Int
+                    fn Self.max(other: Int): Int
+                    fn Self.to_float(): Float
+                }
+
+                impl Float {
+                    fn Self.sqrt(): Float
+                    fn Self.pow(exp: Float): Float
+                    fn Self.abs(): Float
+                    fn Self.floor(): Float
+                    fn Self.min(other: Float): Float
+                    fn Self.max(other: Float): Float
+                    fn Self.to_int(): Int
+                }
+
+                trait ToString {
+                    fn Self.to_string(): String
+                }
+
+                impl ToString for Int {
+                    fn Self.to_string(): String
+                }
+
+                impl ToString for Float {
+                    fn Self.to_string(): String
+                }
+
+                impl ToString for Bool {
+                    fn Self.to_string(): String
+                }
+
+                impl ToString for Char {
+                    fn Self.to_string(): String
+                }
+
+                impl ToString for String {
+                    fn Self.to_string(): String
+                }
+
+                trait Task {
+                    fn Self.run()
+                }
+
+                fn spawn(task: Task)
+
+                struct Channel[T]
+
+                fn channel[T](): Channel[T]
+
+                impl[T] Channel[T] {
+                    fn Self.send(value: T)
+                    fn Self.receive(): T
+                }
+
                 trait Iterator[T] {
                     fn Self.next(): Option[T]
                 }
@@ -142,6 +208,18 @@ impl Vfs {
                     fn Self.iter(): Iterator[T]
                 }
 
+                struct Generator[T]
+
+                impl[T] Iterator[T] for Generator[T] {
+                    fn Self.next(): Option[T]
+                }
+
+                struct Range(Int, Int, Bool)
+
+                impl Iterator[Int] for Range {
+                    fn Self.next(): Option[Int]
+                }
+
 
                 struct Vec[T]
 
@@ -156,6 +234,22 @@ impl Vfs {
                     fn Self.len(): Int
                 }
 
+                trait Index[I, T] {
+                    fn Self.index(index: I): T
+                }
+
+                trait IndexMut[I, T] {
+                    fn Self.set_index(index: I, value: T)
+                }
+
+                impl[T] Index[Int, T] for Vec[T] {
+                    fn Self.index(index: Int): T
+                }
+
+                impl[T] IndexMut[Int, T] for Vec[T] {
+                    fn Self.set_index(index: Int, value: T)
+                }
+
                 enum Option[T] {
                     Some(T),
                     None
@@ -165,6 +259,10 @@ impl Vfs {
                     Ok(R),
                     Err(E),
                 }
+
+                trait From[T] {
+                    fn from(value: T): Self
+                }
                 "#
                 .to_string(),
                 vec!["std".to_string()],
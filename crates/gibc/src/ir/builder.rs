@@ -1,7 +1,10 @@
 use chumsky::span::Span as _;
 use gvm::format::instr::ByteCode;
 
-use crate::{range::offset_to_position_str, util::Span};
+use crate::{
+    range::offset_to_position_str,
+    util::{Span, Spanned},
+};
 
 pub enum ByteCodeNode {
     Code(Vec<ByteCode>),
@@ -11,20 +14,36 @@ pub enum ByteCodeNode {
         else_: Option<Box<ByteCodeNode>>,
     },
     While(Box<ByteCodeNode>, Box<ByteCodeNode>),
+    /// A loop that `Break` and `Continue` can target by its label, from within nested loops
+    Labelled(String, Box<ByteCodeNode>),
     Spanned(Box<ByteCodeNode>, Span),
     MaybeBreak,
-    Continue,
-    Break,
+    /// Jumps to the start of the labelled loop, or the innermost one
+    Continue(Option<String>),
+    /// Jumps past the end of the labelled loop, or the innermost one
+    Break(Option<String>),
     Next,
 }
 
+/// The break and continue targets of each labelled loop being built, innermost last
+type Labels = [(String, u32, u32)];
+
 impl ByteCodeNode {
+    /// Lets `Break` and `Continue` target this loop by its label, if it has one
+    pub fn labelled(self, label: Option<&Spanned<String>>) -> Self {
+        match label {
+            Some((label, _)) => ByteCodeNode::Labelled(label.clone(), Box::new(self)),
+            None => self,
+        }
+    }
+
     pub fn build(
         self,
         top: u32,
         break_: u32,
         continue_: u32,
         next: u32,
+        labels: &Labels,
         marks: &mut Vec<(usize, (u16, u16))>,
         text: &str,
     ) -> Vec<ByteCode> {
@@ -35,7 +54,7 @@ impl ByteCodeNode {
                 let mut found = vec![];
                 let mut top = top;
                 for stmt in block {
-                    let code = stmt.build(top, break_, continue_, next, marks, text);
+                    let code = stmt.build(top, break_, continue_, next, labels, marks, text);
                     top += code.len() as u32;
                     found.extend(code);
                 }
@@ -53,9 +72,9 @@ impl ByteCodeNode {
                     } else {
                         top + cond.len() + then.len() + 1
                     };
-                    let mut code = cond.build(top, break_, continue_, next, marks, text);
+                    let mut code = cond.build(top, break_, continue_, next, labels, marks, text);
                     top += code.len() as u32;
-                    let body = then.build(top, break_, continue_, next, marks, text);
+                    let body = then.build(top, break_, continue_, next, labels, marks, text);
                     top += body.len() as u32;
                     code.extend(body);
                     if !is_last_branch {
@@ -65,22 +84,43 @@ impl ByteCodeNode {
                     found.extend(code);
                 }
                 if let Some(else_) = else_ {
-                    found.extend(else_.build(top, break_, continue_, next, marks, text));
+                    found.extend(else_.build(top, break_, continue_, next, labels, marks, text));
                 }
                 found
             }
             ByteCodeNode::While(cond, then) => {
                 let new_break = top + len;
-                let mut code = cond.build(top, new_break, top, new_break, marks, text);
-                code.extend(then.build(top + code.len() as u32, new_break, top, next, marks, text));
+                let mut code = cond.build(top, new_break, top, new_break, labels, marks, text);
+                code.extend(then.build(
+                    top + code.len() as u32,
+                    new_break,
+                    top,
+                    next,
+                    labels,
+                    marks,
+                    text,
+                ));
                 code
             }
-            ByteCodeNode::Break => vec![ByteCode::Jmp(break_)],
+            ByteCodeNode::Labelled(label, inner) => {
+                let mut labels = labels.to_vec();
+                labels.push((label, top + len, top));
+                inner.build(top, break_, continue_, next, &labels, marks, text)
+            }
+            ByteCodeNode::Break(label) => {
+                vec![ByteCode::Jmp(
+                    label.map_or(break_, |label| find_label(labels, &label).0),
+                )]
+            }
             ByteCodeNode::MaybeBreak => vec![ByteCode::Jne(break_)],
-            ByteCodeNode::Continue => vec![ByteCode::Jmp(continue_)],
+            ByteCodeNode::Continue(label) => {
+                vec![ByteCode::Jmp(
+                    label.map_or(continue_, |label| find_label(labels, &label).1),
+                )]
+            }
             ByteCodeNode::Next => vec![ByteCode::Jne(next)],
             ByteCodeNode::Spanned(inner, span) => {
-                let code = inner.build(top, break_, continue_, next, marks, text);
+                let code = inner.build(top, break_, continue_, next, labels, marks, text);
                 let pos = offset_to_position_str(span.start(), text);
                 marks.push((top as usize, (pos.line as u16 + 1, pos.character as u16)));
                 code
@@ -104,11 +144,70 @@ impl ByteCodeNode {
                 }
             }
             ByteCodeNode::While(cond, then) => cond.len() + then.len(),
-            ByteCodeNode::Break
+            ByteCodeNode::Break(_)
             | ByteCodeNode::Next
             | ByteCodeNode::MaybeBreak
-            | ByteCodeNode::Continue => 1,
-            ByteCodeNode::Spanned(inner, _) => inner.len(),
+            | ByteCodeNode::Continue(_) => 1,
+            ByteCodeNode::Spanned(inner, _) | ByteCodeNode::Labelled(_, inner) => inner.len(),
         }
     }
 }
+
+/// The break and continue targets of the innermost loop with the label, which is known to exist
+/// once the labels have been checked
+fn find_label(labels: &Labels, label: &str) -> (u32, u32) {
+    let (_, break_, continue_) = labels
+        .iter()
+        .rev()
+        .find(|(name, _, _)| name == label)
+        .expect("Label should have been checked");
+    (*break_, *continue_)
+}
+
+/// Turns calls whose result is returned straight away, possibly after following some jumps,
+/// into tail calls so the VM can reuse the caller's frame
+pub fn mark_tail_calls(code: &mut [ByteCode]) {
+    for index in 0..code.len() {
+        let mut next = index + 1;
+        // A jump can't be followed more often than there are instructions, unless it loops
+        for _ in 0..code.len() {
+            let Some(ByteCode::Jmp(target)) = code.get(next) else {
+                break;
+            };
+            next = *target as usize;
+        }
+        if code.get(next) != Some(&ByteCode::Return) {
+            continue;
+        }
+        code[index] = match code[index] {
+            ByteCode::Call(id) => ByteCode::TailCall(id),
+            ByteCode::DynCall(id) => ByteCode::TailDynCall(id),
+            _ => continue,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gvm::format::instr::ByteCode;
+
+    use super::mark_tail_calls;
+
+    #[test]
+    fn test_mark_tail_calls() {
+        let mut code = vec![
+            ByteCode::Param(0),
+            ByteCode::Jne(5),
+            ByteCode::Call(1),
+            ByteCode::Call(2),
+            ByteCode::Jmp(7),
+            ByteCode::DynCall(3),
+            ByteCode::Jmp(7),
+            ByteCode::Return,
+        ];
+        mark_tail_calls(&mut code);
+        assert_eq!(code[2], ByteCode::Call(1));
+        assert_eq!(code[3], ByteCode::TailCall(2));
+        assert_eq!(code[5], ByteCode::TailDynCall(3));
+    }
+}
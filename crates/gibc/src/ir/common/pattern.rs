@@ -2,7 +2,6 @@ use std::collections::HashMap;
 
 use async_lsp::lsp_types::CompletionItem;
 use gvm::format::{instr::ByteCode, literal::Literal};
-use salsa::plumbing::AsId as _;
 
 use crate::{
     check::{
@@ -74,6 +73,7 @@ impl<'db> Pattern {
                     ty: Ty::Unknown,
                     kind: TokenKind::Var,
                     span: name.1,
+                    mutability: state.binding,
                 };
                 state.insert_variable(&name.0, var);
                 PatternIR::Name(name.clone())
@@ -114,6 +114,7 @@ impl<'db> Pattern {
                 ty: ty.clone(),
                 kind: TokenKind::Var,
                 span: name.1,
+                mutability: state.binding,
             };
             state.insert_variable(&name.0, var);
             return PatternIR::Name(name.clone());
@@ -273,6 +274,7 @@ impl<'db> StructFieldPattern {
                         ty: ty.clone(),
                         kind: TokenKind::Var,
                         span: name.1,
+                        mutability: state.binding,
                     };
                     state.insert_variable(&name.0, var);
                 } else {
@@ -484,7 +486,7 @@ impl<'db> PatternIR<'db> {
             _ => unreachable!(),
         };
 
-        let id = decl.as_id().as_u32();
+        let id = state.get_type_id(decl);
         let mut code = vec![];
         code.push(ByteCodeNode::Code(vec![
             ByteCode::Copy,
@@ -1,5 +1,4 @@
 use gvm::format::instr::ByteCode;
-use salsa::plumbing::AsId;
 
 use crate::{
     check::{build_state::BuildState, scoped_state::Scoped as _, SemanticToken},
@@ -163,11 +162,11 @@ impl<'db> CallIR<'db> {
                 IdentDef::Generic(_) => todo!(),
                 IdentDef::Decl(decl) => match decl.kind(state.db) {
                     DeclKind::Function(_) => code.push(ByteCodeNode::Code(vec![ByteCode::Call(
-                        decl.as_id().as_u32(),
+                        state.get_func_id(decl),
                     )])),
                     DeclKind::Struct { body, .. } | DeclKind::Member { body, .. } => {
                         code.push(ByteCodeNode::Code(vec![ByteCode::Construct {
-                            id: decl.as_id().as_u32(),
+                            id: state.get_type_id(decl),
                             len: body.arg_count(),
                         }]));
                     }
@@ -144,6 +144,19 @@ impl<'db> IrNode<'db> for FieldIR<'db> {
         });
     }
 
+    fn hover(&self, _: usize, state: &mut IrState<'db>) -> Option<String> {
+        let decl = self.decl?;
+        let hover = format!(
+            "field {}::{}",
+            decl.path(state.db).name(state.db).join("::"),
+            self.name.0
+        );
+        Some(match decl.field_docs(state.db).get(&self.name.0) {
+            Some(doc) => format!("{hover}\n\n{doc}"),
+            None => hover,
+        })
+    }
+
     fn debug_name(&self) -> &'static str {
         "FieldIR"
     }
@@ -1,5 +1,4 @@
-use gvm::format::instr::ByteCode;
-use salsa::plumbing::AsId;
+use gvm::format::{instr::ByteCode, literal::Literal};
 
 use crate::{
     check::{build_state::BuildState, scoped_state::Scoped as _, state::CheckState},
@@ -16,11 +15,13 @@ use crate::{
 
 use super::{
     block::{check_block, CodeBlockIR},
+    range::{build_advance, build_has_next, RangeIR},
     ExprIR, ExprIRData,
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ForIR<'db> {
+    pub label: Option<Spanned<String>>,
     pub pattern: Box<Spanned<PatternIR<'db>>>,
     pub expr: Box<Spanned<ExprIR<'db>>>,
     pub block: Spanned<CodeBlockIR<'db>>,
@@ -32,7 +33,17 @@ impl<'db> For {
     pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
         let expr = self.expr.0.check(state);
 
-        let iter_decl = if let Some((IdentDef::Decl(decl), _)) = expr
+        let is_iter = expr
+            .ty
+            .imply_named_sub_ty(
+                ModulePath::new(state.db, vec!["std".to_string(), "Iterator".to_string()]),
+                state,
+            )
+            .is_some();
+
+        let iter_decl = if is_iter {
+            None
+        } else if let Some((IdentDef::Decl(decl), _)) = expr
             .ty
             .get_member_func(&("iter".to_string(), self.expr.1), state)
         {
@@ -41,7 +52,9 @@ impl<'db> For {
             None
         };
 
-        let iter_ty = if let Some(into_iter_ty) = expr.ty.imply_named_sub_ty(
+        let iter_ty = if is_iter {
+            expr.ty.clone()
+        } else if let Some(into_iter_ty) = expr.ty.imply_named_sub_ty(
             ModulePath::new(state.db, vec!["std".to_string(), "IntoIter".to_string()]),
             state,
         ) {
@@ -99,6 +112,7 @@ impl<'db> For {
         } else {
             self.pattern.0.expect(state, &item_ty)
         };
+        state.enter_loop(self.label.as_ref(), false);
         let ExprIR {
             data: ExprIRData::CodeBlock(block),
             ..
@@ -106,9 +120,11 @@ impl<'db> For {
         else {
             panic!("Expected block")
         };
+        state.exit_loop();
         let block = (block, self.block.1);
         ExprIR {
             data: ExprIRData::For(ForIR {
+                label: self.label.clone(),
                 pattern: Box::new((pattern, self.pattern.1)),
                 expr: Box::new((expr, self.expr.1)),
                 block,
@@ -152,40 +168,39 @@ impl<'db> IrNode<'db> for ForIR<'db> {
 
 impl<'db> ForIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        if let ExprIRData::Range(range) = &self.expr.0.data {
+            return self.build_range(range, state);
+        }
         let expr = self.expr.0.build(state);
-        let iter_id = self.iter_decl.unwrap().as_id().as_u32();
-        let next_id = self.next_decl.unwrap().as_id().as_u32();
-        let some_id = state
-            .project
-            .get_decl(
-                state.db,
-                ModulePath::new(
-                    state.db,
-                    vec!["std".to_string(), "Option".to_string(), "Some".to_string()],
-                ),
-            )
-            .unwrap();
-        let iter = if self.expr.0.ty.is_dyn(state.db, state.project) {
-            ByteCode::DynCall(iter_id)
-        } else {
-            ByteCode::Call(iter_id)
-        };
-        let next = if matches!(
-            self.iter_decl.unwrap().kind(state.db),
-            DeclKind::Trait { .. }
-        ) {
-            ByteCode::DynCall(iter_id)
+        let next_id = state.get_func_id(self.next_decl.unwrap());
+        let some_id = state.get_std_type_id(&["Option", "Some"]);
+        let is_dyn = self.expr.0.ty.is_dyn(state.db, state.project);
+        // Iterators such as generators are looped over directly, without calling 'iter'
+        let (create_iter, next) = if let Some(iter_decl) = self.iter_decl {
+            let iter_id = state.get_func_id(iter_decl);
+            let iter = if is_dyn {
+                ByteCode::DynCall(iter_id)
+            } else {
+                ByteCode::Call(iter_id)
+            };
+            let next = if matches!(iter_decl.kind(state.db), DeclKind::Trait { .. }) {
+                ByteCode::DynCall(iter_id)
+            } else {
+                ByteCode::Call(next_id)
+            };
+            (vec![expr, ByteCodeNode::Code(vec![iter])], next)
+        } else if is_dyn {
+            (vec![expr], ByteCode::DynCall(next_id))
         } else {
-            ByteCode::Call(next_id)
+            (vec![expr], ByteCode::Call(next_id))
         };
-        let create_iter = vec![expr, ByteCodeNode::Code(vec![iter])];
 
         let cond = ByteCodeNode::Block(vec![
             ByteCodeNode::Code(vec![
                 ByteCode::Copy,
                 next,
                 ByteCode::Copy,
-                ByteCode::Match(some_id.as_id().as_u32()),
+                ByteCode::Match(some_id),
             ]),
             ByteCodeNode::MaybeBreak,
         ]);
@@ -194,11 +209,74 @@ impl<'db> ForIR<'db> {
             ByteCodeNode::Code(vec![ByteCode::Index(0)]),
             self.pattern.0.build(state),
             self.block.0.build(state),
-            ByteCodeNode::Continue,
+            ByteCodeNode::Continue(None),
         ]);
         ByteCodeNode::Block(vec![
             ByteCodeNode::Block(create_iter),
-            ByteCodeNode::While(Box::new(cond), Box::new(then)),
+            ByteCodeNode::While(Box::new(cond), Box::new(then)).labelled(self.label.as_ref()),
         ])
     }
+
+    /// Counts through a range literal with locals, instead of constructing a `Range`
+    fn build_range(&self, range: &RangeIR<'db>, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let start = range.start.0.build(state);
+        let end = range.end.0.build(state);
+        // The names can't clash with user variables, as they aren't valid identifiers
+        let next_id = state.add_var("$next".to_string());
+        let end_id = state.add_var("$end".to_string());
+        let get_next = [ByteCode::GetLocal(next_id)];
+        let get_end = [ByteCode::GetLocal(end_id)];
+        let increment = vec![
+            ByteCode::GetLocal(next_id),
+            ByteCode::Push(Literal::Int(1)),
+            ByteCode::Add,
+            ByteCode::SetLocal(next_id),
+        ];
+
+        let mut locals = vec![
+            start,
+            ByteCodeNode::Code(vec![ByteCode::NewLocal(next_id)]),
+            end,
+            ByteCodeNode::Code(vec![ByteCode::NewLocal(end_id)]),
+        ];
+        let (has_next, advance) = if range.inclusive {
+            let inclusive_id = state.add_var("$inclusive".to_string());
+            locals.push(ByteCodeNode::Code(vec![
+                ByteCode::Push(Literal::Bool(true)),
+                ByteCode::NewLocal(inclusive_id),
+            ]));
+            let has_next = build_has_next(&get_next, &get_end, &[ByteCode::GetLocal(inclusive_id)]);
+            let exclude_end = vec![
+                ByteCode::Push(Literal::Bool(false)),
+                ByteCode::SetLocal(inclusive_id),
+            ];
+            (
+                has_next,
+                build_advance(&get_next, &get_end, increment, exclude_end),
+            )
+        } else {
+            let has_next = vec![
+                ByteCode::GetLocal(next_id),
+                ByteCode::GetLocal(end_id),
+                ByteCode::Lt,
+            ];
+            (has_next, ByteCodeNode::Code(increment))
+        };
+
+        let cond =
+            ByteCodeNode::Block(vec![ByteCodeNode::Code(has_next), ByteCodeNode::MaybeBreak]);
+
+        // The counter moves on before the body runs, so 'continue' can't skip it
+        let then = ByteCodeNode::Block(vec![
+            ByteCodeNode::Code(get_next.to_vec()),
+            self.pattern.0.build(state),
+            advance,
+            self.block.0.build(state),
+            ByteCodeNode::Continue(None),
+        ]);
+        locals.push(
+            ByteCodeNode::While(Box::new(cond), Box::new(then)).labelled(self.label.as_ref()),
+        );
+        ByteCodeNode::Block(locals)
+    }
 }
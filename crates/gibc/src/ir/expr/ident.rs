@@ -280,7 +280,37 @@ impl<'db> IdentDef<'db> {
         match self {
             IdentDef::Variable(var) => format!("{}: {}", var.name, var.ty.get_ir_name(state)),
             IdentDef::Decl(decl) => {
-                format!("{} {}", decl.get_kind_name(state.db), decl.name(state.db))
+                if let DeclKind::TypeAlias { generics, ty } = decl.kind(state.db) {
+                    // Aliases show what they expand to, as that's what they're checked as
+                    let generics = if generics.is_empty() {
+                        String::new()
+                    } else {
+                        let generics = generics
+                            .iter()
+                            .map(|generic| generic.get_ir_name(state))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        format!("[{generics}]")
+                    };
+                    let alias = format!(
+                        "type {}{generics} = {}",
+                        decl.name(state.db),
+                        ty.get_ir_name(state)
+                    );
+                    return decl.with_doc(state.db, alias);
+                }
+                if let DeclKind::Const { ty, value } = decl.kind(state.db) {
+                    let mut const_ =
+                        format!("const {}: {}", decl.name(state.db), ty.get_ir_name(state));
+                    if let Some(value) = value {
+                        const_ = format!("{const_} = {value}");
+                    }
+                    return decl.with_doc(state.db, const_);
+                }
+                decl.with_doc(
+                    state.db,
+                    format!("{} {}", decl.get_kind_name(state.db), decl.name(state.db)),
+                )
             }
             IdentDef::Generic(generic) => {
                 format!("{}: {}", generic.name.0, generic.super_.get_ir_name(state))
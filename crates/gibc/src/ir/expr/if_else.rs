@@ -1,7 +1,9 @@
-use gvm::format::instr::ByteCode;
-
 use crate::{
-    check::{build_state::BuildState, scoped_state::Scoped, state::CheckState},
+    check::{
+        build_state::BuildState,
+        scoped_state::{Scope, Scoped},
+        state::CheckState,
+    },
     ir::{builder::ByteCodeNode, common::condition::ConditionIR, ContainsOffset as _, IrNode},
     parser::expr::if_else::{IfBranch, IfElse},
     ty::Ty,
@@ -23,6 +25,8 @@ pub struct IfElseIR<'db> {
 pub struct IfBranchIR<'db> {
     pub condition: Spanned<ConditionIR<'db>>,
     pub body: Spanned<CodeBlockIR<'db>>,
+    /// Holds the variables bound by an `if let`, which only exist in this branch
+    pub scope: Scope<'db>,
 }
 
 impl<'db> IfElse {
@@ -96,6 +100,7 @@ impl<'db> IfElse {
 
 impl<'db> IfBranch {
     pub fn check(&self, state: &mut CheckState<'db>) -> IfBranchIR<'db> {
+        state.enter_scope();
         let condition = self.condition.0.check(state, self.condition.1);
         let condition = (condition, self.condition.1);
         let ExprIR {
@@ -106,10 +111,16 @@ impl<'db> IfBranch {
             panic!("Expected code block...")
         };
         let body = (body, self.body.1);
-        IfBranchIR { condition, body }
+        let scope = state.exit_scope();
+        IfBranchIR {
+            condition,
+            body,
+            scope,
+        }
     }
 
     pub fn expect(&self, state: &mut CheckState<'db>, expected: &Ty<'db>) -> IfBranchIR<'db> {
+        state.enter_scope();
         let condition = self.condition.0.check(state, self.condition.1);
         let condition = (condition, self.condition.1);
         let ExprIR {
@@ -120,7 +131,12 @@ impl<'db> IfBranch {
             panic!("Expected code block...")
         };
         let body = (body, self.body.1);
-        IfBranchIR { condition, body }
+        let scope = state.exit_scope();
+        IfBranchIR {
+            condition,
+            body,
+            scope,
+        }
     }
 }
 
@@ -158,6 +174,7 @@ impl<'db> IrNode<'db> for IfElseIR<'db> {
 }
 impl<'db> IrNode<'db> for IfBranchIR<'db> {
     fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        state.push_scope(self.scope.clone());
         if self.condition.1.contains_offset(offset) {
             return self.condition.0.at_offset(offset, state);
         }
@@ -192,17 +209,16 @@ impl<'db> IfElseIR<'db> {
 
 impl<'db> IfBranchIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> (Box<ByteCodeNode>, Box<ByteCodeNode>) {
-        match &self.condition.0 {
+        state.enter_scope();
+        let branch = match &self.condition.0 {
+            // The matched value is left for the pattern to take apart, like a match arm
             ConditionIR::Let(let_) => {
                 let expr = let_.expr.0.build(state);
-                let then = let_.pattern.0.build(state);
-                let cond = let_.pattern.0.build_match(state);
-                let cond = if then.len() == 0 {
-                    ByteCodeNode::Block(vec![expr, cond])
-                } else {
-                    ByteCodeNode::Block(vec![expr, ByteCodeNode::Code(vec![ByteCode::Copy]), cond])
-                };
-                let then = ByteCodeNode::Block(vec![then, self.body.0.build(state)]);
+                let cond = ByteCodeNode::Block(vec![expr, let_.pattern.0.build_match(state)]);
+                let then = ByteCodeNode::Block(vec![
+                    let_.pattern.0.build(state),
+                    self.body.0.build(state),
+                ]);
                 (Box::new(cond), Box::new(then))
             }
             ConditionIR::Expr(e) => {
@@ -210,6 +226,83 @@ impl<'db> IfBranchIR<'db> {
                 let then = ByteCodeNode::Block(vec![self.body.0.build(state)]);
                 (Box::new(cond), Box::new(then))
             }
-        }
+        };
+        state.exit_scope();
+        branch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::{check_src, run_src};
+
+    #[test]
+    fn test_if_let_bindings_stay_in_branch() {
+        let errors = check_src(
+            "use std::Int
+use std::Option
+
+fn main() {
+    let found: Option[Int] = Option::Some(1)
+    if let Option::Some(n) = found {
+        let inside: Int = n
+    } else {
+        let other = n
+    }
+    let after = n
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Unresolved name `n`".to_string(),
+                "Unresolved name `n`".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_if_let_shadows_in_branch_only() {
+        let errors = check_src(
+            "use std::Int
+use std::Bool
+use std::Option
+
+fn main() {
+    let n = true
+    let found: Option[Int] = Option::Some(1)
+    if let Option::Some(n) = found {
+        let inside: Int = n
+    }
+    let after: Bool = n
+}
+",
+        );
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_if_let_runs_matching_branch() {
+        let output = run_src(
+            "use std::Int
+use std::Option
+use std::println
+
+fn show(found: Option[Int]) {
+    if let Option::Some(n) = found {
+        println(n + 1)
+    } else {
+        println(\"Nothing\")
+    }
+}
+
+fn main() {
+    show(Option::Some(41))
+    show(Option::None)
+}
+",
+        );
+        assert_eq!(output, "42\nNothing\n");
     }
 }
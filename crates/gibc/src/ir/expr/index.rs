@@ -0,0 +1,314 @@
+use gvm::format::instr::ByteCode;
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState, SemanticToken},
+    db::{
+        decl::{func::Function, Decl, DeclKind},
+        path::ModulePath,
+    },
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode, IrState},
+    item::definitions::ident::IdentDef,
+    parser::expr::{index::Index, op::OpKind},
+    ty::{sub_tys::path_to_sub_ty, FuncTy, Generic, Named, Ty},
+    util::{Span, Spanned},
+};
+
+use super::{ExprIR, ExprIRData};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IndexIR<'db> {
+    pub expr: Box<Spanned<ExprIR<'db>>>,
+    pub index: Box<Spanned<ExprIR<'db>>>,
+    /// The `Index::index` reading the item, or `IndexMut::set_index` when it's assigned to
+    pub func: Option<Decl<'db>>,
+    /// The `Index::index` reading the item before a compound assignment stores it
+    pub get: Option<Decl<'db>>,
+}
+
+impl<'db> Index {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let (ir, _, ret) = self.check_func(state, "index", "Index", 1);
+        ExprIR { ty: ret, ..ir }
+    }
+
+    pub fn expect(
+        &self,
+        state: &mut CheckState<'db>,
+        expected: &Ty<'db>,
+        span: Span,
+    ) -> ExprIR<'db> {
+        let ir = self.check(state);
+        ir.ty.expect_is_instance_of(expected, state, span);
+        ir
+    }
+
+    /// Checks `expr[index]` as the target of an assignment, typed as the value it takes
+    pub fn check_assign(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let (ir, args, _) = self.check_func(state, "set_index", "IndexMut", 2);
+        let ty = args[1].clone();
+        ExprIR { ty, ..ir }
+    }
+
+    /// Checks `expr[index]` as the target of a compound assignment, which reads the item too
+    pub fn check_compound_assign(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let mut ir = self.check_assign(state);
+        let ExprIRData::Index(index) = &mut ir.data else {
+            unreachable!()
+        };
+        if index.func.is_some() {
+            let ty = index.expr.0.ty.clone();
+            index.get = find_index_func(&ty, "index", "Index", 1, state, self.expr.1)
+                .map(|(decl, _, _)| decl);
+        }
+        ir
+    }
+
+    fn check_func(
+        &self,
+        state: &mut CheckState<'db>,
+        name: &str,
+        trait_: &str,
+        arity: usize,
+    ) -> (ExprIR<'db>, Vec<Ty<'db>>, Ty<'db>) {
+        let expr = self.expr.0.check(state);
+        let (func, args, ret) =
+            match find_index_func(&expr.ty, name, trait_, arity, state, self.expr.1) {
+                Some((decl, args, ret)) => (Some(decl), args, ret),
+                None => (None, vec![Ty::Unknown; arity], Ty::Unknown),
+            };
+        let index = self.index.0.expect(state, &args[0], self.index.1);
+        let ir = ExprIR {
+            data: ExprIRData::Index(IndexIR {
+                expr: Box::new((expr, self.expr.1)),
+                index: Box::new((index, self.index.1)),
+                func,
+                get: None,
+            }),
+            ty: Ty::Unknown,
+            order: state.inc_order(),
+        };
+        (ir, args, ret)
+    }
+}
+
+/// Finds the function `trait_` indexes `ty` with, along with its argument and return types,
+/// reporting an error if `ty` doesn't implement the trait
+fn find_index_func<'db>(
+    ty: &Ty<'db>,
+    name: &str,
+    trait_: &str,
+    arity: usize,
+    state: &mut CheckState<'db>,
+    span: Span,
+) -> Option<(Decl<'db>, Vec<Ty<'db>>, Ty<'db>)> {
+    let resolved = state.resolved_ty(ty);
+    let ty_name = match &resolved {
+        Ty::Unknown | Ty::Nothing => return None,
+        Ty::Named(Named { name, .. }) => Some(*name),
+        Ty::Generic(Generic { super_, .. }) => match super_.as_ref() {
+            Ty::Named(Named { name, .. }) => Some(*name),
+            _ => None,
+        },
+        _ => None,
+    };
+    let trait_path = ModulePath::new(state.db, vec!["std".to_string(), trait_.to_string()]);
+    let found = match ty_name.and_then(|ty_name| path_to_sub_ty(ty_name, trait_path, state)) {
+        Some(path) => match ty.get_member_func(&(name.to_string(), span), state) {
+            Some((IdentDef::Decl(decl), FuncTy { args, ret, .. })) => {
+                // Implemented by an impl, so it's that impl's function rather than one that happens
+                // to have the same name
+                let decl = path
+                    .last()
+                    .and_then(|impl_| {
+                        impl_
+                            .functions(state.db)
+                            .iter()
+                            .find(|func| func.name(state.db) == name)
+                            .copied()
+                    })
+                    .unwrap_or(decl);
+                Some((decl, args, *ret))
+            }
+            _ => None,
+        },
+        None => None,
+    };
+    let Some((decl, args, ret)) = found else {
+        state.simple_error(
+            &format!(
+                "Type '{}' can't be indexed, as it doesn't implement '{trait_}'",
+                resolved.get_name(state)
+            ),
+            span,
+        );
+        return None;
+    };
+    if args.len() != arity {
+        state.simple_error(
+            &format!(
+                "Expected '{name}' to take {arity} argument(s) to implement '{trait_}', but it takes {}",
+                args.len()
+            ),
+            span,
+        );
+        return None;
+    }
+    Some((decl, args, ret))
+}
+
+impl<'db> IrNode<'db> for IndexIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut IrState<'db>) -> &dyn IrNode {
+        if self.expr.1.contains_offset(offset) {
+            return self.expr.0.at_offset(offset, state);
+        }
+        if self.index.1.contains_offset(offset) {
+            return self.index.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut IrState<'db>) {
+        self.expr.0.tokens(tokens, state);
+        self.index.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "IndexIR"
+    }
+}
+
+impl<'db> IndexIR<'db> {
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        ByteCodeNode::Block(vec![
+            self.expr.0.build(state),
+            self.index.0.build(state),
+            self.call(state),
+        ])
+    }
+
+    /// Stores `value` at the index with `IndexMut::set_index`
+    pub fn build_assign(&self, value: &ExprIR<'db>, state: &mut BuildState<'db>) -> ByteCodeNode {
+        ByteCodeNode::Block(vec![
+            self.expr.0.build(state),
+            self.index.0.build(state),
+            value.build(state),
+            self.call(state),
+        ])
+    }
+
+    /// Applies `op` to the item and stores the result, evaluating `expr` and `index` only once
+    pub fn build_compound_assign(
+        &self,
+        op: &OpKind,
+        value: &ExprIR<'db>,
+        state: &mut BuildState<'db>,
+    ) -> ByteCodeNode {
+        let expr = self.expr.0.build(state);
+        let index = self.index.0.build(state);
+        let expr_id = state.add_var("$expr".to_string());
+        let index_id = state.add_var("$index".to_string());
+        let get = self.get.expect("Index function should be resolved");
+        ByteCodeNode::Block(vec![
+            expr,
+            index,
+            ByteCodeNode::Code(vec![
+                ByteCode::NewLocal(index_id),
+                ByteCode::NewLocal(expr_id),
+                ByteCode::GetLocal(expr_id),
+                ByteCode::GetLocal(index_id),
+                ByteCode::GetLocal(expr_id),
+                ByteCode::GetLocal(index_id),
+            ]),
+            Self::call_func(get, state),
+            value.build(state),
+            ByteCodeNode::Code(vec![op.build()]),
+            self.call(state),
+        ])
+    }
+
+    fn call(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let decl = self.func.expect("Index function should be resolved");
+        Self::call_func(decl, state)
+    }
+
+    fn call_func(decl: Decl<'db>, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let DeclKind::Function(Function { virtual_, .. }) = decl.kind(state.db) else {
+            panic!("Expected function")
+        };
+        let id = state.get_func_id(decl);
+        ByteCodeNode::Code(vec![if *virtual_ {
+            ByteCode::DynCall(id)
+        } else {
+            ByteCode::Call(id)
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_vec_literal_and_index() {
+        let errors = check_src(
+            "use std::Int
+use std::Vec
+
+fn main() {
+    let items: Vec[Int] = [1, 2, 3]
+    items[0] = items[1] + items[2]
+    items[2] += 1
+}
+",
+        );
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_vec_literal_items_must_match() {
+        let errors = check_src(
+            "use std::Int
+use std::Vec
+
+fn main() {
+    let items: Vec[Int] = [1, true]
+}
+",
+        );
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn test_index_needs_trait() {
+        let errors = check_src(
+            "use std::Int
+
+struct Foo
+
+trait Lookup {
+    fn Self.index(index: Int): Int
+}
+
+impl Lookup for Foo {
+    fn Self.index(index: Int): Int {
+        index
+    }
+}
+
+fn read(foo: Foo): Int {
+    foo[0]
+}
+
+fn write(foo: Foo) {
+    foo[0] = 1
+}
+",
+        );
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("can't be indexed, as it doesn't implement 'Index'")));
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("can't be indexed, as it doesn't implement 'IndexMut'")));
+    }
+}
@@ -0,0 +1,212 @@
+use gvm::format::{instr::ByteCode, literal::Literal};
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState, SemanticToken},
+    db::{
+        decl::{func::Function, Decl, DeclKind},
+        path::ModulePath,
+    },
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode, IrState},
+    item::definitions::ident::IdentDef,
+    parser::expr::interpolation::{Interpolation, InterpolationPart},
+    ty::{sub_tys::path_to_sub_ty, Generic, Named, Ty},
+    util::{Span, Spanned},
+};
+
+use super::{lit::Typed as _, ExprIR, ExprIRData};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterpolationIR<'db> {
+    pub parts: Vec<InterpolationPartIR<'db>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InterpolationPartIR<'db> {
+    Text(String),
+    Expr {
+        expr: Spanned<ExprIR<'db>>,
+        /// The `to_string` turning the value into text, unless it's already a `String`
+        to_string: Option<Decl<'db>>,
+    },
+}
+
+impl<'db> Interpolation {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let string = Literal::String(String::new()).to_ty(state.db);
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                InterpolationPart::Text(text) => InterpolationPartIR::Text(text.clone()),
+                InterpolationPart::Expr((expr, span)) => {
+                    let expr = expr.check(state);
+                    let to_string = if expr.ty == string {
+                        None
+                    } else {
+                        find_to_string(&expr.ty, state, *span)
+                    };
+                    InterpolationPartIR::Expr {
+                        expr: (expr, *span),
+                        to_string,
+                    }
+                }
+            })
+            .collect();
+        ExprIR {
+            data: ExprIRData::Interpolation(InterpolationIR { parts }),
+            ty: string,
+            order: state.inc_order(),
+        }
+    }
+
+    pub fn expect(
+        &self,
+        state: &mut CheckState<'db>,
+        expected: &Ty<'db>,
+        span: Span,
+    ) -> ExprIR<'db> {
+        let ir = self.check(state);
+        ir.ty.expect_is_instance_of(expected, state, span);
+        ir
+    }
+}
+
+/// Finds the `ToString::to_string` for a type, reporting an error if it doesn't implement the trait
+fn find_to_string<'db>(ty: &Ty<'db>, state: &mut CheckState<'db>, span: Span) -> Option<Decl<'db>> {
+    let resolved = state.resolved_ty(ty);
+    let name = match &resolved {
+        Ty::Unknown | Ty::Nothing => return None,
+        Ty::Named(Named { name, .. }) => Some(*name),
+        Ty::Generic(Generic { super_, .. }) => match super_.as_ref() {
+            Ty::Named(Named { name, .. }) => Some(*name),
+            _ => None,
+        },
+        _ => None,
+    };
+    let trait_ = ModulePath::new(state.db, vec!["std".to_string(), "ToString".to_string()]);
+    let found = match name.and_then(|name| path_to_sub_ty(name, trait_, state)) {
+        // Implemented by an impl, so it's that impl's function
+        Some(path) if !path.is_empty() => path.last().and_then(|impl_| {
+            impl_
+                .functions(state.db)
+                .iter()
+                .find(|func| func.name(state.db) == "to_string")
+                .copied()
+        }),
+        // A trait object or a generic, which is called through its vtable
+        Some(_) => match ty.get_member_func(&("to_string".to_string(), span), state) {
+            Some((IdentDef::Decl(decl), _)) => Some(decl),
+            _ => None,
+        },
+        None => None,
+    };
+    if found.is_none() {
+        state.simple_error(
+            &format!(
+                "Only types implementing 'ToString' can be interpolated, but found '{}'",
+                resolved.get_name(state)
+            ),
+            span,
+        );
+    }
+    found
+}
+
+impl<'db> IrNode<'db> for InterpolationIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut IrState<'db>) -> &dyn IrNode {
+        for part in &self.parts {
+            if let InterpolationPartIR::Expr { expr, .. } = part {
+                if expr.1.contains_offset(offset) {
+                    return expr.0.at_offset(offset, state);
+                }
+            }
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut IrState<'db>) {
+        for part in &self.parts {
+            if let InterpolationPartIR::Expr { expr, .. } = part {
+                expr.0.tokens(tokens, state);
+            }
+        }
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "InterpolationIR"
+    }
+}
+
+impl<'db> InterpolationIR<'db> {
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let mut code = vec![];
+        for part in &self.parts {
+            match part {
+                InterpolationPartIR::Text(text) => {
+                    code.push(ByteCodeNode::Code(vec![ByteCode::Push(Literal::String(
+                        text.clone(),
+                    ))]));
+                }
+                InterpolationPartIR::Expr { expr, to_string } => {
+                    code.push(expr.0.build(state));
+                    if let Some(decl) = to_string {
+                        let DeclKind::Function(Function { virtual_, .. }) = decl.kind(state.db)
+                        else {
+                            panic!("Expected function")
+                        };
+                        let id = state.get_func_id(*decl);
+                        code.push(ByteCodeNode::Code(vec![if *virtual_ {
+                            ByteCode::DynCall(id)
+                        } else {
+                            ByteCode::Call(id)
+                        }]));
+                    }
+                }
+            }
+        }
+        code.push(ByteCodeNode::Code(vec![ByteCode::Concat(
+            self.parts.len() as u32
+        )]));
+        ByteCodeNode::Block(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_interpolation_needs_to_string() {
+        let errors = check_src(
+            r#"
+            use std::Int
+            use std::String
+            use std::ToString
+
+            struct Shown(Int)
+
+            impl ToString for Shown {
+                fn Self.to_string(): String {
+                    "shown"
+                }
+            }
+
+            struct Hidden(Int)
+
+            impl Hidden {
+                fn Self.to_string(): String {
+                    "hidden"
+                }
+            }
+
+            fn main() {
+                let text = "{1} {Shown(2)} {Hidden(3)}"
+            }
+            "#,
+        );
+        assert_eq!(
+            errors,
+            vec!["Only types implementing 'ToString' can be interpolated, but found 'Hidden'"]
+        );
+    }
+}
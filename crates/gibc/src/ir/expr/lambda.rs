@@ -1,7 +1,7 @@
 use crate::{
     check::{
         scoped_state::{Scope, Scoped as _},
-        state::{CheckState, VarDecl},
+        state::{CheckState, Mutability, VarDecl},
         SemanticToken, TokenKind,
     },
     ir::{
@@ -31,6 +31,21 @@ pub struct LambdaParamIR<'db> {
     pub pattern: Spanned<PatternIR<'db>>,
     pub ty: Spanned<TypeIR<'db>>,
 }
+
+/// Checks a lambda's body, which can't break out of the loops around it or yield from
+/// the function it's in
+fn in_body<'db, T>(
+    state: &mut CheckState<'db>,
+    check: impl FnOnce(&mut CheckState<'db>) -> T,
+) -> T {
+    let loops = std::mem::take(&mut state.loops);
+    let in_lambda = std::mem::replace(&mut state.in_lambda, true);
+    let res = check(state);
+    state.loops = loops;
+    state.in_lambda = in_lambda;
+    res
+}
+
 impl<'db> Lambda {
     pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
         state.enter_scope();
@@ -43,7 +58,7 @@ impl<'db> Lambda {
             data: ExprIRData::CodeBlock(body),
             ty,
             ..
-        } = check_block(&self.body.0, state)
+        } = in_body(state, |state| check_block(&self.body.0, state))
         else {
             panic!("Expected block");
         };
@@ -90,6 +105,7 @@ impl<'db> Lambda {
                     ty: expected.args[0].clone(),
                     kind: TokenKind::Var,
                     span,
+                    mutability: Mutability::Immutable,
                 };
                 state.insert_variable("it", var);
             }
@@ -100,7 +116,9 @@ impl<'db> Lambda {
                 data: ExprIRData::CodeBlock(body),
                 ty,
                 ..
-            } = expect_block(&self.body.0, state, &expected.ret, self.body.1)
+            } = in_body(state, |state| {
+                expect_block(&self.body.0, state, &expected.ret, self.body.1)
+            })
             else {
                 panic!("Expected block???");
             };
@@ -123,7 +141,7 @@ impl<'db> Lambda {
                 data: ExprIRData::CodeBlock(body),
                 ty,
                 ..
-            } = check_block(&self.body.0, state)
+            } = in_body(state, |state| check_block(&self.body.0, state))
             else {
                 panic!("Expected a code block???");
             };
@@ -16,15 +16,9 @@ impl Typed for Literal {
                 name: ModulePath::new(db, vec!["std".to_string(), "String".to_string()]),
                 args: vec![],
             }),
-            Literal::Int(_) => Ty::Named(Named {
-                name: ModulePath::new(db, vec!["std".to_string(), "Int".to_string()]),
-                args: vec![],
-            }),
+            Literal::Int(_) => Ty::int(db),
             Literal::Bool(_) => Ty::bool(db),
-            Literal::Float(_) => Ty::Named(Named {
-                name: ModulePath::new(db, vec!["std".to_string(), "Float".to_string()]),
-                args: vec![],
-            }),
+            Literal::Float(_) => Ty::float(db),
             Literal::Char(_) => Ty::Named(Named {
                 name: ModulePath::new(db, vec!["std".to_string(), "Char".to_string()]),
                 args: vec![],
@@ -40,4 +34,18 @@ impl<'db> Ty<'db> {
             args: vec![],
         })
     }
+
+    pub fn int(db: &'db dyn Db) -> Self {
+        Ty::Named(Named {
+            name: ModulePath::new(db, vec!["std".to_string(), "Int".to_string()]),
+            args: vec![],
+        })
+    }
+
+    pub fn float(db: &'db dyn Db) -> Self {
+        Ty::Named(Named {
+            name: ModulePath::new(db, vec!["std".to_string(), "Float".to_string()]),
+            args: vec![],
+        })
+    }
 }
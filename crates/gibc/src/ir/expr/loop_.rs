@@ -0,0 +1,93 @@
+use crate::{
+    check::{build_state::BuildState, state::CheckState},
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode},
+    parser::expr::loop_::Loop,
+    ty::Ty,
+    util::{Span, Spanned},
+};
+
+use super::{
+    block::{check_block, CodeBlockIR},
+    ExprIR, ExprIRData,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoopIR<'db> {
+    pub label: Option<Spanned<String>>,
+    pub block: Spanned<CodeBlockIR<'db>>,
+}
+
+impl<'db> Loop {
+    /// A loop's value is given to `break`, and a loop that's never broken out of has no value
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        state.enter_loop(self.label.as_ref(), true);
+        let block = self.check_block(state);
+        let ty = state.exit_loop().unwrap_or(Ty::Nothing);
+        ExprIR {
+            data: ExprIRData::Loop(LoopIR {
+                label: self.label.clone(),
+                block,
+            }),
+            ty,
+            order: state.inc_order(),
+        }
+    }
+
+    pub fn expect(&self, state: &mut CheckState<'db>, expected: &Ty<'db>, _: Span) -> ExprIR<'db> {
+        state.enter_loop(self.label.as_ref(), true);
+        state.loops.last_mut().unwrap().ty = Some(expected.clone());
+        let block = self.check_block(state);
+        state.exit_loop();
+        ExprIR {
+            data: ExprIRData::Loop(LoopIR {
+                label: self.label.clone(),
+                block,
+            }),
+            ty: expected.clone(),
+            order: state.inc_order(),
+        }
+    }
+
+    fn check_block(&self, state: &mut CheckState<'db>) -> Spanned<CodeBlockIR<'db>> {
+        let ExprIR {
+            data: ExprIRData::CodeBlock(block),
+            ..
+        } = check_block(&self.block.0, state)
+        else {
+            panic!("Expected block")
+        };
+        (block, self.block.1)
+    }
+}
+
+impl<'db> IrNode<'db> for LoopIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        if self.block.1.contains_offset(offset) {
+            return self.block.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(
+        &self,
+        tokens: &mut Vec<crate::check::SemanticToken>,
+        state: &mut crate::ir::IrState<'db>,
+    ) {
+        self.block.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "LoopIR"
+    }
+}
+
+impl<'db> LoopIR<'db> {
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let then = vec![self.block.0.build(state), ByteCodeNode::Continue(None)];
+        ByteCodeNode::While(
+            Box::new(ByteCodeNode::Block(vec![])),
+            Box::new(ByteCodeNode::Block(then)),
+        )
+        .labelled(self.label.as_ref())
+    }
+}
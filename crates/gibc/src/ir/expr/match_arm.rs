@@ -1,3 +1,5 @@
+use gvm::format::{instr::ByteCode, literal::Literal};
+
 use crate::{
     check::{
         build_state::BuildState,
@@ -14,18 +16,21 @@ use super::{match_::MatchIR, ExprIR};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct MatchArmIR<'db> {
-    pub pattern: Spanned<PatternIR<'db>>,
+    pub patterns: Vec<Spanned<PatternIR<'db>>>,
+    pub guard: Option<Spanned<ExprIR<'db>>>,
     pub expr: Box<Spanned<ExprIR<'db>>>,
     pub scope: Scope<'db>,
 }
 impl<'db> MatchArm {
     pub fn check(&self, state: &mut CheckState<'db>, ty: &Ty<'db>) -> MatchArmIR<'db> {
         state.enter_scope();
-        let pattern = (self.pattern.0.expect(state, ty), self.pattern.1);
+        let patterns = self.check_patterns(state, ty);
+        let guard = self.check_guard(state);
         let expr = Box::new((self.expr.0.check(state), self.expr.1));
         let scope = state.exit_scope();
         MatchArmIR {
-            pattern,
+            patterns,
+            guard,
             expr,
             scope,
         }
@@ -39,21 +44,85 @@ impl<'db> MatchArm {
         ty: &Ty<'db>,
     ) -> MatchArmIR<'db> {
         state.enter_scope();
-        let pattern = (self.pattern.0.expect(state, ty), self.pattern.1);
+        let patterns = self.check_patterns(state, ty);
+        let guard = self.check_guard(state);
         let expr = Box::new((self.expr.0.expect(state, expected, span), self.expr.1));
         let scope = state.exit_scope();
         MatchArmIR {
-            pattern,
+            patterns,
+            guard,
             expr,
             scope,
         }
     }
+
+    /// Checks each alternative in its own scope, so they can be made to bind the same variables,
+    /// then adds the variables of the first to the arm's scope
+    fn check_patterns(
+        &self,
+        state: &mut CheckState<'db>,
+        ty: &Ty<'db>,
+    ) -> Vec<Spanned<PatternIR<'db>>> {
+        let mut patterns = vec![];
+        let mut bound = vec![];
+        for (pattern, span) in &self.patterns {
+            state.enter_scope();
+            patterns.push((pattern.expect(state, ty), *span));
+            let scope = state.exit_scope();
+            let vars = scope
+                .vars
+                .get_all(scope.order)
+                .into_iter()
+                .map(|(name, var)| (name.clone(), var.clone()))
+                .collect::<Vec<_>>();
+            if patterns.len() == 1 {
+                bound = vars;
+                continue;
+            }
+            for (name, var) in &vars {
+                if let Some((_, first)) = bound.iter().find(|(bound, _)| bound == name) {
+                    var.ty.expect_is_instance_of(&first.ty, state, var.span);
+                } else {
+                    state.simple_error(
+                        &format!("Variable '{name}' isn't bound by the first alternative"),
+                        var.span,
+                    );
+                }
+            }
+            for (name, _) in &bound {
+                if !vars.iter().any(|(var, _)| var == name) {
+                    state.simple_error(
+                        &format!("Variable '{name}' isn't bound by this alternative"),
+                        *span,
+                    );
+                }
+            }
+        }
+        for (name, var) in bound {
+            state.insert_variable(&name, var);
+        }
+        patterns
+    }
+
+    fn check_guard(&self, state: &mut CheckState<'db>) -> Option<Spanned<ExprIR<'db>>> {
+        self.guard.as_ref().map(|(guard, span)| {
+            let expected = Ty::bool(state.db);
+            (guard.expect(state, &expected, *span), *span)
+        })
+    }
 }
 
 impl<'db> IrNode<'db> for MatchArmIR<'db> {
     fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
-        if self.pattern.1.contains_offset(offset) {
-            return self.pattern.0.at_offset(offset, state);
+        for (pattern, span) in &self.patterns {
+            if span.contains_offset(offset) {
+                return pattern.at_offset(offset, state);
+            }
+        }
+        if let Some((guard, span)) = &self.guard {
+            if span.contains_offset(offset) {
+                return guard.at_offset(offset, state);
+            }
         }
         if self.expr.1.contains_offset(offset) {
             return self.expr.0.at_offset(offset, state);
@@ -66,7 +135,12 @@ impl<'db> IrNode<'db> for MatchArmIR<'db> {
         tokens: &mut Vec<crate::check::SemanticToken>,
         state: &mut crate::ir::IrState<'db>,
     ) {
-        self.pattern.0.tokens(tokens, state);
+        for (pattern, _) in &self.patterns {
+            pattern.tokens(tokens, state);
+        }
+        if let Some((guard, _)) = &self.guard {
+            guard.tokens(tokens, state);
+        }
         self.expr.0.tokens(tokens, state);
     }
 
@@ -77,10 +151,7 @@ impl<'db> IrNode<'db> for MatchArmIR<'db> {
 
 impl<'db> MatchIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
-        let mut branches = vec![];
-        for (arm, _) in &self.arms {
-            branches.push(arm.build(state));
-        }
+        let branches = self.arms.iter().map(|(arm, _)| arm.build(state)).collect();
         let if_ = ByteCodeNode::If {
             branches,
             else_: None,
@@ -90,12 +161,188 @@ impl<'db> MatchIR<'db> {
 }
 
 impl<'db> MatchArmIR<'db> {
+    /// Builds the arm's branch of the match's jump chain, with its guard and body shared by all of
+    /// its alternatives
     pub fn build(&self, state: &mut BuildState<'db>) -> (Box<ByteCodeNode>, Box<ByteCodeNode>) {
         state.enter_scope();
-        let pattern = self.pattern.0.build(state);
-        let cond = self.pattern.0.build_match(state);
-        let then = vec![pattern, self.expr.0.build(state)];
+        // The guard needs the variables, but a copy of the value is kept for the following arms
+        // in case it fails
+        let keep = if self.guard.is_some() {
+            vec![ByteCode::Copy]
+        } else {
+            vec![]
+        };
+        let mut cond = vec![self.build_patterns(&keep, state)];
+        let mut then = vec![];
+        if let Some((guard, _)) = &self.guard {
+            cond.extend([guard.build(state), ByteCodeNode::Next]);
+            then.push(ByteCodeNode::Code(vec![ByteCode::Pop]));
+        }
+        then.push(self.expr.0.build(state));
         state.exit_scope();
-        (Box::new(cond), Box::new(ByteCodeNode::Block(then)))
+        (
+            Box::new(ByteCodeNode::Block(cond)),
+            Box::new(ByteCodeNode::Block(then)),
+        )
+    }
+
+    /// Binds the value with the first alternative that matches it, or moves on to the next arm
+    /// when none do. The variables are those of the first alternative, which the others copy
+    /// theirs into
+    fn build_patterns(&self, keep: &[ByteCode], state: &mut BuildState<'db>) -> ByteCodeNode {
+        if let [(pattern, _)] = &self.patterns[..] {
+            return ByteCodeNode::Block(vec![
+                pattern.build_match(state),
+                ByteCodeNode::Code(keep.to_vec()),
+                pattern.build(state),
+            ]);
+        }
+        let (matched, unmatched) = (Literal::Bool(true), Literal::Bool(false));
+        let mut branches = vec![];
+        for (index, (pattern, _)) in self.patterns.iter().enumerate() {
+            let cond = pattern.build_match(state);
+            let mut then = vec![ByteCodeNode::Code(keep.to_vec())];
+            if index == 0 {
+                then.push(pattern.build(state));
+            } else {
+                state.enter_scope();
+                then.push(pattern.build(state));
+                let bound = state.vars.last().unwrap().clone();
+                state.exit_scope();
+                let mut moves = bound
+                    .into_iter()
+                    .map(|(name, id)| (id, state.get_var(&name).unwrap()))
+                    .collect::<Vec<_>>();
+                moves.sort_unstable();
+                then.push(ByteCodeNode::Code(
+                    moves
+                        .into_iter()
+                        .flat_map(|(id, first)| [ByteCode::GetLocal(id), ByteCode::NewLocal(first)])
+                        .collect(),
+                ));
+            }
+            then.push(ByteCodeNode::Code(vec![ByteCode::Push(matched.clone())]));
+            branches.push((Box::new(cond), Box::new(ByteCodeNode::Block(then))));
+        }
+        let alternatives = ByteCodeNode::If {
+            branches,
+            else_: Some(Box::new(ByteCodeNode::Code(vec![ByteCode::Push(
+                unmatched,
+            )]))),
+        };
+        ByteCodeNode::Block(vec![alternatives, ByteCodeNode::Next])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::{check_src, run_src};
+
+    const SHAPE: &str = "use std::Int
+use std::Bool
+
+enum Shape {
+    Circle(Int),
+    Square(Int),
+    Rect(Int, Int),
+}
+";
+
+    #[test]
+    fn test_alternatives_bind_the_same_variables() {
+        let errors = check_src(&format!(
+            "{SHAPE}
+fn size(shape: Shape): Int {{
+    match shape {{
+        Shape::Circle(size) | Shape::Rect(size, _) => size,
+        Shape::Square(side) | Shape::Rect(_, side) => side,
+    }}
+}}
+"
+        ));
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_alternatives_missing_variables() {
+        let errors = check_src(&format!(
+            "{SHAPE}
+fn size(shape: Shape): Int {{
+    match shape {{
+        Shape::Circle(size) | Shape::Square(side) => 1,
+        Shape::Rect(width, height) | Shape::Square(width) => width,
+    }}
+}}
+"
+        ));
+        assert!(
+            errors.contains(&"Variable 'side' isn't bound by the first alternative".to_string())
+        );
+        assert!(errors.contains(&"Variable 'size' isn't bound by this alternative".to_string()));
+        assert!(errors.contains(&"Variable 'height' isn't bound by this alternative".to_string()));
+    }
+
+    #[test]
+    fn test_guards_are_bools_with_the_arms_variables() {
+        let errors = check_src(&format!(
+            "{SHAPE}
+fn large(shape: Shape): Bool {{
+    match shape {{
+        Shape::Circle(size) | Shape::Square(size) if size > 10 => true,
+        Shape::Rect(width, _) if width => true,
+        _ => false,
+    }}
+}}
+"
+        ));
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_later_alternative_binds() {
+        let output = run_src(&format!(
+            "use std::println
+{SHAPE}
+fn width(shape: Shape): Int {{
+    match shape {{
+        Shape::Circle(size) | Shape::Square(size) | Shape::Rect(size, _) => size * 10,
+    }}
+}}
+
+fn main() {{
+    println(width(Shape::Circle(1)))
+    println(width(Shape::Square(2)))
+    println(width(Shape::Rect(3, 4)))
+}}
+"
+        ));
+        assert_eq!(output, "10\n20\n30\n");
+    }
+
+    #[test]
+    fn test_failing_guard_falls_through() {
+        let output = run_src(&format!(
+            "use std::String
+use std::println
+{SHAPE}
+fn describe(shape: Shape): String {{
+    match shape {{
+        Shape::Circle(size) | Shape::Square(size) if size > 10 => \"large\",
+        Shape::Rect(width, height) if width == height => \"square\",
+        Shape::Square(size) => \"small square {{size}}\",
+        _ => \"other\",
+    }}
+}}
+
+fn main() {{
+    println(describe(Shape::Square(20)))
+    println(describe(Shape::Square(3)))
+    println(describe(Shape::Rect(2, 2)))
+    println(describe(Shape::Rect(2, 5)))
+    println(describe(Shape::Circle(5)))
+}}
+"
+        ));
+        assert_eq!(output, "large\nsmall square 3\nsquare\nother\nother\n");
     }
 }
@@ -1,5 +1,4 @@
 use gvm::format::instr::ByteCode;
-use salsa::plumbing::AsId;
 
 use crate::{
     check::{
@@ -129,12 +128,20 @@ impl<'db> IrNode<'db> for MemberCallIR<'db> {
     }
 
     fn hover(&self, _: usize, state: &mut IrState<'db>) -> Option<String> {
-        Some(format!(
-            "{}: {}",
-            self.def.hover(state),
-            self.ty
-                .as_ref()
-                .map_or("Unknown".to_string(), |func| func.get_ir_name(state))
+        let ty = self
+            .ty
+            .as_ref()
+            .map_or("Unknown".to_string(), |func| func.get_ir_name(state));
+        let IdentDef::Decl(decl) = &self.def else {
+            return Some(format!("{}: {ty}", self.def.hover(state)));
+        };
+        Some(decl.with_doc(
+            state.db,
+            format!(
+                "{} {}: {ty}",
+                decl.get_kind_name(state.db),
+                decl.name(state.db)
+            ),
         ))
     }
 
@@ -166,11 +173,11 @@ impl<'db> MemberCallIR<'db> {
                 };
                 if *virtual_ {
                     code.push(ByteCodeNode::Code(vec![ByteCode::DynCall(
-                        decl.as_id().as_u32(),
+                        state.get_func_id(*decl),
                     )]));
                 } else {
                     code.push(ByteCodeNode::Code(vec![ByteCode::Call(
-                        decl.as_id().as_u32(),
+                        state.get_func_id(*decl),
                     )]));
                 }
                 ByteCodeNode::Block(code)
@@ -5,13 +5,18 @@ use for_::ForIR;
 use gvm::format::{instr::ByteCode, literal::Literal};
 use ident::{check_ident, expect_ident};
 use if_else::IfElseIR;
+use index::IndexIR;
+use interpolation::InterpolationIR;
 use lambda::LambdaIR;
 use lit::Typed as _;
+use loop_::LoopIR;
 use match_::MatchIR;
 use member::MemberCallIR;
 use op::OpIR;
-use salsa::plumbing::AsId;
+use range::RangeIR;
+use try_::TryIR;
 use tuple::{check_tuple, expect_tuple};
+use vec_lit::VecLitIR;
 use while_::WhileIR;
 
 use crate::{
@@ -37,13 +42,19 @@ pub mod field;
 pub mod for_;
 pub mod ident;
 pub mod if_else;
+pub mod index;
+pub mod interpolation;
 pub mod lambda;
 pub mod lit;
+pub mod loop_;
 pub mod match_;
 pub mod match_arm;
 pub mod member;
 pub mod op;
+pub mod range;
+pub mod try_;
 pub mod tuple;
+pub mod vec_lit;
 pub mod while_;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -66,10 +77,16 @@ pub enum ExprIRData<'db> {
     Op(OpIR<'db>),
     Lambda(LambdaIR<'db>),
     While(WhileIR<'db>),
+    Loop(LoopIR<'db>),
     IfElse(IfElseIR<'db>),
     ImplicitDyn(Box<ExprIR<'db>>, Decl<'db>),
     Phantom(Box<ExprIR<'db>>),
     For(ForIR<'db>),
+    Try(TryIR<'db>),
+    Interpolation(InterpolationIR<'db>),
+    VecLit(VecLitIR<'db>),
+    Index(IndexIR<'db>),
+    Range(RangeIR<'db>),
     Error,
 }
 
@@ -91,6 +108,7 @@ impl<'db> Expr {
             Expr::Op(op) => op.check(state),
             Expr::Lambda(lambda) => lambda.check(state),
             Expr::While(while_) => while_.check(state),
+            Expr::Loop(loop_) => loop_.check(state),
             Expr::Error => ExprIR {
                 data: ExprIRData::Error,
                 ty: Ty::Unknown,
@@ -98,6 +116,11 @@ impl<'db> Expr {
             },
             Expr::IfElse(if_else) => if_else.check(state),
             Expr::For(for_) => for_.check(state),
+            Expr::Try(try_) => try_.check(state),
+            Expr::Interpolation(interpolation) => interpolation.check(state),
+            Expr::VecLit(vec_lit) => vec_lit.check(state),
+            Expr::Index(index) => index.check(state),
+            Expr::Range(range) => range.check(state),
         }
     }
 
@@ -136,12 +159,18 @@ impl<'db> Expr {
                 Ty::unit().expect_is_instance_of(expected, state, span);
                 ir
             }
+            Expr::Loop(loop_) => loop_.expect(state, expected, span),
             Expr::For(for_) => {
                 let ir = for_.check(state);
                 Ty::unit().expect_is_instance_of(expected, state, span);
                 ir
             }
             Expr::IfElse(if_else) => if_else.expect(state, expected),
+            Expr::Try(try_) => try_.expect(state, expected, span),
+            Expr::Interpolation(interpolation) => interpolation.expect(state, expected, span),
+            Expr::VecLit(vec_lit) => vec_lit.expect(state, expected, span),
+            Expr::Index(index) => index.expect(state, expected, span),
+            Expr::Range(range) => range.expect(state, expected, span),
         };
         if let Ty::Named(Named { name, .. }) = &expected {
             let decl = state.project.get_decl(state.db, *name).unwrap();
@@ -182,7 +211,13 @@ impl<'db> IrNode<'db> for ExprIR<'db> {
             ExprIRData::Op(op) => op.at_offset(offset, state),
             ExprIRData::Lambda(lambda) => lambda.at_offset(offset, state),
             ExprIRData::While(while_) => while_.at_offset(offset, state),
+            ExprIRData::Loop(loop_) => loop_.at_offset(offset, state),
             ExprIRData::For(for_) => for_.at_offset(offset, state),
+            ExprIRData::Try(try_) => try_.at_offset(offset, state),
+            ExprIRData::Interpolation(interpolation) => interpolation.at_offset(offset, state),
+            ExprIRData::VecLit(vec_lit) => vec_lit.at_offset(offset, state),
+            ExprIRData::Index(index) => index.at_offset(offset, state),
+            ExprIRData::Range(range) => range.at_offset(offset, state),
             ExprIRData::IfElse(if_else) => if_else.at_offset(offset, state),
             ExprIRData::ImplicitDyn(expr, _) => expr.at_offset(offset, state),
             ExprIRData::Phantom(_) => unreachable!(),
@@ -209,7 +244,13 @@ impl<'db> IrNode<'db> for ExprIR<'db> {
             ExprIRData::Op(op) => op.tokens(tokens, state),
             ExprIRData::Lambda(lambda) => lambda.tokens(tokens, state),
             ExprIRData::While(while_) => while_.tokens(tokens, state),
+            ExprIRData::Loop(loop_) => loop_.tokens(tokens, state),
             ExprIRData::For(for_) => for_.tokens(tokens, state),
+            ExprIRData::Try(try_) => try_.tokens(tokens, state),
+            ExprIRData::Interpolation(interpolation) => interpolation.tokens(tokens, state),
+            ExprIRData::VecLit(vec_lit) => vec_lit.tokens(tokens, state),
+            ExprIRData::Index(index) => index.tokens(tokens, state),
+            ExprIRData::Range(range) => range.tokens(tokens, state),
             ExprIRData::IfElse(if_else) => if_else.tokens(tokens, state),
             ExprIRData::ImplicitDyn(expr, _) => expr.tokens(tokens, state),
             ExprIRData::Phantom(_) | ExprIRData::Literal(_) | ExprIRData::Error => {}
@@ -225,19 +266,12 @@ impl<'db> IrNode<'db> for ExprIR<'db> {
     }
 }
 
-pub fn replace_chars(lit: Literal) -> Literal {
-    match lit {
-        Literal::String(text) => Literal::String(text.replace("\\n", "\n")),
-        _ => lit,
-    }
-}
-
 impl<'db> ExprIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
         match &self.data {
             ExprIRData::Literal(lit) => {
                 state.inc_index(1);
-                ByteCodeNode::Code(vec![ByteCode::Push(replace_chars(lit.clone()))])
+                ByteCodeNode::Code(vec![ByteCode::Push(lit.clone())])
             }
             ExprIRData::Field(field) => field.build(state),
             ExprIRData::Ident(ident) => match &ident.last().unwrap().0 {
@@ -253,24 +287,26 @@ impl<'db> ExprIR<'db> {
                     _ => todo!(),
                 },
                 IdentDef::Generic(_) => todo!(),
-                IdentDef::Decl(decl) => {
-                    if matches!(
-                        decl.kind(state.db),
-                        DeclKind::Struct {
-                            body: StructDecl::None,
-                            ..
-                        } | DeclKind::Member {
-                            body: StructDecl::None,
-                        }
-                    ) {
-                        ByteCodeNode::Code(vec![ByteCode::Construct {
-                            id: decl.as_id().as_u32(),
-                            len: 0,
-                        }])
-                    } else {
-                        panic!("Can only construct unit decl as ident")
+                IdentDef::Decl(decl) => match decl.kind(state.db) {
+                    DeclKind::Struct {
+                        body: StructDecl::None,
+                        ..
                     }
-                }
+                    | DeclKind::Member {
+                        body: StructDecl::None,
+                    } => ByteCodeNode::Code(vec![ByteCode::Construct {
+                        id: state.get_type_id(*decl),
+                        len: 0,
+                    }]),
+                    // Constants are folded while resolving, so they're inlined like literals
+                    DeclKind::Const {
+                        value: Some(value), ..
+                    } => {
+                        state.inc_index(1);
+                        ByteCodeNode::Code(vec![ByteCode::Push(value.clone())])
+                    }
+                    _ => panic!("Can only construct unit decl as ident"),
+                },
                 IdentDef::Unresolved => unreachable!(),
             },
             ExprIRData::CodeBlock(block) => block.build(state),
@@ -288,7 +324,13 @@ impl<'db> ExprIR<'db> {
             ExprIRData::Op(op) => op.build(state),
             ExprIRData::Lambda(_) => todo!(),
             ExprIRData::While(while_) => while_.build(state),
+            ExprIRData::Loop(loop_) => loop_.build(state),
             ExprIRData::For(for_) => for_.build(state),
+            ExprIRData::Try(try_) => try_.build(state),
+            ExprIRData::Interpolation(interpolation) => interpolation.build(state),
+            ExprIRData::VecLit(vec_lit) => vec_lit.build(state),
+            ExprIRData::Index(index) => index.build(state),
+            ExprIRData::Range(range) => range.build(state),
             ExprIRData::IfElse(if_else) => if_else.build(state),
             ExprIRData::ImplicitDyn(expr, _) => {
                 let mut code = vec![expr.build(state)];
@@ -368,10 +410,16 @@ impl<'db> ExprIRData<'db> {
             | ExprIRData::Match(_)
             | ExprIRData::ImplicitDyn(_, _)
             | ExprIRData::While(_)
+            | ExprIRData::Loop(_)
             | ExprIRData::For(_)
+            | ExprIRData::Try(_)
+            | ExprIRData::Index(_)
             | ExprIRData::Error  => true,
             ExprIRData::Tuple(_)
             | ExprIRData::Literal(_)
+            | ExprIRData::Interpolation(_)
+            | ExprIRData::VecLit(_)
+            | ExprIRData::Range(_)
             // TODO: Will change with op overloading
             | ExprIRData::Op(_)
             | ExprIRData::Lambda(_) => false,
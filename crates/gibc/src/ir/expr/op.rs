@@ -21,14 +21,14 @@ impl<'db> Op {
     pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
         let left = Box::new((self.left.as_ref().0.check(state), self.left.1));
         let right = Box::new((self.right.as_ref().0.check(state), self.right.1));
-        // TODO: Implement operator checking
+        let ty = self.kind.check(&left, &right, state);
         ExprIR {
             data: ExprIRData::Op(OpIR {
                 left,
                 right,
                 kind: self.kind.clone(),
             }),
-            ty: Ty::Unknown,
+            ty,
             order: state.inc_order(),
         }
     }
@@ -45,6 +45,78 @@ impl<'db> Op {
     }
 }
 
+impl<'db> OpKind {
+    /// The type of applying the operator, shared by binary ops and compound assignments. Both
+    /// sides are the same type, which arithmetic and comparisons need to be a number and `&&` and
+    /// `||` need to be a `Bool`. `%` only works on `Int`s, as the VM has no float remainder
+    pub fn check(
+        &self,
+        left: &Spanned<ExprIR<'db>>,
+        right: &Spanned<ExprIR<'db>>,
+        state: &mut CheckState<'db>,
+    ) -> Ty<'db> {
+        let bool = Ty::bool(state.db);
+        let is_arithmetic = matches!(
+            self,
+            OpKind::Add | OpKind::Sub | OpKind::Mul | OpKind::Div | OpKind::Mod
+        );
+        let known = |ty: &Ty<'db>| !matches!(ty, Ty::Unknown | Ty::Nothing | Ty::TypeVar { .. });
+        let left_ty = state.resolved_ty(&left.0.ty);
+        let right_ty = state.resolved_ty(&right.0.ty);
+        // Whichever side's type is known decides the other's
+        let ty = if known(&left_ty) {
+            right.0.ty.expect_is_instance_of(&left_ty, state, right.1);
+            left_ty
+        } else if known(&right_ty) {
+            left.0.ty.expect_is_instance_of(&right_ty, state, left.1);
+            right_ty
+        } else if is_arithmetic {
+            return Ty::Unknown;
+        } else {
+            return bool;
+        };
+        let allowed = match self {
+            OpKind::Eq | OpKind::Neq => true,
+            OpKind::And | OpKind::Or => ty == bool,
+            OpKind::Mod => ty == Ty::int(state.db),
+            _ => ty == Ty::int(state.db) || ty == Ty::float(state.db),
+        };
+        if !allowed {
+            state.simple_error(
+                &format!(
+                    "Operator '{self}' can't be applied to '{}'",
+                    ty.get_name(state)
+                ),
+                Span::from(left.1.start..right.1.end),
+            );
+            return if is_arithmetic { Ty::Unknown } else { bool };
+        }
+        if is_arithmetic {
+            ty
+        } else {
+            bool
+        }
+    }
+
+    pub fn build(&self) -> ByteCode {
+        match self {
+            OpKind::Add => ByteCode::Add,
+            OpKind::Sub => ByteCode::Sub,
+            OpKind::Mul => ByteCode::Mul,
+            OpKind::Div => ByteCode::Div,
+            OpKind::Mod => ByteCode::Mod,
+            OpKind::Eq => ByteCode::Eq,
+            OpKind::Neq => ByteCode::Neq,
+            OpKind::Lt => ByteCode::Lt,
+            OpKind::Gt => ByteCode::Gt,
+            OpKind::Lte => ByteCode::Lte,
+            OpKind::Gte => ByteCode::Gte,
+            OpKind::And => ByteCode::And,
+            OpKind::Or => ByteCode::Or,
+        }
+    }
+}
+
 impl<'db> IrNode<'db> for OpIR<'db> {
     fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
         if self.left.1.contains_offset(offset) {
@@ -74,22 +146,84 @@ impl<'db> OpIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
         let mut code = vec![self.left.0.build(state)];
         code.push(self.right.0.build(state));
-        let op = match &self.kind {
-            OpKind::Add => ByteCode::Add,
-            OpKind::Sub => ByteCode::Sub,
-            OpKind::Mul => ByteCode::Mul,
-            OpKind::Div => ByteCode::Div,
-            OpKind::Mod => ByteCode::Mod,
-            OpKind::Eq => ByteCode::Eq,
-            OpKind::Neq => ByteCode::Neq,
-            OpKind::Lt => ByteCode::Lt,
-            OpKind::Gt => ByteCode::Gt,
-            OpKind::Lte => ByteCode::Lte,
-            OpKind::Gte => ByteCode::Gte,
-            OpKind::And => ByteCode::And,
-            OpKind::Or => ByteCode::Or,
-        };
-        code.push(ByteCodeNode::Code(vec![op]));
+        code.push(ByteCodeNode::Code(vec![self.kind.build()]));
         ByteCodeNode::Block(code)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_number_and_bool_ops() {
+        let errors = check_src(
+            "use std::Int
+use std::Float
+use std::Bool
+
+fn main() {
+    let a: Int = 1 + 2 * 3 % 4
+    let b: Float = 1.5 / 2.0 - 0.5
+    let c: Bool = a < 3 && b >= 1.0 || a == 2
+    let mut d = 1
+    d += a
+}
+",
+        );
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_op_on_string() {
+        let errors = check_src(
+            "use std::String
+
+fn main() {
+    let mut s = \"a\"
+    s += 1
+    let t = \"a\" < \"b\"
+}
+",
+        );
+        assert!(errors.contains(&"Operator '+' can't be applied to 'String'".to_string()));
+        assert!(errors.contains(&"Operator '<' can't be applied to 'String'".to_string()));
+    }
+
+    #[test]
+    fn test_op_sides_must_match() {
+        let errors = check_src(
+            "use std::Int
+use std::Bool
+
+fn main() {
+    let a = 1 + 2.0
+    let b = 1 && true
+}
+",
+        );
+        assert!(errors.len() >= 2);
+        assert!(errors.contains(&"Operator '&&' can't be applied to 'Int'".to_string()));
+    }
+
+    #[test]
+    fn test_mod_on_float() {
+        let errors = check_src(
+            "use std::Float
+
+fn main() {
+    let a = 1.5 % 2.0
+    let mut b = 1.5
+    b %= 2.0
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Operator '%' can't be applied to 'Float'".to_string(),
+                "Operator '%' can't be applied to 'Float'".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use gvm::format::{instr::ByteCode, literal::Literal};
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState, SemanticToken},
+    db::{input::Db, path::ModulePath},
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode, IrState},
+    parser::expr::range::Range,
+    ty::{Named, Ty},
+    util::{Span, Spanned},
+};
+
+use super::{ExprIR, ExprIRData};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RangeIR<'db> {
+    pub start: Box<Spanned<ExprIR<'db>>>,
+    pub end: Box<Spanned<ExprIR<'db>>>,
+    pub inclusive: bool,
+}
+
+impl<'db> Ty<'db> {
+    pub fn range(db: &'db dyn Db) -> Self {
+        Ty::Named(Named {
+            name: ModulePath::new(db, vec!["std".to_string(), "Range".to_string()]),
+            args: vec![],
+        })
+    }
+}
+
+impl<'db> Range {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let int = Ty::int(state.db);
+        let start = self.start.0.expect(state, &int, self.start.1);
+        let end = self.end.0.expect(state, &int, self.end.1);
+        ExprIR {
+            data: ExprIRData::Range(RangeIR {
+                start: Box::new((start, self.start.1)),
+                end: Box::new((end, self.end.1)),
+                inclusive: self.inclusive,
+            }),
+            ty: Ty::range(state.db),
+            order: state.inc_order(),
+        }
+    }
+
+    pub fn expect(
+        &self,
+        state: &mut CheckState<'db>,
+        expected: &Ty<'db>,
+        span: Span,
+    ) -> ExprIR<'db> {
+        let ir = self.check(state);
+        ir.ty.expect_is_instance_of(expected, state, span);
+        ir
+    }
+}
+
+impl<'db> IrNode<'db> for RangeIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut IrState<'db>) -> &dyn IrNode {
+        if self.start.1.contains_offset(offset) {
+            return self.start.0.at_offset(offset, state);
+        }
+        if self.end.1.contains_offset(offset) {
+            return self.end.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut IrState<'db>) {
+        self.start.0.tokens(tokens, state);
+        self.end.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "RangeIR"
+    }
+}
+
+impl<'db> RangeIR<'db> {
+    /// Builds a `std::Range` holding the next value, the end and whether the end is included
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let id = state.get_std_type_id(&["Range"]);
+        ByteCodeNode::Block(vec![
+            self.start.0.build(state),
+            self.end.0.build(state),
+            ByteCodeNode::Code(vec![
+                ByteCode::Push(Literal::Bool(self.inclusive)),
+                ByteCode::Construct { id, len: 3 },
+            ]),
+        ])
+    }
+}
+
+/// Whether a range has another value, given the code reading its next value, end and whether the
+/// end is included
+pub fn build_has_next(
+    next: &[ByteCode],
+    end: &[ByteCode],
+    inclusive: &[ByteCode],
+) -> Vec<ByteCode> {
+    [
+        next,
+        end,
+        &[ByteCode::Lt],
+        next,
+        end,
+        &[ByteCode::Eq],
+        inclusive,
+        &[ByteCode::And, ByteCode::Or],
+    ]
+    .concat()
+}
+
+/// Moves a range past its next value. That's only incremented while it's before the end, as
+/// `a..=b` would overflow when `b` is the largest `Int`, so reaching the end excludes it instead
+pub fn build_advance(
+    next: &[ByteCode],
+    end: &[ByteCode],
+    increment: Vec<ByteCode>,
+    exclude_end: Vec<ByteCode>,
+) -> ByteCodeNode {
+    let at_end = ByteCodeNode::Block(vec![
+        ByteCodeNode::Code([next, end, &[ByteCode::Eq]].concat()),
+        ByteCodeNode::Next,
+    ]);
+    ByteCodeNode::If {
+        branches: vec![(Box::new(at_end), Box::new(ByteCodeNode::Code(exclude_end)))],
+        else_: Some(Box::new(ByteCodeNode::Code(increment))),
+    }
+}
+
+/// The body of `Range::next`, which returns the next value and moves the range past it
+pub fn build_range_next(state: &mut BuildState<'_>) -> ByteCodeNode {
+    let some = state.get_std_type_id(&["Option", "Some"]);
+    let none = state.get_std_type_id(&["Option", "None"]);
+    let next = [ByteCode::Param(0), ByteCode::Index(0)];
+    let end = [ByteCode::Param(0), ByteCode::Index(1)];
+    let inclusive = [ByteCode::Param(0), ByteCode::Index(2)];
+    let cond = ByteCodeNode::Block(vec![
+        ByteCodeNode::Code(build_has_next(&next, &end, &inclusive)),
+        ByteCodeNode::Next,
+    ]);
+    let then = ByteCodeNode::Block(vec![
+        ByteCodeNode::Code(next.to_vec()),
+        build_advance(
+            &next,
+            &end,
+            vec![
+                ByteCode::Param(0),
+                ByteCode::Param(0),
+                ByteCode::Index(0),
+                ByteCode::Push(Literal::Int(1)),
+                ByteCode::Add,
+                ByteCode::SetIndex(0),
+            ],
+            vec![
+                ByteCode::Param(0),
+                ByteCode::Push(Literal::Bool(false)),
+                ByteCode::SetIndex(2),
+            ],
+        ),
+        ByteCodeNode::Code(vec![ByteCode::Construct { id: some, len: 1 }]),
+    ]);
+    ByteCodeNode::If {
+        branches: vec![(Box::new(cond), Box::new(then))],
+        else_: Some(Box::new(ByteCodeNode::Code(vec![ByteCode::Construct {
+            id: none,
+            len: 0,
+        }]))),
+    }
+}
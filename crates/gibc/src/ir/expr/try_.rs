@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use gvm::format::instr::ByteCode;
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState},
+    db::decl::{func::Function, Decl, DeclKind},
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode, IrState},
+    parser::expr::try_::Try,
+    ty::{Named, Ty},
+    util::{Span, Spanned},
+};
+
+use super::{ExprIR, ExprIRData};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TryIR<'db> {
+    pub expr: Box<Spanned<ExprIR<'db>>>,
+    pub kind: TryKind,
+    /// The `From::from` used to convert the error into the function's error type
+    pub from: Option<Decl<'db>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TryKind {
+    Option,
+    Result,
+}
+
+impl<'db> Try {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        let expr = self.expr.0.check(state);
+        let ty = state.resolved_ty(&expr.ty);
+        let ret = match state.current_decl().kind(state.db) {
+            DeclKind::Function(Function { ret, .. }) => ret.clone(),
+            _ => Ty::Unknown,
+        };
+        let span = self.expr.1;
+        // '?' would return from the lambda, which doesn't have the function's return type
+        let (kind, value, from) = if state.in_lambda {
+            state.simple_error("'?' can't be used in a lambda", span);
+            (TryKind::Option, Ty::Unknown, None)
+        } else if let Some(args) = ty.std_args(state.db, "Option") {
+            if ret.std_args(state.db, "Option").is_none() {
+                state.simple_error(
+                    &format!(
+                        "'?' on an 'Option' can only be used in a function returning 'Option', not '{}'",
+                        ret.get_name(state)
+                    ),
+                    span,
+                );
+            }
+            (TryKind::Option, args[0].clone(), None)
+        } else if let Some(args) = ty.std_args(state.db, "Result") {
+            let from = if let Some(ret_args) = ret.std_args(state.db, "Result") {
+                convert_error(&args[1], &ret_args[1], state, span)
+            } else {
+                state.simple_error(
+                    &format!(
+                        "'?' on a 'Result' can only be used in a function returning 'Result', not '{}'",
+                        ret.get_name(state)
+                    ),
+                    span,
+                );
+                None
+            };
+            (TryKind::Result, args[0].clone(), from)
+        } else {
+            if !matches!(ty, Ty::Unknown | Ty::Nothing) {
+                state.simple_error(
+                    &format!(
+                        "'?' can only be used on an 'Option' or a 'Result', not '{}'",
+                        ty.get_name(state)
+                    ),
+                    span,
+                );
+            }
+            (TryKind::Option, Ty::Unknown, None)
+        };
+        ExprIR {
+            data: ExprIRData::Try(TryIR {
+                expr: Box::new((expr, span)),
+                kind,
+                from,
+            }),
+            ty: value,
+            order: state.inc_order(),
+        }
+    }
+
+    pub fn expect(
+        &self,
+        state: &mut CheckState<'db>,
+        expected: &Ty<'db>,
+        span: Span,
+    ) -> ExprIR<'db> {
+        let ir = self.check(state);
+        ir.ty.expect_is_instance_of(expected, state, span);
+        ir
+    }
+}
+
+/// Finds the `From::from` that turns `found` into `expected`, when the two error types differ
+fn convert_error<'db>(
+    found: &Ty<'db>,
+    expected: &Ty<'db>,
+    state: &mut CheckState<'db>,
+    span: Span,
+) -> Option<Decl<'db>> {
+    let found = state.resolved_ty(found);
+    let expected = state.resolved_ty(expected);
+    if found == expected {
+        return None;
+    }
+    if let Ty::Named(Named { name, .. }) = &expected {
+        let from = state
+            .project
+            .get_impls(state.db, *name)
+            .into_iter()
+            .find_map(|impl_| {
+                let mut implied = HashMap::new();
+                impl_
+                    .from_ty(state.db)
+                    .imply_generic_args(&expected, &mut implied);
+                let trait_ = impl_.to_ty(state.db)?.parameterize(&implied);
+                if trait_.std_args(state.db, "From")?.first() != Some(&found) {
+                    return None;
+                }
+                impl_
+                    .functions(state.db)
+                    .iter()
+                    .find(|func| func.name(state.db) == "from")
+                    .copied()
+            });
+        if from.is_some() {
+            return from;
+        }
+    }
+    // Without a conversion the error has to fit the function's error type as it is
+    found.expect_is_instance_of(&expected, state, span);
+    None
+}
+
+impl<'db> IrNode<'db> for TryIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut IrState<'db>) -> &dyn IrNode {
+        if self.expr.1.contains_offset(offset) {
+            return self.expr.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<crate::check::SemanticToken>, state: &mut IrState<'db>) {
+        self.expr.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "TryIR"
+    }
+}
+
+impl<'db> TryIR<'db> {
+    /// Returns early with the `None` or `Err`, otherwise unwraps the value
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let failed = match self.kind {
+            TryKind::Option => state.get_std_type_id(&["Option", "None"]),
+            TryKind::Result => state.get_std_type_id(&["Result", "Err"]),
+        };
+        let mut bail = vec![];
+        if let Some(from) = self.from {
+            bail.extend([
+                ByteCode::Index(0),
+                ByteCode::Call(state.get_func_id(from)),
+                ByteCode::Construct { id: failed, len: 1 },
+            ]);
+        }
+        bail.push(ByteCode::Return);
+        let check = ByteCodeNode::Block(vec![
+            ByteCodeNode::Code(vec![ByteCode::Copy, ByteCode::Match(failed)]),
+            ByteCodeNode::Next,
+        ]);
+        ByteCodeNode::Block(vec![
+            self.expr.0.build(state),
+            ByteCodeNode::If {
+                branches: vec![(Box::new(check), Box::new(ByteCodeNode::Code(bail)))],
+                else_: Some(Box::new(ByteCodeNode::Code(vec![ByteCode::Index(0)]))),
+            },
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::{check_src, run_src};
+
+    #[test]
+    fn test_try_in_lambda() {
+        let errors = check_src(
+            r#"
+            use std::Option
+            use std::Int
+            use std::parse_int
+
+            fn first(): Option[Int] {
+                let value = parse_int("1")?
+                let later = { parse_int("2")? }
+                Option::Some(value)
+            }
+            "#,
+        );
+        assert!(errors.contains(&"'?' can't be used in a lambda".to_string()));
+        assert!(!errors.iter().any(|err| err.contains("function returning")));
+    }
+
+    #[test]
+    fn test_try_returns_none() {
+        let output = run_src(
+            r#"
+            use std::Option
+            use std::Int
+            use std::String
+            use std::parse_int
+            use std::println
+
+            fn parse(text: String): Option[Int] {
+                let value = parse_int(text)?
+                println("parsed {value}")
+                Option::Some(value + 1)
+            }
+
+            fn show(value: Option[Int]): String {
+                match value {
+                    Option::Some(value) => "Some {value}",
+                    Option::None => "None"
+                }
+            }
+
+            fn main() {
+                println(show(parse("41")))
+                println(show(parse("forty one")))
+            }
+            "#,
+        );
+        assert_eq!(output, "parsed 41\nSome 42\nNone\n");
+    }
+
+    #[test]
+    fn test_try_returns_err() {
+        let output = run_src(
+            r#"
+            use std::Result
+            use std::Int
+            use std::String
+            use std::println
+
+            fn check(value: Int): Result[Int, String] {
+                if value > 100 {
+                    Result::Err("Too big")
+                } else {
+                    Result::Ok(value)
+                }
+            }
+
+            fn double(value: Int): Result[Int, String] {
+                let checked = check(value)?
+                println("checked {checked}")
+                Result::Ok(checked * 2)
+            }
+
+            fn show(value: Result[Int, String]): String {
+                match value {
+                    Result::Ok(value) => "Ok {value}",
+                    Result::Err(message) => "Err {message}"
+                }
+            }
+
+            fn main() {
+                println(show(double(21)))
+                println(show(double(500)))
+            }
+            "#,
+        );
+        assert_eq!(output, "checked 21\nOk 42\nErr Too big\n");
+    }
+
+    #[test]
+    fn test_try_converts_err() {
+        let output = run_src(
+            r#"
+            use std::Result
+            use std::From
+            use std::Int
+            use std::String
+            use std::println
+
+            struct Error(Int, String)
+
+            impl From[String] for Error {
+                fn from(value: String): Error {
+                    Error(7, value)
+                }
+            }
+
+            fn check(value: Int): Result[Int, String] {
+                if value > 100 {
+                    Result::Err("Too big")
+                } else {
+                    Result::Ok(value)
+                }
+            }
+
+            fn double(value: Int): Result[Int, Error] {
+                let checked = check(value)?
+                Result::Ok(checked * 2)
+            }
+
+            fn show(value: Result[Int, Error]): String {
+                match value {
+                    Result::Ok(value) => "Ok {value}",
+                    Result::Err(error) => match error {
+                        Error(code, message) => "Err {code} {message}"
+                    }
+                }
+            }
+
+            fn main() {
+                println(show(double(21)))
+                println(show(double(500)))
+            }
+            "#,
+        );
+        assert_eq!(output, "Ok 42\nErr 7 Too big\n");
+    }
+}
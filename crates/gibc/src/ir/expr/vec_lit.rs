@@ -0,0 +1,98 @@
+use gvm::format::instr::ByteCode;
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState, SemanticToken},
+    db::{input::Db, path::ModulePath},
+    ir::{builder::ByteCodeNode, ContainsOffset, IrNode, IrState},
+    parser::expr::vec_lit::VecLit,
+    ty::{Named, Ty},
+    util::{Span, Spanned},
+};
+
+use super::{ExprIR, ExprIRData};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VecLitIR<'db> {
+    pub items: Vec<Spanned<ExprIR<'db>>>,
+}
+
+impl<'db> Ty<'db> {
+    pub fn vec(db: &'db dyn Db, item: Ty<'db>) -> Self {
+        Ty::Named(Named {
+            name: ModulePath::new(db, vec!["std".to_string(), "Vec".to_string()]),
+            args: vec![item],
+        })
+    }
+}
+
+impl<'db> VecLit {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        // The item type is inferred from the items, or left open for an empty literal
+        let id = state.type_state.new_type_var(self.span, state.file_data);
+        self.check_items(state, &Ty::TypeVar { id })
+    }
+
+    pub fn expect(
+        &self,
+        state: &mut CheckState<'db>,
+        expected: &Ty<'db>,
+        span: Span,
+    ) -> ExprIR<'db> {
+        if let Some(args) = state.resolved_ty(expected).std_args(state.db, "Vec") {
+            return self.check_items(state, &args[0].clone());
+        }
+        let ir = self.check(state);
+        ir.ty.expect_is_instance_of(expected, state, span);
+        ir
+    }
+
+    fn check_items(&self, state: &mut CheckState<'db>, item: &Ty<'db>) -> ExprIR<'db> {
+        let items = self
+            .items
+            .iter()
+            .map(|(expr, span)| (expr.expect(state, item, *span), *span))
+            .collect();
+        ExprIR {
+            data: ExprIRData::VecLit(VecLitIR { items }),
+            ty: Ty::vec(state.db, item.clone()),
+            order: state.inc_order(),
+        }
+    }
+}
+
+impl<'db> IrNode<'db> for VecLitIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut IrState<'db>) -> &dyn IrNode {
+        for (item, span) in &self.items {
+            if span.contains_offset(offset) {
+                return item.at_offset(offset, state);
+            }
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut IrState<'db>) {
+        for (item, _) in &self.items {
+            item.tokens(tokens, state);
+        }
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "VecLitIR"
+    }
+}
+
+impl<'db> VecLitIR<'db> {
+    /// Pushes the items onto a new vec one at a time, so they're evaluated in order
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let mut code = vec![ByteCodeNode::Code(vec![ByteCode::Construct {
+            id: 0,
+            len: 0,
+        }])];
+        for (item, _) in &self.items {
+            code.push(ByteCodeNode::Code(vec![ByteCode::Copy]));
+            code.push(item.build(state));
+            code.push(ByteCodeNode::Code(vec![ByteCode::VecPush]));
+        }
+        ByteCodeNode::Block(code)
+    }
+}
@@ -1,5 +1,9 @@
 use crate::{
-    check::{build_state::BuildState, scoped_state::Scoped as _, state::CheckState},
+    check::{
+        build_state::BuildState,
+        scoped_state::{Scope, Scoped as _},
+        state::CheckState,
+    },
     ir::{builder::ByteCodeNode, common::condition::ConditionIR, ContainsOffset, IrNode},
     parser::expr::while_::While,
     ty::Ty,
@@ -13,14 +17,19 @@ use super::{
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct WhileIR<'db> {
+    pub label: Option<Spanned<String>>,
     pub condition: Box<Spanned<ConditionIR<'db>>>,
     pub block: Spanned<CodeBlockIR<'db>>,
+    /// Holds the variables bound by a `while let`, which only exist in the loop
+    pub scope: Scope<'db>,
 }
 
 impl<'db> While {
     pub fn check(&self, state: &mut CheckState<'db>) -> ExprIR<'db> {
+        state.enter_scope();
         let condition = self.condition.0.check(state, self.condition.1);
         let condition = (condition, self.condition.1);
+        state.enter_loop(self.label.as_ref(), false);
         let ExprIR {
             data: ExprIRData::CodeBlock(block),
             ..
@@ -28,11 +37,15 @@ impl<'db> While {
         else {
             panic!("Expected block")
         };
+        state.exit_loop();
         let block = (block, self.block.1);
+        let scope = state.exit_scope();
         ExprIR {
             data: ExprIRData::While(WhileIR {
+                label: self.label.clone(),
                 condition: Box::new(condition),
                 block,
+                scope,
             }),
             ty: Ty::unit(),
             order: state.inc_order(),
@@ -42,6 +55,7 @@ impl<'db> While {
 
 impl<'db> IrNode<'db> for WhileIR<'db> {
     fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        state.push_scope(self.scope.clone());
         if self.condition.1.contains_offset(offset) {
             return self.condition.0.at_offset(offset, state);
         }
@@ -67,13 +81,14 @@ impl<'db> IrNode<'db> for WhileIR<'db> {
 
 impl<'db> WhileIR<'db> {
     pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
-        match &self.condition.0 {
+        state.enter_scope();
+        let while_ = match &self.condition.0 {
             ConditionIR::Let(let_) => {
                 let cond = vec![let_.expr.0.build(state), let_.pattern.0.build_match(state)];
                 let then = vec![
                     let_.pattern.0.build(state),
                     self.block.0.build(state),
-                    ByteCodeNode::Continue,
+                    ByteCodeNode::Continue(None),
                 ];
                 ByteCodeNode::While(
                     Box::new(ByteCodeNode::Block(cond)),
@@ -82,12 +97,60 @@ impl<'db> WhileIR<'db> {
             }
             ConditionIR::Expr(expr) => {
                 let cond = vec![expr.build(state), ByteCodeNode::MaybeBreak];
-                let then = vec![self.block.0.build(state), ByteCodeNode::Continue];
+                let then = vec![self.block.0.build(state), ByteCodeNode::Continue(None)];
                 ByteCodeNode::While(
                     Box::new(ByteCodeNode::Block(cond)),
                     Box::new(ByteCodeNode::Block(then)),
                 )
             }
-        }
+        };
+        state.exit_scope();
+        while_.labelled(self.label.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::{check_src, run_src};
+
+    #[test]
+    fn test_while_let_bindings_stay_in_loop() {
+        let errors = check_src(
+            "use std::Int
+use std::Option
+use std::Range
+
+fn main() {
+    let range = 0..3
+    while let Option::Some(item) = range.next() {
+        let inside: Int = item
+    }
+    let after = item
+}
+",
+        );
+        assert_eq!(errors, vec!["Unresolved name `item`".to_string()]);
+    }
+
+    #[test]
+    fn test_while_let_runs_until_no_match() {
+        let output = run_src(
+            "use std::Int
+use std::Option
+use std::Range
+use std::println
+
+fn main() {
+    let range = 1..4
+    let mut total = 0
+    while let Option::Some(item) = range.next() {
+        total = total + item
+        println(total)
+    }
+    println(\"Done\")
+}
+",
+        );
+        assert_eq!(output, "1\n3\n6\nDone\n");
     }
 }
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 
 use async_lsp::lsp_types::CompletionItem;
 use gvm::format::ByteCodeFile;
-use salsa::plumbing::AsId;
 use top::TopIR;
 
 use crate::{
@@ -120,26 +119,23 @@ impl<'db> FileIR<'db> {
             .flat_map(|(top, _)| top.build(state))
             .collect();
         let tables = state.vtables.clone();
-        let file_names = state.db.files();
-        let file_names = file_names
+        let file_names = state
+            .db
+            .files()
             .iter()
-            .map(|f| {
-                (
-                    f.as_id().as_u32(),
-                    f.path(state.db)
-                        .to_string_lossy()
-                        .strip_prefix(&state.db.root())
-                        .unwrap()
-                        .strip_prefix("/")
-                        .unwrap()
-                        .to_string(),
-                )
-            })
+            .map(|f| (state.get_file_id(**f), state.get_file_name(**f)))
             .collect();
+        let type_names = state.type_names.clone();
+        let func_names = state.func_names.clone();
+        let table_names = state.table_names.clone();
         ByteCodeFile {
+            entry: state.entry,
             funcs,
             tables,
             file_names,
+            type_names,
+            func_names,
+            table_names,
         }
     }
 }
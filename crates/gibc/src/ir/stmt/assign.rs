@@ -1,7 +1,12 @@
 use gvm::format::instr::ByteCode;
 
 use crate::{
-    check::{build_state::BuildState, state::CheckState},
+    check::{
+        build_state::BuildState,
+        err::{immutable::ImmutableAssign, CheckError},
+        scoped_state::Scoped as _,
+        state::{CheckState, Mutability},
+    },
     db::decl::{struct_::StructDecl, Decl, DeclKind},
     ir::{
         builder::ByteCodeNode,
@@ -9,7 +14,10 @@ use crate::{
         ContainsOffset, IrNode,
     },
     item::definitions::ident::IdentDef,
-    parser::{expr::Expr, stmt::assign::Assign},
+    parser::{
+        expr::{op::OpKind, Expr},
+        stmt::assign::Assign,
+    },
     ty::Ty,
     util::Spanned,
 };
@@ -17,13 +25,23 @@ use crate::{
 #[derive(Debug, PartialEq, Clone)]
 pub struct AssignIR<'db> {
     pub refr: Spanned<ExprIR<'db>>,
+    pub op: Option<OpKind>,
     pub value: Spanned<ExprIR<'db>>,
 }
 
 impl<'db> Assign {
     pub fn check(&self, state: &mut CheckState<'db>) -> AssignIR<'db> {
-        let refr = (self.refr.0.check(state), self.refr.1);
-        let value = if let Ty::Unknown = &refr.0.ty {
+        let refr = match (&self.refr.0, &self.op) {
+            (Expr::Index(index), Some(_)) => (index.check_compound_assign(state), self.refr.1),
+            (Expr::Index(index), None) => (index.check_assign(state), self.refr.1),
+            (refr, _) => (refr.check(state), self.refr.1),
+        };
+        let value = if let Some(op) = &self.op {
+            let value = (self.value.0.check(state), self.value.1);
+            let ty = op.check(&refr, &value, state);
+            ty.expect_is_instance_of(&refr.0.ty, state, self.value.1);
+            value
+        } else if let Ty::Unknown = &refr.0.ty {
             (self.value.0.check(state), self.value.1)
         } else {
             (
@@ -31,10 +49,41 @@ impl<'db> Assign {
                 self.value.1,
             )
         };
-        if !matches!(self.refr.0, Expr::Field(_) | Expr::Ident(_)) {
-            state.simple_error("Expected a ident or field", self.refr.1);
+        if !matches!(
+            self.refr.0,
+            Expr::Field(_) | Expr::Ident(_) | Expr::Index(_)
+        ) {
+            state.simple_error("Expected an ident, field or index", self.refr.1);
         };
-        AssignIR { refr, value }
+        self.check_mutable(state);
+        AssignIR {
+            refr,
+            op: self.op.clone(),
+            value,
+        }
+    }
+
+    /// Only the variable itself needs to be mutable, as fields and indexes change the value
+    fn check_mutable(&self, state: &mut CheckState<'db>) {
+        let Expr::Ident(path) = &self.refr.0 else {
+            return;
+        };
+        let [(name, _)] = path.as_slice() else {
+            return;
+        };
+        let Some(var) = state.get_variable(name) else {
+            return;
+        };
+        if var.mutability != Mutability::Mutable {
+            let error = ImmutableAssign {
+                name: name.clone(),
+                span: self.refr.1,
+                decl: var.span,
+                mutability: var.mutability,
+                file: state.file_data,
+            };
+            state.error(CheckError::ImmutableAssign(error));
+        }
     }
 }
 
@@ -84,20 +133,86 @@ impl<'db> AssignIR<'db> {
             ExprIRData::Field(field) => {
                 let index = field.decl.unwrap().get_field_index(&field.name.0, state);
                 let mut code = vec![field.struct_.0.build(state)];
-                code.push(self.value.0.build(state));
+                if let Some(op) = &self.op {
+                    // The struct is copied, so it's only evaluated once
+                    code.push(ByteCodeNode::Code(vec![
+                        ByteCode::Copy,
+                        ByteCode::Index(index),
+                    ]));
+                    code.push(self.value.0.build(state));
+                    code.push(ByteCodeNode::Code(vec![op.build()]));
+                } else {
+                    code.push(self.value.0.build(state));
+                }
                 code.push(ByteCodeNode::Code(vec![ByteCode::SetIndex(index)]));
                 ByteCodeNode::Block(code)
             }
             ExprIRData::Ident(name) => match &name.last().unwrap().0 {
                 IdentDef::Variable(var) => {
                     let id = state.get_var(&var.name).unwrap();
-                    let mut code = vec![self.value.0.build(state)];
+                    let mut code = vec![];
+                    if let Some(op) = &self.op {
+                        code.push(ByteCodeNode::Code(vec![ByteCode::GetLocal(id)]));
+                        code.push(self.value.0.build(state));
+                        code.push(ByteCodeNode::Code(vec![op.build()]));
+                    } else {
+                        code.push(self.value.0.build(state));
+                    }
                     code.push(ByteCodeNode::Code(vec![ByteCode::SetLocal(id)]));
                     ByteCodeNode::Block(code)
                 }
                 _ => panic!("Don't think so?"),
             },
+            ExprIRData::Index(index) => match &self.op {
+                Some(op) => index.build_compound_assign(op, &self.value.0, state),
+                None => index.build_assign(&self.value.0, state),
+            },
             _ => unreachable!(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_assign_needs_let_mut() {
+        let errors = check_src(
+            "use std::Int
+
+fn main() {
+    let count = 0
+    count = 1
+    count += 1
+    let mut total = 0
+    total = 1
+    total += count
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Can't assign to 'count', as it isn't declared with 'let mut'".to_string(),
+                "Can't assign to 'count', as it isn't declared with 'let mut'".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assign_to_param() {
+        let errors = check_src(
+            "use std::Int
+
+fn reset(count: Int) {
+    count = 0
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec!["Can't assign to 'count', as it isn't mutable".to_string()]
+        );
+    }
+}
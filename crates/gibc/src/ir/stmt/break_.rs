@@ -0,0 +1,117 @@
+use gvm::format::instr::ByteCode;
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState},
+    ir::{builder::ByteCodeNode, expr::ExprIR, ContainsOffset as _, IrNode},
+    parser::stmt::break_::{Break, Continue},
+    ty::Ty,
+    util::{Span, Spanned},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakIR<'db> {
+    pub label: Option<Spanned<String>>,
+    pub value: Option<Spanned<ExprIR<'db>>>,
+    /// Whether it breaks out of a `loop`, which has a unit value when it isn't given one
+    pub has_value: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueIR {
+    pub label: Option<Spanned<String>>,
+}
+
+impl<'db> Break {
+    pub fn check(&self, state: &mut CheckState<'db>, span: Span) -> BreakIR<'db> {
+        let found = state.find_loop(self.label.as_ref(), span);
+        let has_value = found.is_some_and(|index| state.loops[index].has_value);
+        let expected = found.and_then(|index| state.loops[index].ty.clone());
+        let value = self.value.as_ref().map(|(value, span)| {
+            if found.is_some() && !has_value {
+                state.simple_error("Only a 'loop' can 'break' with a value", *span);
+            }
+            let ir = match &expected {
+                Some(expected) if has_value => value.expect(state, expected, *span),
+                _ => value.check(state),
+            };
+            (ir, *span)
+        });
+        if let (Some(index), true) = (found, has_value) {
+            // A 'break' without a value gives the loop a unit value
+            let ty = value
+                .as_ref()
+                .map_or(Ty::unit(), |(value, _)| value.ty.clone());
+            match expected {
+                Some(expected) if value.is_none() => {
+                    ty.expect_is_instance_of(&expected, state, span)
+                }
+                Some(_) => {}
+                None => state.loops[index].ty = Some(ty),
+            }
+        }
+        BreakIR {
+            label: self.label.clone(),
+            value,
+            has_value: found.is_some() && has_value,
+        }
+    }
+}
+
+impl Continue {
+    pub fn check(&self, state: &mut CheckState<'_>, span: Span) -> ContinueIR {
+        state.find_loop(self.label.as_ref(), span);
+        ContinueIR {
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl<'db> IrNode<'db> for BreakIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        if let Some((value, span)) = &self.value {
+            if span.contains_offset(offset) {
+                return value.at_offset(offset, state);
+            }
+        }
+        self
+    }
+
+    fn tokens(
+        &self,
+        tokens: &mut Vec<crate::check::SemanticToken>,
+        state: &mut crate::ir::IrState<'db>,
+    ) {
+        if let Some((value, _)) = &self.value {
+            value.tokens(tokens, state);
+        }
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "BreakIR"
+    }
+}
+
+impl<'db> BreakIR<'db> {
+    /// Leaves the value on the stack as the result of the loop, then jumps out of it
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let mut code = vec![];
+        if let Some((value, _)) = &self.value {
+            code.push(value.build(state));
+        } else if self.has_value {
+            code.push(ByteCodeNode::Code(vec![ByteCode::Construct {
+                id: 0,
+                len: 0,
+            }]));
+        }
+        code.push(ByteCodeNode::Break(
+            self.label.as_ref().map(|(label, _)| label.clone()),
+        ));
+        ByteCodeNode::Block(code)
+    }
+}
+
+impl ContinueIR {
+    pub fn build(&self) -> ByteCodeNode {
+        ByteCodeNode::Continue(self.label.as_ref().map(|(label, _)| label.clone()))
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    check::{build_state::BuildState, state::CheckState},
+    check::{
+        build_state::BuildState,
+        state::{CheckState, Mutability},
+    },
     ir::{
         builder::ByteCodeNode, common::pattern::PatternIR, expr::ExprIR, ty::TypeIR,
         ContainsOffset, IrNode,
@@ -29,6 +32,11 @@ impl<'db> LetStatement {
             let expr = self.value.0.check(state);
             expr
         };
+        state.binding = if self.mutable {
+            Mutability::Mutable
+        } else {
+            Mutability::Let(self.pattern.1)
+        };
         let pattern = if let Some(explicit) = &explicit {
             (self.pattern.0.expect(state, &explicit.0.ty), self.pattern.1)
         } else if Ty::Unknown == expr.ty {
@@ -36,6 +44,7 @@ impl<'db> LetStatement {
         } else {
             (self.pattern.0.expect(state, &expr.ty), self.pattern.1)
         };
+        state.binding = Mutability::Immutable;
         LetIR {
             pattern,
             expr: Box::new((expr, self.value.1)),
@@ -1,5 +1,7 @@
 use assign::AssignIR;
+use break_::{BreakIR, ContinueIR};
 use let_::LetIR;
+use yield_::YieldIR;
 
 use crate::{
     check::{build_state::BuildState, state::CheckState, SemanticToken},
@@ -11,22 +13,25 @@ use crate::{
 use super::{builder::ByteCodeNode, expr::ExprIR, AstKind, IrNode, IrState};
 
 pub mod assign;
+pub mod break_;
 pub mod let_;
+pub mod yield_;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum StmtIR<'db> {
     Expr(Spanned<ExprIR<'db>>),
     Let(Spanned<LetIR<'db>>),
     Assign(Spanned<AssignIR<'db>>),
-    Break(Span),
-    Continue(Span),
+    Break(Spanned<BreakIR<'db>>),
+    Continue(Spanned<ContinueIR>),
+    Yield(Spanned<YieldIR<'db>>),
 }
 
 impl<'db> StmtIR<'db> {
     pub fn get_ty(&self) -> Ty<'db> {
         match self {
             StmtIR::Expr(e) => e.0.ty.clone(),
-            StmtIR::Let(_) | StmtIR::Assign(_) => Ty::unit(),
+            StmtIR::Let(_) | StmtIR::Assign(_) | StmtIR::Yield(_) => Ty::unit(),
             StmtIR::Break(_) | StmtIR::Continue(_) => Ty::Nothing,
         }
     }
@@ -38,8 +43,9 @@ impl<'db> Stmt {
             Stmt::Let(l) => StmtIR::Let((l.0.check(state), l.1)),
             Stmt::Expr(e) => StmtIR::Expr((e.0.check(state), e.1)),
             Stmt::Assign(e) => StmtIR::Assign((e.0.check(state), e.1)),
-            Stmt::Break(s) => StmtIR::Break(*s),
-            Stmt::Continue(s) => StmtIR::Continue(*s),
+            Stmt::Break(b) => StmtIR::Break((b.0.check(state, b.1), b.1)),
+            Stmt::Continue(c) => StmtIR::Continue((c.0.check(state, c.1), c.1)),
+            Stmt::Yield(y) => StmtIR::Yield((y.0.check(state), y.1)),
         }
     }
 
@@ -81,6 +87,21 @@ impl<'db> Stmt {
                 }
                 StmtIR::Assign((ir, a.1))
             }
+            Stmt::Yield(y) => {
+                let ir = y.0.check(state);
+                let actual = Ty::unit();
+                if !expected.eq(&actual) {
+                    state.simple_error(
+                        &format!(
+                            "Expected value to be of type '{}' but found '{}'",
+                            expected.get_name(state),
+                            actual.get_name(state),
+                        ),
+                        span,
+                    );
+                }
+                StmtIR::Yield((ir, y.1))
+            }
             Stmt::Break(_) | Stmt::Continue(_) => self.check(state),
         }
     }
@@ -93,7 +114,9 @@ impl<'db> IrNode<'db> for StmtIR<'db> {
             StmtIR::Expr(e) => e.0.at_offset(offset, state),
             StmtIR::Let(l) => l.0.at_offset(offset, state),
             StmtIR::Assign(a) => a.0.at_offset(offset, state),
-            StmtIR::Break(_) | StmtIR::Continue(_) => self,
+            StmtIR::Yield(y) => y.0.at_offset(offset, state),
+            StmtIR::Break(b) => b.0.at_offset(offset, state),
+            StmtIR::Continue(_) => self,
         }
     }
 
@@ -102,7 +125,9 @@ impl<'db> IrNode<'db> for StmtIR<'db> {
             StmtIR::Expr(e) => e.0.tokens(tokens, state),
             StmtIR::Let(l) => l.0.tokens(tokens, state),
             StmtIR::Assign(a) => a.0.tokens(tokens, state),
-            StmtIR::Break(_) | StmtIR::Continue(_) => {}
+            StmtIR::Yield(y) => y.0.tokens(tokens, state),
+            StmtIR::Break(b) => b.0.tokens(tokens, state),
+            StmtIR::Continue(_) => {}
         }
     }
 
@@ -117,8 +142,9 @@ impl<'db> StmtIR<'db> {
             StmtIR::Expr(e) => e.0.build(state),
             StmtIR::Let(l) => l.0.build(state),
             StmtIR::Assign(a) => a.0.build(state),
-            StmtIR::Continue(_) => ByteCodeNode::Continue,
-            StmtIR::Break(_) => ByteCodeNode::Break,
+            StmtIR::Yield(y) => y.0.build(state),
+            StmtIR::Continue(c) => c.0.build(),
+            StmtIR::Break(b) => b.0.build(state),
         };
         ByteCodeNode::Spanned(Box::new(res), self.span())
     }
@@ -130,7 +156,9 @@ impl StmtIR<'_> {
             StmtIR::Expr(e) => e.1,
             StmtIR::Let(l) => l.1,
             StmtIR::Assign(a) => a.1,
-            StmtIR::Break(s) | StmtIR::Continue(s) => *s,
+            StmtIR::Yield(y) => y.1,
+            StmtIR::Break(b) => b.1,
+            StmtIR::Continue(c) => c.1,
         }
     }
 }
@@ -0,0 +1,91 @@
+use gvm::format::instr::ByteCode;
+
+use crate::{
+    check::{build_state::BuildState, state::CheckState},
+    db::decl::{func::Function, DeclKind},
+    ir::{builder::ByteCodeNode, expr::ExprIR, IrNode},
+    parser::stmt::yield_::Yield,
+    ty::Ty,
+    util::Spanned,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct YieldIR<'db> {
+    pub value: Spanned<ExprIR<'db>>,
+}
+
+impl<'db> Yield {
+    pub fn check(&self, state: &mut CheckState<'db>) -> YieldIR<'db> {
+        let item_ty = match state.current_decl().kind(state.db) {
+            DeclKind::Function(Function { ret, .. }) => ret.generator_item(state.db),
+            _ => None,
+        };
+        let value = if state.in_lambda {
+            state.simple_error("'yield' can't be used in a lambda", self.value.1);
+            self.value.0.check(state)
+        } else if let Some(item_ty) = item_ty {
+            self.value.0.expect(state, &item_ty, self.value.1)
+        } else {
+            state.simple_error(
+                "'yield' can only be used in a function returning 'Generator'",
+                self.value.1,
+            );
+            self.value.0.check(state)
+        };
+        YieldIR {
+            value: (value, self.value.1),
+        }
+    }
+}
+
+impl<'db> IrNode<'db> for YieldIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        self.value.0.at_offset(offset, state)
+    }
+
+    fn tokens(
+        &self,
+        tokens: &mut Vec<crate::check::SemanticToken>,
+        state: &mut crate::ir::IrState<'db>,
+    ) {
+        self.value.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "YieldIR"
+    }
+}
+
+impl<'db> YieldIR<'db> {
+    pub fn build(&self, state: &mut BuildState<'db>) -> ByteCodeNode {
+        let some = state.get_std_type_id(&["Option", "Some"]);
+        ByteCodeNode::Block(vec![
+            self.value.0.build(state),
+            ByteCodeNode::Code(vec![ByteCode::Yield(some)]),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_yield_in_lambda() {
+        let errors = check_src(
+            r"
+            use std::Generator
+            use std::Int
+
+            fn numbers(): Generator[Int] {
+                yield 1
+                let later = { yield 2 }
+            }
+            ",
+        );
+        assert!(errors.contains(&"'yield' can't be used in a lambda".to_string()));
+        assert!(!errors
+            .iter()
+            .any(|err| err.contains("returning 'Generator'")));
+    }
+}
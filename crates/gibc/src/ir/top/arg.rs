@@ -1,7 +1,7 @@
 use crate::{
     check::{
         scoped_state::Scoped as _,
-        state::{CheckState, VarDecl},
+        state::{CheckState, Mutability, VarDecl},
         SemanticToken, TokenKind,
     },
     ir::{ty::TypeIR, ContainsOffset, IrNode, IrState},
@@ -23,6 +23,7 @@ impl<'db> FunctionArg {
             ty: ty.0.ty.clone(),
             kind: TokenKind::Param,
             span: self.name.1,
+            mutability: Mutability::Immutable,
         };
         state.insert_variable(&self.name.0, var);
         FunctionArgIR {
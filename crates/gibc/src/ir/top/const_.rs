@@ -0,0 +1,53 @@
+use crate::{
+    check::{state::CheckState, SemanticToken, TokenKind},
+    ir::{expr::ExprIR, ty::TypeIR, ContainsOffset, IrNode},
+    parser::top::const_::Const,
+    util::Spanned,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstIR<'db> {
+    pub name: Spanned<String>,
+    pub ty: Spanned<TypeIR<'db>>,
+    pub value: Spanned<ExprIR<'db>>,
+}
+
+impl<'db> Const {
+    pub fn check(&self, state: &mut CheckState<'db>) -> ConstIR<'db> {
+        let ty = (self.ty.0.check(state), self.ty.1);
+        let value = (
+            self.value.0.expect(state, &ty.0.ty, self.value.1),
+            self.value.1,
+        );
+        ConstIR {
+            name: self.name.clone(),
+            ty,
+            value,
+        }
+    }
+}
+
+impl<'db> IrNode<'db> for ConstIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        if self.ty.1.contains_offset(offset) {
+            return self.ty.0.at_offset(offset, state);
+        }
+        if self.value.1.contains_offset(offset) {
+            return self.value.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut crate::ir::IrState<'db>) {
+        tokens.push(SemanticToken {
+            span: self.name.1,
+            kind: TokenKind::Const,
+        });
+        self.ty.0.tokens(tokens, state);
+        self.value.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "ConstIR"
+    }
+}
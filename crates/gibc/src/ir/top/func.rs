@@ -7,21 +7,21 @@ use crate::{
     },
     db::decl::Decl,
     ir::{
-        builder::ByteCodeNode,
+        builder::{mark_tail_calls, ByteCodeNode},
         common::generic_args::GenericArgsIR,
         expr::{
             block::{check_block, expect_block, CodeBlockIR},
+            range::build_range_next,
             ExprIR, ExprIRData,
         },
         ty::TypeIR,
         ContainsOffset, IrNode, IrState,
     },
     parser::top::func::Func,
-    ty::Ty,
+    ty::{Generic, Ty},
     util::Spanned,
 };
 use gvm::format::{func::FuncDef, instr::ByteCode, literal::Literal};
-use salsa::plumbing::AsId;
 
 use super::arg::FunctionArgIR;
 
@@ -56,7 +56,10 @@ impl<'db> Func {
             (ir, *span)
         });
         let expected = ret.as_ref().map_or(Ty::unit(), |ret| ret.0.ty.clone());
-        let block = if !allow_empty || self.body.is_some() {
+        // A generator's body produces its values with 'yield', not as the block's value
+        let block = if expected.generator_item(state.db).is_some() {
+            check_block(self.body.as_ref().unwrap_or(&vec![]), state)
+        } else if !allow_empty || self.body.is_some() {
             expect_block(
                 self.body.as_ref().unwrap_or(&vec![]),
                 state,
@@ -139,6 +142,23 @@ impl<'db> IrNode<'db> for FuncIR<'db> {
 }
 
 impl<'db> FuncIR<'db> {
+    /// Whether this is `Range::next`, which shares its name with `Generator::next`
+    fn is_range_next(&self, state: &BuildState<'db>) -> bool {
+        self.receiver.as_ref().is_some_and(|receiver| {
+            let ty = match &receiver.0.ty {
+                Ty::Generic(Generic { super_, .. }) => super_.as_ref(),
+                ty => ty,
+            };
+            ty.std_args(state.db, "Range").is_some()
+        })
+    }
+
+    fn is_generator(&self, state: &BuildState<'db>) -> bool {
+        self.ret
+            .as_ref()
+            .is_some_and(|ret| ret.0.ty.generator_item(state.db).is_some())
+    }
+
     pub fn build(&self, state: &mut BuildState<'db>) -> (u32, FuncDef) {
         state.clear();
         let mut i = 0;
@@ -151,12 +171,12 @@ impl<'db> FuncIR<'db> {
             state.add_param(param.0.name.0.clone(), i);
             i += 1;
         }
-        let id = if self.name.0 == "main" {
-            0
-        } else {
-            self.decl.as_id().as_u32()
-        };
+        let id = state.get_func_id(self.decl);
         let path = self.decl.path(state.db).name(state.db);
+        // Only the root module's `main` starts the program, other modules can have their own
+        if path == ["main", "main"] {
+            state.entry = Some(id);
+        }
         let mut marks = vec![];
         let mut body = if path[0] == "std" {
             if let Some(name) = path.get(1) {
@@ -169,12 +189,40 @@ impl<'db> FuncIR<'db> {
                         ByteCode::Print,
                     ],
                     "panic" => vec![ByteCode::Param(0), ByteCode::Panic],
+                    "parse_int" => vec![
+                        ByteCode::Param(0),
+                        ByteCode::ParseInt {
+                            some: state.get_std_type_id(&["Option", "Some"]),
+                            none: state.get_std_type_id(&["Option", "None"]),
+                        },
+                    ],
+                    "parse_float" => vec![
+                        ByteCode::Param(0),
+                        ByteCode::ParseFloat {
+                            some: state.get_std_type_id(&["Option", "Some"]),
+                            none: state.get_std_type_id(&["Option", "None"]),
+                        },
+                    ],
+                    "spawn" => vec![
+                        ByteCode::Param(0),
+                        ByteCode::Spawn(state.get_func_id(state.get_std_decl(&["Task", "run"]))),
+                    ],
+                    "channel" => vec![ByteCode::Channel],
+                    "read_line" => vec![ByteCode::ReadLine {
+                        some: state.get_std_type_id(&["Option", "Some"]),
+                        none: state.get_std_type_id(&["Option", "None"]),
+                    }],
+                    "time" => vec![ByteCode::Time],
+                    "random" => vec![ByteCode::Random],
+                    "args" => vec![ByteCode::Args],
                     _ => vec![],
                 }
             } else {
                 match self.name.0.as_str() {
-                    "get" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::VecGet],
-                    "set" => vec![
+                    "get" | "index" => {
+                        vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::VecGet]
+                    }
+                    "set" | "set_index" => vec![
                         ByteCode::Param(0),
                         ByteCode::Param(2),
                         ByteCode::Param(1),
@@ -194,21 +242,50 @@ impl<'db> FuncIR<'db> {
                     "remove" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::VecRemove],
                     "len" => vec![ByteCode::Param(0), ByteCode::VecLen],
                     "new" => vec![ByteCode::Construct { id: 0, len: 0 }],
+                    "sqrt" => vec![ByteCode::Param(0), ByteCode::Sqrt],
+                    "pow" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::Pow],
+                    "abs" => vec![ByteCode::Param(0), ByteCode::Abs],
+                    "floor" => vec![ByteCode::Param(0), ByteCode::Floor],
+                    "min" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::Min],
+                    "max" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::Max],
+                    "to_int" => vec![ByteCode::Param(0), ByteCode::ToInt],
+                    "to_float" => vec![ByteCode::Param(0), ByteCode::ToFloat],
+                    "to_string" => vec![ByteCode::Param(0), ByteCode::ToString],
+                    "send" => vec![ByteCode::Param(0), ByteCode::Param(1), ByteCode::Send],
+                    "receive" => vec![ByteCode::Param(0), ByteCode::Receive],
+                    "next" if self.is_range_next(state) => {
+                        build_range_next(state).build(0, 0, 0, 0, &[], &mut marks, text)
+                    }
+                    "next" => vec![
+                        ByteCode::Param(0),
+                        ByteCode::Resume(state.get_std_type_id(&["Option", "None"])),
+                    ],
                     _ => panic!("Unknown std function: {}", self.name.0),
                 }
             }
         } else {
-            ByteCodeNode::Block(
-                self.body
-                    .stmts
-                    .iter()
-                    .map(|(stmt, _)| stmt.build(state))
-                    .collect(),
-            )
-            .build(0, 0, 0, 0, &mut marks, text)
+            let mut stmts = self
+                .body
+                .stmts
+                .iter()
+                .map(|(stmt, _)| stmt.build(state))
+                .collect::<Vec<_>>();
+            if self.is_generator(state) {
+                let none = state.get_std_type_id(&["Option", "None"]);
+                stmts.insert(0, ByteCodeNode::Code(vec![ByteCode::Generator]));
+                stmts.push(ByteCodeNode::Code(vec![ByteCode::Construct {
+                    id: none,
+                    len: 0,
+                }]));
+            }
+            ByteCodeNode::Block(stmts).build(0, 0, 0, 0, &[], &mut marks, text)
         };
         marks.sort_by(|a, b| a.0.cmp(&b.0));
         body.push(ByteCode::Return);
+        // A generator's frame has to stay, as returning from it is what finishes the generator
+        if !self.is_generator(state) {
+            mark_tail_calls(&mut body);
+        }
         (
             id,
             FuncDef {
@@ -217,7 +294,7 @@ impl<'db> FuncIR<'db> {
                 marks,
                 name: self.name.0.to_string(),
                 pos: state.get_pos(self.name.1),
-                file: state.file.as_id().as_u32(),
+                file: state.get_file_id(state.file),
             },
         )
     }
@@ -1,9 +1,11 @@
+use const_::ConstIR;
 use enum_::EnumIR;
 use func::FuncIR;
 use gvm::format::func::FuncDef;
 use impl_::ImplIR;
 use struct_::StructIR;
 use trait_::TraitIR;
+use type_alias::TypeAliasIR;
 
 use crate::{
     check::{
@@ -19,6 +21,7 @@ use crate::{
 use super::{common::pattern::SpannedQualifiedNameIR, IrNode, IrState};
 
 pub mod arg;
+pub mod const_;
 pub mod enum_;
 pub mod enum_member;
 pub mod func;
@@ -27,6 +30,7 @@ pub mod struct_;
 pub mod struct_body;
 pub mod struct_field;
 pub mod trait_;
+pub mod type_alias;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TopIRData<'db> {
@@ -35,6 +39,8 @@ pub enum TopIRData<'db> {
     Enum(EnumIR<'db>),
     Trait(TraitIR<'db>),
     Impl(ImplIR<'db>),
+    TypeAlias(TypeAliasIR<'db>),
+    Const(ConstIR<'db>),
     Use(SpannedQualifiedNameIR<'db>),
 }
 
@@ -62,6 +68,8 @@ impl<'db> Top {
             Top::Struct(s) => TopIRData::Struct(s.check(state)),
             Top::Func(f) => TopIRData::Func(f.check(state, false)),
             Top::Impl(i) => TopIRData::Impl(i.check(state)),
+            Top::TypeAlias(t) => TopIRData::TypeAlias(t.check(state)),
+            Top::Const(c) => TopIRData::Const(c.check(state)),
             Top::Use(_) => unreachable!(),
         };
         if name.is_some() {
@@ -87,6 +95,8 @@ impl<'db> IrNode<'db> for TopIR<'db> {
             TopIRData::Enum(e) => e.at_offset(offset, state),
             TopIRData::Trait(t) => t.at_offset(offset, state),
             TopIRData::Impl(i) => i.at_offset(offset, state),
+            TopIRData::TypeAlias(t) => t.at_offset(offset, state),
+            TopIRData::Const(c) => c.at_offset(offset, state),
             TopIRData::Use(u) => u.at_offset(offset, state),
         };
         res
@@ -99,6 +109,8 @@ impl<'db> IrNode<'db> for TopIR<'db> {
             TopIRData::Enum(e) => e.tokens(tokens, state),
             TopIRData::Trait(t) => t.tokens(tokens, state),
             TopIRData::Impl(i) => i.tokens(tokens, state),
+            TopIRData::TypeAlias(t) => t.tokens(tokens, state),
+            TopIRData::Const(c) => c.tokens(tokens, state),
             TopIRData::Use(u) => u.tokens(tokens, state),
         }
     }
@@ -0,0 +1,50 @@
+use crate::{
+    check::{state::CheckState, SemanticToken, TokenKind},
+    ir::{common::generic_args::GenericArgsIR, ty::TypeIR, ContainsOffset, IrNode},
+    parser::top::type_alias::TypeAlias,
+    util::Spanned,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAliasIR<'db> {
+    pub name: Spanned<String>,
+    pub generics: Spanned<GenericArgsIR<'db>>,
+    pub ty: Spanned<TypeIR<'db>>,
+}
+
+impl<'db> TypeAlias {
+    pub fn check(&self, state: &mut CheckState<'db>) -> TypeAliasIR<'db> {
+        let generics = (self.generics.0.check(state), self.generics.1);
+        let ty = (self.ty.0.check(state), self.ty.1);
+        TypeAliasIR {
+            name: self.name.clone(),
+            generics,
+            ty,
+        }
+    }
+}
+
+impl<'db> IrNode<'db> for TypeAliasIR<'db> {
+    fn at_offset(&self, offset: usize, state: &mut crate::ir::IrState<'db>) -> &dyn IrNode {
+        if self.generics.1.contains_offset(offset) {
+            return self.generics.0.at_offset(offset, state);
+        }
+        if self.ty.1.contains_offset(offset) {
+            return self.ty.0.at_offset(offset, state);
+        }
+        self
+    }
+
+    fn tokens(&self, tokens: &mut Vec<SemanticToken>, state: &mut crate::ir::IrState<'db>) {
+        tokens.push(SemanticToken {
+            span: self.name.1,
+            kind: TokenKind::TypeAlias,
+        });
+        self.generics.0.tokens(tokens, state);
+        self.ty.0.tokens(tokens, state);
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "TypeAliasIR"
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use crate::{
     check::{err::CheckError, scoped_state::Scoped as _, state::CheckState, SemanticToken},
+    db::decl::DeclKind,
     ir::{common::pattern::SpannedQualifiedNameIR, ContainsOffset, IrNode, IrState},
     parser::common::type_::NamedType,
     ty::{Named, Ty},
@@ -45,11 +48,36 @@ impl<'db> NamedType {
                 for (arg, _gen) in self.args.iter().zip(decl.generics(state.db)) {
                     args.push((arg.0.check(state), arg.1));
                 }
-                let arg_tys = args.iter().map(|ir| ir.0.ty.clone()).collect();
-                let ty = Ty::Named(Named {
-                    name: decl.path(state.db),
-                    args: arg_tys,
-                });
+                let arg_tys = args.iter().map(|ir| ir.0.ty.clone()).collect::<Vec<_>>();
+                let ty = if let DeclKind::TypeAlias { generics, ty } = decl.kind(state.db) {
+                    if generics.len() == self.args.len() {
+                        let generics = generics
+                            .iter()
+                            .map(|generic| generic.name.0.clone())
+                            .zip(arg_tys)
+                            .collect::<HashMap<_, _>>();
+                        ty.parameterize(&generics)
+                    } else {
+                        let span: Span = (self.name.first().unwrap().1.start
+                            ..self.name.last().unwrap().1.end)
+                            .into();
+                        state.simple_error(
+                            &format!(
+                                "Expected {} type argument(s) for '{}', but found {}",
+                                generics.len(),
+                                decl.name(state.db),
+                                self.args.len()
+                            ),
+                            span,
+                        );
+                        Ty::Unknown
+                    }
+                } else {
+                    Ty::Named(Named {
+                        name: decl.path(state.db),
+                        args: arg_tys,
+                    })
+                };
                 TypeIR {
                     data: TypeIRData::Named(NamedTypeIR {
                         name: state.get_ident_ir(&self.name),
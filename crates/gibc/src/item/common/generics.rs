@@ -1,4 +1,8 @@
-use crate::{item::AstItem, parser::common::generic_args::GenericArgs, util::Spanned};
+use crate::{
+    item::{pretty_docs, AstItem},
+    parser::common::generic_args::GenericArgs,
+    util::Spanned,
+};
 
 impl AstItem for GenericArgs {
     fn item_name(&self) -> &'static str {
@@ -70,7 +74,8 @@ where
 
 pub fn comma_sep_braces<'b, D, A, T>(
     allocator: &'b D,
-    items: &'b [T],
+    items: &'b [Spanned<T>],
+    docs: &'b [Spanned<Vec<&'b str>>],
 ) -> pretty::DocBuilder<'b, D, A>
 where
     D: pretty::DocAllocator<'b, A>,
@@ -79,12 +84,15 @@ where
     T: AstItem,
 {
     let separator = allocator.text(",").append(allocator.hardline());
+    let items = items
+        .iter()
+        .map(|(item, span)| pretty_docs(allocator, docs, *span).append(item.pretty(allocator)));
     allocator
         .text(" {")
         .append(
             allocator
                 .hardline()
-                .append(allocator.intersperse(items.iter().map(|i| i.pretty(allocator)), separator))
+                .append(allocator.intersperse(items, separator))
                 .nest(4),
         )
         .append(allocator.hardline())
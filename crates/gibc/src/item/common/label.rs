@@ -0,0 +1,8 @@
+use crate::util::Spanned;
+
+/// The `'label: ` written in front of a loop, if it has one
+pub fn loop_label(label: &Option<Spanned<String>>) -> String {
+    label
+        .as_ref()
+        .map_or(String::new(), |(label, _)| format!("'{label}: "))
+}
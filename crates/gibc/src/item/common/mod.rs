@@ -1,6 +1,7 @@
 pub mod condition;
 pub mod generic;
 pub mod generics;
+pub mod label;
 pub mod named;
 pub mod pattern;
 pub mod type_;
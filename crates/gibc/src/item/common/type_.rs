@@ -18,7 +18,7 @@ impl AstItem for Type {
             Type::Wildcard(_) => allocator.text("_"),
             Type::Named(named) => named.pretty(allocator),
             Type::Tuple(tys) => brackets(allocator, "(", ")", tys),
-            Type::Sum(tys) => comma_sep_braces(allocator, tys),
+            Type::Sum(tys) => comma_sep_braces(allocator, tys, &[]),
             Type::Function {
                 receiver,
                 args,
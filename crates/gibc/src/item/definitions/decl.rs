@@ -6,14 +6,22 @@ use crate::{
     },
     ir::{AstKind, IrState},
 };
-use async_lsp::lsp_types::{CompletionItem, CompletionItemKind, InsertTextFormat};
+use async_lsp::lsp_types::{CompletionItem, CompletionItemKind, Documentation, InsertTextFormat};
 
 impl<'db> Decl<'db> {
     #[allow(unused)]
     pub fn hover(self, state: &mut CheckState<'db>) -> String {
         let path_name = self.path(state.db).name(state.db).join("::");
         let kind = self.get_kind_name(state.db);
-        format!("{kind} {path_name}")
+        self.with_doc(state.db, format!("{kind} {path_name}"))
+    }
+
+    /// Puts the declaration's doc comment below its hover text
+    pub fn with_doc(self, db: &'db dyn Db, hover: String) -> String {
+        match self.doc(db) {
+            Some(doc) => format!("{hover}\n\n{doc}"),
+            None => hover,
+        }
     }
 
     pub fn get_kind_name(self, db: &'db dyn Db) -> &'static str {
@@ -24,6 +32,8 @@ impl<'db> Decl<'db> {
             DeclKind::Member { .. } => "member",
             DeclKind::Function(Function { .. }) => "function",
             DeclKind::Module(_) => "module",
+            DeclKind::TypeAlias { .. } => "type",
+            DeclKind::Const { .. } => "const",
         }
     }
 
@@ -35,6 +45,8 @@ impl<'db> Decl<'db> {
             DeclKind::Function { .. } => CompletionItemKind::FUNCTION,
             DeclKind::Member { .. } => CompletionItemKind::ENUM_MEMBER,
             DeclKind::Module(_) => CompletionItemKind::MODULE,
+            DeclKind::TypeAlias { .. } => CompletionItemKind::TYPE_PARAMETER,
+            DeclKind::Const { .. } => CompletionItemKind::CONSTANT,
         }
     }
 
@@ -44,13 +56,14 @@ impl<'db> Decl<'db> {
         if state.kind != AstKind::Type
             || !matches!(
                 self.kind(state.db),
-                DeclKind::Function(_) | DeclKind::Member { .. }
+                DeclKind::Function(_) | DeclKind::Member { .. } | DeclKind::Const { .. }
             )
         {
             completions.push(CompletionItem {
                 label: self.name(state.db()),
                 kind: Some(self.complention_kind(state.db())),
                 detail: Some(self.path(state.db()).name(state.db()).join("::")),
+                documentation: self.doc(state.db()).clone().map(Documentation::String),
                 ..Default::default()
             });
         }
@@ -102,6 +115,7 @@ impl<'db> Decl<'db> {
             DeclKind::Struct { generics, .. }
             | DeclKind::Trait { generics, .. }
             | DeclKind::Enum { generics, .. }
+            | DeclKind::TypeAlias { generics, .. }
                 if !generics.is_empty() =>
             {
                 let name = self.name(state.db);
@@ -242,3 +256,76 @@ impl<'db> Function<'db> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        check::{check_file, resolve_project, tests::source_db},
+        db::input::Db as _,
+        ir::{IrNode as _, IrState},
+    };
+    use async_lsp::lsp_types::Documentation;
+
+    const SRC: &str = "use std::Int
+
+/// Adds one to `x`
+fn inc(x: Int): Int {
+    x + 1
+}
+
+fn main() {
+    inc(1)
+}
+";
+
+    #[test]
+    fn test_doc_in_hover_and_completions() {
+        let mut db = source_db(SRC);
+        let file = db.input(Path::new("/test/main.gib"));
+        let offset = SRC.rfind("inc").unwrap();
+        let project = resolve_project(&db, db.vfs.unwrap());
+        let ir = check_file(&db, file, project);
+        let mut state = IrState::new(&db, project, ir.type_vars(&db), file);
+        let node = ir.at_offset(offset, &mut state);
+        assert_eq!(
+            node.hover(offset, &mut state).as_deref(),
+            Some("function inc\n\nAdds one to `x`")
+        );
+        let completion = node
+            .completions(offset, &mut state)
+            .into_iter()
+            .find(|item| item.label == "inc")
+            .unwrap();
+        assert_eq!(
+            completion.documentation,
+            Some(Documentation::String("Adds one to `x`".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_alias_hover_shows_expansion() {
+        let src = "use std::Int
+use std::Option
+
+/// A value that might not be there
+type Maybe[T] = Option[T]
+
+fn main() {
+    let found: Maybe[Int] = Option::Some(3)
+}
+";
+        let mut db = source_db(src);
+        let file = db.input(Path::new("/test/main.gib"));
+        let offset = src.rfind("Maybe").unwrap();
+        let project = resolve_project(&db, db.vfs.unwrap());
+        let ir = check_file(&db, file, project);
+        let mut state = IrState::new(&db, project, ir.type_vars(&db), file);
+        let node = ir.at_offset(offset, &mut state);
+        assert_eq!(
+            node.hover(offset, &mut state).as_deref(),
+            Some("type Maybe[T] = Option[T]\n\nA value that might not be there")
+        );
+    }
+}
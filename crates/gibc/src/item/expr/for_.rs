@@ -1,4 +1,7 @@
-use crate::{item::AstItem, parser::expr::for_::For};
+use crate::{
+    item::{common::label::loop_label, AstItem},
+    parser::expr::for_::For,
+};
 
 impl AstItem for For {
     fn item_name(&self) -> &'static str {
@@ -13,7 +16,8 @@ impl AstItem for For {
         A: Clone,
     {
         allocator
-            .text("for")
+            .text(loop_label(&self.label))
+            .append("for")
             .append(allocator.space())
             .append(self.pattern.pretty(allocator))
             .append(allocator.space())
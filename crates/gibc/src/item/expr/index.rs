@@ -0,0 +1,21 @@
+use crate::{item::AstItem, parser::expr::index::Index};
+
+impl AstItem for Index {
+    fn item_name(&self) -> &'static str {
+        "index"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        self.expr
+            .0
+            .pretty(allocator)
+            .append("[")
+            .append(self.index.0.pretty(allocator))
+            .append("]")
+    }
+}
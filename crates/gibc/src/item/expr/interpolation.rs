@@ -0,0 +1,29 @@
+use crate::{
+    item::{expr::lit::escape, AstItem},
+    parser::expr::interpolation::{Interpolation, InterpolationPart},
+};
+
+impl AstItem for Interpolation {
+    fn item_name(&self) -> &'static str {
+        "interpolation"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let parts = self.parts.iter().map(|part| match part {
+            InterpolationPart::Text(text) => allocator.text(escape(text, '"')),
+            InterpolationPart::Expr((expr, _)) => allocator
+                .text("{")
+                .append(expr.pretty(allocator))
+                .append("}"),
+        });
+        allocator
+            .text("\"")
+            .append(allocator.concat(parts))
+            .append("\"")
+    }
+}
@@ -16,8 +16,8 @@ impl AstItem for Literal {
         match self {
             Literal::Int(i) => allocator.text(i.to_string()),
             Literal::Float(f) => allocator.text(f.to_string()),
-            Literal::String(s) => allocator.text(format!("\"{s}\"")),
-            Literal::Char(c) => allocator.text(format!("'{c}'")),
+            Literal::String(s) => allocator.text(format!("\"{}\"", escape(s, '"'))),
+            Literal::Char(c) => allocator.text(format!("'{}'", escape(&c.to_string(), '\''))),
             Literal::Bool(b) => {
                 if *b {
                     allocator.text("true")
@@ -28,3 +28,24 @@ impl AstItem for Literal {
         }
     }
 }
+
+/// Escapes the text of a literal delimited by `quote`, so it lexes back to the same value
+pub fn escape(text: &str, quote: char) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            '\\' => escaped.push_str("\\\\"),
+            '{' if quote == '"' => escaped.push_str("\\{"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
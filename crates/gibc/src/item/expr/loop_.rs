@@ -0,0 +1,23 @@
+use crate::{
+    item::{common::label::loop_label, AstItem},
+    parser::expr::loop_::Loop,
+};
+
+impl AstItem for Loop {
+    fn item_name(&self) -> &'static str {
+        "Loop"
+    }
+
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        allocator
+            .text(loop_label(&self.label))
+            .append("loop")
+            .append(self.block.0.pretty(allocator))
+    }
+}
@@ -11,10 +11,24 @@ impl AstItem for MatchArm {
         D::Doc: Clone,
         A: Clone,
     {
-        let pattern = self.pattern.0.pretty(allocator);
+        let patterns = allocator.intersperse(
+            self.patterns
+                .iter()
+                .map(|(pattern, _)| pattern.pretty(allocator)),
+            allocator.text(" | "),
+        );
+        let patterns = if let Some((guard, _)) = &self.guard {
+            patterns
+                .append(allocator.space())
+                .append("if")
+                .append(allocator.space())
+                .append(guard.pretty(allocator))
+        } else {
+            patterns
+        };
         let expr = self.expr.0.pretty(allocator);
 
-        pattern
+        patterns
             .append(allocator.space())
             .append("=>")
             .append(allocator.space())
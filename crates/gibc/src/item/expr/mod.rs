@@ -12,12 +12,18 @@ pub mod field;
 pub mod for_;
 pub mod ident;
 pub mod if_else;
+pub mod index;
+pub mod interpolation;
 pub mod lambda;
 pub mod lit;
+pub mod loop_;
 pub mod match_;
 pub mod match_arm;
 pub mod member_call;
 pub mod op;
+pub mod range;
+pub mod try_;
+pub mod vec_lit;
 pub mod while_;
 
 impl AstItem for Expr {
@@ -48,7 +54,13 @@ impl AstItem for Expr {
             Expr::Field(field) => field.pretty(allocator),
             Expr::Lambda(lambda) => lambda.pretty(allocator),
             Expr::While(while_) => while_.pretty(allocator),
+            Expr::Loop(loop_) => loop_.pretty(allocator),
             Expr::For(for_) => for_.pretty(allocator),
+            Expr::Try(try_) => try_.pretty(allocator),
+            Expr::Interpolation(interpolation) => interpolation.pretty(allocator),
+            Expr::VecLit(vec_lit) => vec_lit.pretty(allocator),
+            Expr::Index(index) => index.pretty(allocator),
+            Expr::Range(range) => range.pretty(allocator),
         }
     }
 }
@@ -0,0 +1,20 @@
+use crate::{item::AstItem, parser::expr::range::Range};
+
+impl AstItem for Range {
+    fn item_name(&self) -> &'static str {
+        "range"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        self.start
+            .0
+            .pretty(allocator)
+            .append(if self.inclusive { "..=" } else { ".." })
+            .append(self.end.0.pretty(allocator))
+    }
+}
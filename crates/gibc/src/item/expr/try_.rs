@@ -0,0 +1,16 @@
+use crate::{item::AstItem, parser::expr::try_::Try};
+
+impl AstItem for Try {
+    fn item_name(&self) -> &'static str {
+        "try"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        self.expr.0.pretty(allocator).append("?")
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{
+    item::{common::generics::brackets, AstItem},
+    parser::expr::vec_lit::VecLit,
+};
+
+impl AstItem for VecLit {
+    fn item_name(&self) -> &'static str {
+        "vec"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        brackets(allocator, "[", "]", &self.items)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{item::AstItem, parser::expr::while_::While};
+use crate::{
+    item::{common::label::loop_label, AstItem},
+    parser::expr::while_::While,
+};
 
 impl AstItem for While {
     fn item_name(&self) -> &'static str {
@@ -13,7 +16,8 @@ impl AstItem for While {
         A: Clone,
     {
         allocator
-            .text("while")
+            .text(loop_label(&self.label))
+            .append("while")
             .append(allocator.space())
             .append(self.condition.0.pretty(allocator))
             .append(self.block.0.pretty(allocator))
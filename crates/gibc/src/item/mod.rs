@@ -4,7 +4,7 @@ use pretty::{DocAllocator, DocBuilder};
 
 use crate::{
     parser::{top::Top, Ast},
-    util::Spanned,
+    util::{Span, Spanned},
 };
 
 pub mod common;
@@ -41,8 +41,37 @@ impl<T: AstItem> AstItem for Spanned<T> {
 }
 
 impl<'db> Ast<'db> {}
+
+/// The doc comment lines above each documented item, with the item's span
+pub type Docs<'a> = Vec<Spanned<Vec<&'a str>>>;
+
+/// The doc comments above the item at `span`, one line each
+pub fn pretty_docs<'b, D, A>(
+    allocator: &'b D,
+    docs: &'b [Spanned<Vec<&'b str>>],
+    span: Span,
+) -> DocBuilder<'b, D, A>
+where
+    D: DocAllocator<'b, A>,
+    D::Doc: Clone,
+    A: Clone,
+{
+    let Some((lines, _)) = docs.iter().find(|(_, doc_span)| *doc_span == span) else {
+        return allocator.nil();
+    };
+    allocator.concat(lines.iter().map(|doc| {
+        let line = if doc.is_empty() {
+            "///".to_string()
+        } else {
+            format!("/// {doc}")
+        };
+        allocator.text(line).append(allocator.hardline())
+    }))
+}
+
 pub fn pretty_format<'b, 'db, D, A>(
     ast: &'b [Spanned<Top>],
+    docs: &'b [Spanned<Vec<&'b str>>],
     allocator: &'b D,
 ) -> DocBuilder<'b, D, A>
 where
@@ -50,12 +79,150 @@ where
     D::Doc: Clone,
     A: Clone,
 {
-    let tops = ast.iter().map(|(item, _)| {
+    let tops = ast.iter().map(|(item, span)| {
+        let docs_above = pretty_docs(allocator, docs, *span);
         if let Top::Use(_) = item {
-            item.pretty(allocator)
+            docs_above.append(item.pretty(allocator))
         } else {
-            allocator.hardline().append(item.pretty(allocator))
+            allocator
+                .hardline()
+                .append(docs_above)
+                .append(item.pretty_with_docs(allocator, docs))
         }
     });
     allocator.intersperse(tops, allocator.hardline())
 }
+
+/// The doc comments directly above each top level item, struct field, enum member or function in
+/// a trait or impl, or `None` if some of the file's docs aren't above one, as those would be lost
+/// by formatting
+pub fn item_docs<'a>(
+    text: &str,
+    tops: &[Spanned<Top>],
+    docs: &'a [Spanned<String>],
+) -> Option<Docs<'a>> {
+    let mut spans = vec![];
+    for (top, span) in tops {
+        spans.push(*span);
+        spans.extend(top.member_spans());
+    }
+    spans.sort_by_key(|span| span.start);
+    let mut remaining = docs;
+    let mut found = vec![];
+    for span in spans {
+        let (above, rest) =
+            remaining.split_at(remaining.partition_point(|(_, doc)| doc.end <= span.start));
+        let mut lines = vec![];
+        let mut start = span.start;
+        for (doc, doc_span) in above.iter().rev() {
+            if !text[doc_span.end..start].trim().is_empty() {
+                return None;
+            }
+            lines.push(doc.as_str());
+            start = doc_span.start;
+        }
+        if !lines.is_empty() {
+            lines.reverse();
+            found.push((lines, span));
+        }
+        remaining = rest;
+    }
+    remaining.is_empty().then_some(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{input::Input as _, Parser as _};
+    use pretty::BoxAllocator;
+
+    use crate::{
+        lexer::parser::{lexer, split_docs},
+        parser::file_parser,
+        util::Span,
+    };
+
+    use super::{item_docs, pretty_format};
+
+    /// Formats `text`, or returns `None` if its docs can't be kept
+    fn format(text: &str) -> Option<String> {
+        let (tokens, docs) = split_docs(lexer().parse(text).unwrap());
+        let ast = file_parser()
+            .parse(tokens.spanned(Span::splat(text.len())))
+            .unwrap();
+        let docs = item_docs(text, &ast, &docs)?;
+        Some(
+            pretty_format::<_, ()>(&ast, &docs, &BoxAllocator)
+                .1
+                .pretty(70)
+                .to_string(),
+        )
+    }
+
+    #[test]
+    fn test_format_keeps_docs() {
+        let formatted = format("/// A point\n///\n/// On a grid\nstruct Point\n").unwrap();
+        assert!(formatted.contains("/// A point\n///\n/// On a grid\nstruct Point"));
+    }
+
+    #[test]
+    fn test_format_skips_unattached_docs() {
+        assert_eq!(format("struct Point\n/// Nothing below\n"), None);
+        assert_eq!(format("/// A point\n// A comment\nstruct Point\n"), None);
+    }
+
+    #[test]
+    fn test_format_keeps_member_docs() {
+        let src = "struct Point {
+    /// Across
+    x: Int,
+    y: Int
+}
+
+enum Shape {
+    /// Round
+    Circle(Int),
+    Square(Int)
+}
+
+impl Point {
+    /// The distance from the origin
+    ///
+    /// Along the grid
+    fn Self.size(): Int {
+        x + y
+    }
+}
+";
+        let formatted = format(src).unwrap();
+        assert!(formatted.contains("{\n    /// Across\n    x: Int,\n    y: Int\n}"));
+        assert!(formatted.contains("{\n    /// Round\n    Circle(Int),\n    Square(Int)\n}"));
+        assert!(formatted.contains(
+            "{\n    /// The distance from the origin\n    ///\n    /// Along the grid\n    fn "
+        ));
+        assert_eq!(format(&formatted).unwrap(), formatted);
+        assert_eq!(
+            format("struct Point {\n    x: Int\n    /// Nothing below\n}\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_format_if_let_and_while_let() {
+        let src = "fn main() {
+    while let Option::Some(item) = range.next() {
+        println(item)
+    }
+    if let Option::Some(first) = range.next() {
+        println(first)
+    } else if let Option::None = range.next() {
+        println(0)
+    }
+}
+";
+        let formatted = format(src).unwrap();
+        assert!(formatted.contains("while let Option::Some(item) = range.next() {"));
+        assert!(formatted.contains("if let Option::Some(first) = range.next() {"));
+        assert!(formatted.contains("} else if let Option::None = range.next() {"));
+        assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+}
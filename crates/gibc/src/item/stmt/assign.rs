@@ -14,7 +14,10 @@ impl AstItem for Assign {
         self.refr
             .pretty(allocator)
             .append(allocator.space())
-            .append("=")
+            .append(match &self.op {
+                Some(op) => format!("{op}="),
+                None => "=".to_string(),
+            })
             .append(allocator.space())
             .append(self.value.pretty(allocator))
     }
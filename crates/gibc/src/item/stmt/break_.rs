@@ -0,0 +1,48 @@
+use crate::{
+    item::AstItem,
+    parser::stmt::break_::{Break, Continue},
+};
+
+impl AstItem for Break {
+    fn item_name(&self) -> &'static str {
+        "break"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let mut doc = allocator.text("break");
+        if let Some((label, _)) = &self.label {
+            doc = doc.append(allocator.space()).append(format!("'{label}"));
+        }
+        if let Some(value) = &self.value {
+            doc = doc
+                .append(allocator.space())
+                .append(value.pretty(allocator));
+        }
+        doc
+    }
+}
+
+impl AstItem for Continue {
+    fn item_name(&self) -> &'static str {
+        "continue"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let doc = allocator.text("continue");
+        if let Some((label, _)) = &self.label {
+            doc.append(allocator.space()).append(format!("'{label}"))
+        } else {
+            doc
+        }
+    }
+}
@@ -21,7 +21,7 @@ impl AstItem for LetStatement {
             None => allocator.nil(),
         };
         allocator
-            .text("let")
+            .text(if self.mutable { "let mut" } else { "let" })
             .append(allocator.space())
             .append(self.pattern.0.pretty(allocator))
             .append(ty)
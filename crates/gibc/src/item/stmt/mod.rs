@@ -3,7 +3,9 @@ use crate::parser::stmt::Stmt;
 use super::AstItem;
 
 pub mod assign;
+pub mod break_;
 pub mod let_;
+pub mod yield_;
 
 impl AstItem for Stmt {
     fn item_name(&self) -> &'static str {
@@ -20,8 +22,9 @@ impl AstItem for Stmt {
             Stmt::Expr(e) => e.pretty(allocator),
             Stmt::Let(l) => l.pretty(allocator),
             Stmt::Assign(a) => a.pretty(allocator),
-            Stmt::Break(_) => allocator.text("break"),
-            Stmt::Continue(_) => allocator.text("continue"),
+            Stmt::Break(b) => b.pretty(allocator),
+            Stmt::Continue(c) => c.pretty(allocator),
+            Stmt::Yield(y) => y.pretty(allocator),
         }
     }
 }
@@ -0,0 +1,19 @@
+use crate::{item::AstItem, parser::stmt::yield_::Yield};
+
+impl AstItem for Yield {
+    fn item_name(&self) -> &'static str {
+        "yield"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        allocator
+            .text("yield")
+            .append(allocator.space())
+            .append(self.value.pretty(allocator))
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     item::{common::generics::comma_sep_braces, AstItem},
     parser::top::enum_::Enum,
     range::span_to_range_str,
-    util::Span,
+    util::{Span, Spanned},
 };
 
 impl AstItem for Enum {
@@ -18,17 +18,31 @@ impl AstItem for Enum {
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl Enum {
+    /// Pretty prints the enum, with the doc comments above its members
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
     {
         allocator
             .text("enum")
             .append(allocator.space())
             .append(self.name.0.clone())
             .append(self.generics.0.pretty(allocator))
-            .append(comma_sep_braces(allocator, &self.members))
+            .append(comma_sep_braces(allocator, &self.members, docs))
     }
-}
 
-impl Enum {
     pub fn document_symbol(&self, state: &mut CheckState, span: Span) -> DocumentSymbol {
         let txt = state.file_data.text(state.db);
         let range = span_to_range_str(span.into(), txt);
@@ -2,7 +2,7 @@ use async_lsp::lsp_types::{DocumentSymbol, SymbolKind};
 
 use crate::{
     check::state::CheckState,
-    item::AstItem,
+    item::{pretty_docs, AstItem},
     parser::top::impl_::Impl,
     range::span_to_range_str,
     util::{Span, Spanned},
@@ -18,6 +18,22 @@ impl AstItem for Impl {
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl Impl {
+    /// Pretty prints the impl, with the doc comments above its functions
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
     {
         let trait_ = if let Some(trait_) = &self.trait_ {
             trait_
@@ -36,13 +52,14 @@ impl AstItem for Impl {
             .append(trait_)
             .append(self.for_.0.pretty(allocator))
             .append(allocator.space())
-            .append(pretty_trait_body(allocator, &self.body))
+            .append(pretty_trait_body(allocator, &self.body, docs))
     }
 }
 
 pub fn pretty_trait_body<'b, D, A, T>(
     allocator: &'b D,
     items: &'b [Spanned<T>],
+    docs: &'b [Spanned<Vec<&'b str>>],
 ) -> pretty::DocBuilder<'b, D, A>
 where
     D: pretty::DocAllocator<'b, A>,
@@ -51,15 +68,15 @@ where
     T: AstItem,
 {
     let separator = allocator.hardline();
+    let items = items
+        .iter()
+        .map(|(item, span)| pretty_docs(allocator, docs, *span).append(item.pretty(allocator)));
     allocator
         .text("{")
         .append(
             allocator
                 .hardline()
-                .append(
-                    allocator
-                        .intersperse(items.iter().map(|(x, _)| x.pretty(allocator)), separator),
-                )
+                .append(allocator.intersperse(items, separator))
                 .nest(4),
         )
         .append(allocator.hardline())
@@ -2,12 +2,13 @@ use async_lsp::lsp_types::DocumentSymbol;
 
 use crate::{
     check::{scoped_state::Scoped as _, state::CheckState},
-    parser::top::Top,
-    util::Span,
+    parser::top::{struct_body::StructBody, Top},
+    util::{Span, Spanned},
 };
 
 use super::AstItem;
 
+pub mod const_;
 pub mod enum_;
 pub mod func;
 pub mod func_arg;
@@ -17,6 +18,7 @@ pub mod struct_;
 pub mod struct_body;
 pub mod struct_field;
 pub mod trait_;
+pub mod type_alias;
 pub mod use_;
 
 impl AstItem for Top {
@@ -29,22 +31,53 @@ impl AstItem for Top {
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl Top {
+    /// Pretty prints the item, with the doc comments above its members
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
     {
         match self {
-            Top::Struct(s) => s.pretty(allocator),
+            Top::Struct(s) => s.pretty_with_docs(allocator, docs),
             Top::Func(f) => f.pretty(allocator),
-            Top::Enum(e) => e.pretty(allocator),
-            Top::Trait(t) => t.pretty(allocator),
-            Top::Impl(i) => i.pretty(allocator),
+            Top::Enum(e) => e.pretty_with_docs(allocator, docs),
+            Top::Trait(t) => t.pretty_with_docs(allocator, docs),
+            Top::Impl(i) => i.pretty_with_docs(allocator, docs),
+            Top::TypeAlias(t) => t.pretty(allocator),
+            Top::Const(c) => c.pretty(allocator),
             Top::Use(u) => allocator
                 .text("use")
                 .append(allocator.space())
                 .append(u.pretty(allocator)),
         }
     }
-}
 
-impl Top {
+    /// The spans of the struct fields, enum members or trait and impl functions in the item,
+    /// which can have doc comments of their own
+    pub fn member_spans(&self) -> Vec<Span> {
+        match self {
+            Top::Struct(s) => match &s.body.0 {
+                StructBody::Fields(fields) => fields.iter().map(|(_, span)| *span).collect(),
+                StructBody::None | StructBody::Tuple(_) => vec![],
+            },
+            Top::Enum(e) => e.members.iter().map(|(_, span)| *span).collect(),
+            Top::Trait(t) => t.body.iter().map(|(_, span)| *span).collect(),
+            Top::Impl(i) => i.body.iter().map(|(_, span)| *span).collect(),
+            Top::Func(_) | Top::TypeAlias(_) | Top::Const(_) | Top::Use(_) => vec![],
+        }
+    }
+
     pub fn document_symbol(&self, state: &mut CheckState, span: Span) -> Option<DocumentSymbol> {
         state.enter_scope();
         let found = match self {
@@ -53,6 +86,8 @@ impl Top {
             Top::Enum(e) => Some(e.document_symbol(state, span)),
             Top::Trait(t) => Some(t.document_symbol(state, span)),
             Top::Impl(i) => Some(i.document_symbol(state, span)),
+            Top::TypeAlias(t) => Some(t.document_symbol(state, span)),
+            Top::Const(c) => Some(c.document_symbol(state, span)),
             Top::Use(u) => {
                 let _ = state.import(u);
                 None
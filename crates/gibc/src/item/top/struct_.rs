@@ -1,8 +1,11 @@
 use async_lsp::lsp_types::{DocumentSymbol, SymbolKind};
 
 use crate::{
-    check::state::CheckState, item::AstItem, parser::top::struct_::Struct,
-    range::span_to_range_str, util::Span,
+    check::state::CheckState,
+    item::AstItem,
+    parser::top::struct_::Struct,
+    range::span_to_range_str,
+    util::{Span, Spanned},
 };
 
 impl AstItem for Struct {
@@ -15,17 +18,31 @@ impl AstItem for Struct {
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl Struct {
+    /// Pretty prints the struct, with the doc comments above its fields
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
     {
         allocator
             .text("struct")
             .append(allocator.space())
             .append(self.name.0.clone())
             .append(self.generics.0.pretty(allocator))
-            .append(self.body.0.pretty(allocator))
+            .append(self.body.0.pretty_with_docs(allocator, docs))
     }
-}
 
-impl Struct {
     pub fn document_symbol(&self, state: &mut CheckState, span: Span) -> DocumentSymbol {
         let txt = state.file_data.text(state.db);
         let range = span_to_range_str(span.into(), txt);
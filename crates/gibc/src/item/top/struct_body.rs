@@ -8,6 +8,7 @@ use crate::{
     },
     parser::top::struct_body::StructBody,
     range::span_to_range_str,
+    util::Spanned,
 };
 
 impl AstItem for StructBody {
@@ -20,16 +21,30 @@ impl AstItem for StructBody {
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl StructBody {
+    /// Pretty prints the body, with the doc comments above its fields
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
     {
         match self {
             StructBody::None => allocator.nil(),
             StructBody::Tuple(tys) => brackets(allocator, "(", ")", tys),
-            StructBody::Fields(fields) => comma_sep_braces(allocator, fields),
+            StructBody::Fields(fields) => comma_sep_braces(allocator, fields, docs),
         }
     }
-}
 
-impl StructBody {
     pub fn document_symbols(&self, state: &mut CheckState) -> Vec<DocumentSymbol> {
         let txt = state.file_data.text(state.db);
         let mut symbols = Vec::new();
@@ -1,8 +1,11 @@
 use async_lsp::lsp_types::{DocumentSymbol, SymbolKind};
 
 use crate::{
-    check::state::CheckState, item::AstItem, parser::top::trait_::Trait, range::span_to_range_str,
-    util::Span,
+    check::state::CheckState,
+    item::AstItem,
+    parser::top::trait_::Trait,
+    range::span_to_range_str,
+    util::{Span, Spanned},
 };
 
 use super::impl_::pretty_trait_body;
@@ -12,6 +15,22 @@ impl AstItem for Trait {
         "trait"
     }
     fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        self.pretty_with_docs(allocator, &[])
+    }
+}
+
+impl Trait {
+    /// Pretty prints the trait, with the doc comments above its functions
+    pub fn pretty_with_docs<'b, D, A>(
+        &'b self,
+        allocator: &'b D,
+        docs: &'b [Spanned<Vec<&'b str>>],
+    ) -> pretty::DocBuilder<'b, D, A>
     where
         D: pretty::DocAllocator<'b, A>,
         D::Doc: Clone,
@@ -23,11 +42,9 @@ impl AstItem for Trait {
             .append(self.name.0.clone())
             .append(self.generics.0.pretty(allocator))
             .append(allocator.space())
-            .append(pretty_trait_body(allocator, &self.body))
+            .append(pretty_trait_body(allocator, &self.body, docs))
     }
-}
 
-impl Trait {
     pub fn document_symbol(&self, state: &mut CheckState, span: Span) -> DocumentSymbol {
         let txt = state.file_data.text(state.db);
         let range = span_to_range_str(span.into(), txt);
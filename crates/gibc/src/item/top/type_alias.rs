@@ -0,0 +1,47 @@
+use async_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+
+use crate::{
+    check::state::CheckState, item::AstItem, parser::top::type_alias::TypeAlias,
+    range::span_to_range_str, util::Span,
+};
+
+impl AstItem for TypeAlias {
+    fn item_name(&self) -> &'static str {
+        "type"
+    }
+    fn pretty<'b, D, A>(&'b self, allocator: &'b D) -> pretty::DocBuilder<'b, D, A>
+    where
+        Self: Sized,
+        D: pretty::DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        allocator
+            .text("type")
+            .append(allocator.space())
+            .append(self.name.0.clone())
+            .append(self.generics.0.pretty(allocator))
+            .append(allocator.space())
+            .append("=")
+            .append(allocator.space())
+            .append(self.ty.0.pretty(allocator))
+    }
+}
+
+impl TypeAlias {
+    pub fn document_symbol(&self, state: &mut CheckState, span: Span) -> DocumentSymbol {
+        let txt = state.file_data.text(state.db);
+        let range = span_to_range_str(span.into(), txt);
+        let selection_range = span_to_range_str(self.name.1.into(), txt);
+        DocumentSymbol {
+            name: self.name.0.clone(),
+            detail: Some("type".to_string()),
+            kind: SymbolKind::TYPE_PARAMETER,
+            range,
+            selection_range,
+            children: None,
+            tags: None,
+            deprecated: None,
+        }
+    }
+}
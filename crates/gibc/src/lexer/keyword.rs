@@ -19,6 +19,11 @@ pub enum Keyword {
     Return,
     Continue,
     Break,
+    Yield,
+    Loop,
+    Mut,
+    Type,
+    Const,
 }
 
 impl Display for Keyword {
@@ -41,6 +46,11 @@ impl Display for Keyword {
             Keyword::Return => write!(f, "return"),
             Keyword::Continue => write!(f, "continue"),
             Keyword::Break => write!(f, "break"),
+            Keyword::Yield => write!(f, "yield"),
+            Keyword::Loop => write!(f, "loop"),
+            Keyword::Mut => write!(f, "mut"),
+            Keyword::Type => write!(f, "type"),
+            Keyword::Const => write!(f, "const"),
         }
     }
 }
@@ -98,6 +108,21 @@ macro_rules! kw {
     (break) => {
         $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Break)
     };
+    (yield) => {
+        $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Yield)
+    };
+    (loop) => {
+        $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Loop)
+    };
+    (mut) => {
+        $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Mut)
+    };
+    (type) => {
+        $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Type)
+    };
+    (const) => {
+        $crate::lexer::token::Token::Keyword($crate::lexer::keyword::Keyword::Const)
+    };
 }
 
 #[cfg(test)]
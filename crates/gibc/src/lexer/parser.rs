@@ -1,12 +1,13 @@
 use chumsky::{
     error::Rich,
     extra,
-    primitive::{choice, end, just, none_of, one_of},
+    primitive::{any, choice, end, just, none_of, one_of},
+    recursive::recursive,
     text, IterParser, Parser,
 };
 use gvm::format::literal::Literal;
 
-use crate::util::Spanned;
+use crate::util::{Span, Spanned};
 
 use super::{keyword::Keyword, token::Token};
 
@@ -30,64 +31,234 @@ pub fn lexer<'src>(
         "return" => Token::Keyword(Keyword::Return),
         "continue" => Token::Keyword(Keyword::Continue),
         "break" => Token::Keyword(Keyword::Break),
+        "yield" => Token::Keyword(Keyword::Yield),
+        "loop" => Token::Keyword(Keyword::Loop),
+        "mut" => Token::Keyword(Keyword::Mut),
+        "type" => Token::Keyword(Keyword::Type),
+        "const" => Token::Keyword(Keyword::Const),
         "true" => Token::Literal(Literal::Bool(true)),
         "false" => Token::Literal(Literal::Bool(false)),
         _ => Token::Ident(ident.to_string()),
     });
 
-    let string = just("\\\"")
-        .or(none_of("\"").to_slice())
-        .repeated()
-        .to_slice()
-        .map(|s: &str| Token::Literal(Literal::String(s.to_string())))
-        .delimited_by(just('"'), just('"'));
-
-    let digits = text::digits(10).repeated().at_least(1);
+    // Digits of the given radix, which can be separated with '_' after the first
+    let digits = |radix: u32| {
+        any()
+            .filter(move |c: &char| c.is_digit(radix))
+            .then(
+                any()
+                    .filter(move |c: &char| c.is_digit(radix) || *c == '_')
+                    .repeated(),
+            )
+            .to_slice()
+    };
 
-    let float = digits
-        .then(just('.'))
-        .then(digits)
+    let exponent = one_of("eE").then(one_of("+-").or_not()).then(digits(10));
+    let float = digits(10)
+        .then(
+            just('.')
+                .then(digits(10))
+                .then(exponent.or_not())
+                .ignored()
+                .or(exponent.ignored()),
+        )
         .to_slice()
-        .map(|s: &str| Token::Literal(Literal::Float(s.parse().unwrap())));
+        .validate(|s: &str, e, emitter| {
+            let value = s.replace('_', "").parse::<f32>().unwrap_or(f32::INFINITY);
+            if value.is_infinite() {
+                emitter.emit(Rich::custom(
+                    e.span(),
+                    format!("Float literal '{s}' is too large for 'Float'"),
+                ));
+            }
+            Token::Literal(Literal::Float(value))
+        });
 
-    let int = digits
-        .to_slice()
-        .map(|s: &str| Token::Literal(Literal::Int(s.parse().unwrap())));
+    let int = choice((
+        just("0x").ignore_then(digits(16)).map(|s| (16, s)),
+        just("0o").ignore_then(digits(8)).map(|s| (8, s)),
+        just("0b").ignore_then(digits(2)).map(|s| (2, s)),
+        digits(10).map(|s| (10, s)),
+    ))
+    .validate(|(radix, s): (u32, &str), e, emitter| {
+        let value = i32::from_str_radix(&s.replace('_', ""), radix).unwrap_or_else(|_| {
+            emitter.emit(Rich::custom(
+                e.span(),
+                format!("Integer literal '{}' is too large for 'Int'", e.slice()),
+            ));
+            0
+        });
+        Token::Literal(Literal::Int(value))
+    });
+
+    let unicode = just('u').ignore_then(
+        any()
+            .filter(char::is_ascii_hexdigit)
+            .repeated()
+            .at_least(1)
+            .at_most(6)
+            .to_slice()
+            .delimited_by(just('{'), just('}'))
+            .map(|hex: &str| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)),
+    );
+    let escape = just('\\')
+        .ignore_then(unicode.or(any().map(|c| match c {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '\\' | '"' | '\'' | '{' | '}' => Some(c),
+            _ => None,
+        })))
+        .validate(|c: Option<char>, e, emitter| {
+            c.unwrap_or_else(|| {
+                emitter.emit(Rich::custom(
+                    e.span(),
+                    format!("Invalid escape sequence '{}'", e.slice()),
+                ));
+                char::REPLACEMENT_CHARACTER
+            })
+        });
 
-    let char = none_of('\'')
+    let char = none_of("\\'")
+        .or(escape)
         .delimited_by(just('\''), just('\''))
         .map(|c: char| Token::Literal(Literal::Char(c)));
 
+    // Only tried after a char, so `'a'` is still the char `a`
+    let label = just('\'')
+        .ignore_then(text::ident())
+        .map(|label: &str| Token::Label(label.to_string()));
+
     let op = one_of("+-*/=<>_!&|%")
+        .and_is(just("//").or(just("/*")).not())
         .repeated()
         .at_least(1)
         .to_slice()
         .map(|s: &str| Token::Op(s.to_string()));
 
-    let punct = one_of("(){}[],.:;").map(Token::Punct);
+    let range = just("..=")
+        .or(just(".."))
+        .map(|s: &str| Token::Op(s.to_string()));
 
-    let whitespace = one_of(" \t").repeated();
+    let punct = one_of("(){}[],.:;?").map(Token::Punct);
+
+    // Block comments can be nested, so commenting out code never ends early on an inner `*/`
+    let block_comment = recursive(|block_comment| {
+        just("/*")
+            .then(
+                block_comment
+                    .or(any().and_is(just("*/").not()).ignored())
+                    .repeated(),
+            )
+            .then(just("*/"))
+            .ignored()
+    });
+
+    let whitespace = one_of(" \t").ignored().or(block_comment).repeated();
+
+    let token = recursive(|token| {
+        let text = none_of("\\\"{")
+            .or(escape)
+            .repeated()
+            .collect::<String>()
+            .map_with(|s, e| (s, e.span()));
+
+        // Each `{...}` holds the tokens of an expression, which can't contain a block
+        let interpolation = token
+            .and_is(just('}').not())
+            .padded_by(whitespace.clone())
+            .repeated()
+            .collect::<Vec<Vec<Spanned<Token>>>>()
+            .map(|tokens| tokens.into_iter().flatten().collect::<Vec<_>>())
+            .delimited_by(just('{'), just('}'));
+
+        let string = text
+            .then(interpolation.then(text).repeated().collect::<Vec<_>>())
+            .delimited_by(just('"'), just('"'))
+            .map_with(|(first, rest), e| string_tokens(first, rest, e.span()));
+
+        let single = choice((ident, char, label, float, int, op, range, punct))
+            .map_with(|t, e| vec![(t, e.span())]);
+        choice((string, single))
+    });
+
+    let doc = just("///")
+        .and_is(just("////").not())
+        .ignore_then(none_of('\n').repeated().to_slice())
+        .map_with(|s: &str, e| {
+            let s = s.strip_prefix(' ').unwrap_or(s);
+            Some((Token::Doc(s.to_string()), e.span()))
+        });
 
     let comment = just("//")
         .then(none_of('\n').repeated())
         .then(just('\n'))
         .ignored();
 
-    let newline = comment
-        .or(just('\n').ignored())
-        .padded_by(whitespace)
+    // A run of line breaks and comments is a single newline, followed by the doc comments in it
+    let newline = choice((doc, comment.to(None), just('\n').to(None)))
+        .padded_by(whitespace.clone())
         .repeated()
         .at_least(1)
-        .map(|()| Token::Newline);
+        .collect::<Vec<_>>()
+        .map_with(|docs, e| {
+            let mut tokens = vec![(Token::Newline, e.span())];
+            tokens.extend(docs.into_iter().flatten());
+            tokens
+        });
 
-    choice((newline, ident, char, float, int, string, op, punct))
-        .map_with(|t, e| (t, e.span()))
+    choice((newline, token))
         .padded_by(whitespace)
         .repeated()
-        .collect()
+        .collect::<Vec<_>>()
+        .map(|tokens| tokens.into_iter().flatten().collect())
         .then_ignore(end())
 }
 
+/// Separates the doc comments from the tokens the parser reads
+#[must_use]
+pub fn split_docs(tokens: Vec<Spanned<Token>>) -> (Vec<Spanned<Token>>, Vec<Spanned<String>>) {
+    let mut docs = vec![];
+    let tokens = tokens
+        .into_iter()
+        .filter_map(|(token, span)| {
+            if let Token::Doc(doc) = token {
+                docs.push((doc, span));
+                None
+            } else {
+                Some((token, span))
+            }
+        })
+        .collect();
+    (tokens, docs)
+}
+
+/// A string without interpolations is a single literal, otherwise its text is split around the
+/// tokens of each interpolated expression, with each part's span covering its quote or braces
+fn string_tokens(
+    first: Spanned<String>,
+    rest: Vec<(Vec<Spanned<Token>>, Spanned<String>)>,
+    span: Span,
+) -> Vec<Spanned<Token>> {
+    if rest.is_empty() {
+        return vec![(Token::Literal(Literal::String(first.0)), span)];
+    }
+    let widen = |span: Span| Span::from(span.start - 1..span.end + 1);
+    let mut tokens = vec![(Token::InterpStart(first.0), widen(first.1))];
+    let last = rest.len() - 1;
+    for (index, (interpolated, (text, span))) in rest.into_iter().enumerate() {
+        tokens.extend(interpolated);
+        let token = if index == last {
+            Token::InterpEnd(text)
+        } else {
+            Token::InterpMid(text)
+        };
+        tokens.push((token, widen(span)));
+    }
+    tokens
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,10 +305,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try() {
+        let input = "parse_int(x)?";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                ident!(parse_int),
+                punct('('),
+                ident!(x),
+                punct(')'),
+                punct('?'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let input = r#""Hi {name}, {count + 1} items \{}" "{x}""#;
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::InterpStart("Hi ".to_string()),
+                ident!(name),
+                Token::InterpMid(", ".to_string()),
+                ident!(count),
+                op!(+),
+                Token::Literal(Literal::Int(1)),
+                Token::InterpEnd(" items {}".to_string()),
+                Token::InterpStart(String::new()),
+                ident!(x),
+                Token::InterpEnd(String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interpolation_spans() {
+        let input = r#""a {b} c""#;
+        let tokens = lexer().parse(input).unwrap();
+        assert_eq!(tokens[0].1, Span::from(0..4));
+        assert_eq!(tokens[1].1, Span::from(4..5));
+        assert_eq!(tokens[2].1, Span::from(5..9));
+    }
+
+    #[test]
+    fn test_escaped_brace() {
+        let input = r#""escaped \{braces}""#;
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![Token::Literal(Literal::String(
+                "escaped {braces}".to_string()
+            ))]
+        );
+    }
+
     #[test]
     fn test_wildcard() {
         let input = "_";
         let tokens = remove_span(lexer().parse(input).unwrap());
         assert_eq!(tokens, vec![op!(_)]);
     }
+
+    #[test]
+    fn test_escapes() {
+        let input = r#""a\tb\\c\0\"\u{1F600}" '\'' '\n'"#;
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Literal(Literal::String("a\tb\\c\0\"\u{1F600}".to_string())),
+                Token::Literal(Literal::Char('\'')),
+                Token::Literal(Literal::Char('\n')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_escape() {
+        let input = r#""a\qb" "\u{110000}""#;
+        let (tokens, errors) = lexer().parse(input).into_output_errors();
+        assert_eq!(
+            remove_span(tokens.unwrap()),
+            vec![
+                Token::Literal(Literal::String("a\u{FFFD}b".to_string())),
+                Token::Literal(Literal::String("\u{FFFD}".to_string())),
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span(), &Span::from(2..4));
+    }
+
+    #[test]
+    fn test_int_radix() {
+        let input = "0xFF 0o17 0b1010 1_000_000";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Literal(Literal::Int(255)),
+                Token::Literal(Literal::Int(15)),
+                Token::Literal(Literal::Int(10)),
+                Token::Literal(Literal::Int(1_000_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_float_exponent() {
+        let input = "1.5e3 2E-2 1_0.2_5";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Literal(Literal::Float(1500.0)),
+                Token::Literal(Literal::Float(0.02)),
+                Token::Literal(Literal::Float(10.25)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_int_overflow() {
+        let input = "2147483648 0x1_0000_0000";
+        let (tokens, errors) = lexer().parse(input).into_output_errors();
+        assert_eq!(
+            remove_span(tokens.unwrap()),
+            vec![
+                Token::Literal(Literal::Int(0)),
+                Token::Literal(Literal::Int(0)),
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "Integer literal '2147483648' is too large for 'Int'"
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let input = "0..10 a..=b 1.5";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Literal(Literal::Int(0)),
+                op!(..),
+                Token::Literal(Literal::Int(10)),
+                ident!(a),
+                op!(..=),
+                ident!(b),
+                Token::Literal(Literal::Float(1.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compound_assign() {
+        let input = "a += 1 b &&= c";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                ident!(a),
+                op!(+=),
+                Token::Literal(Literal::Int(1)),
+                ident!(b),
+                Token::Op("&&=".to_string()),
+                ident!(c),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        let input = "'outer: loop 'a'";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Label("outer".to_string()),
+                punct(':'),
+                kw!(loop),
+                Token::Literal(Literal::Char('a')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let input = "a /* outer /* inner */ still comment */ + b";
+        let tokens = remove_span(lexer().parse(input).unwrap());
+        assert_eq!(tokens, vec![ident!(a), op!(+), ident!(b)]);
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let input = "a /* outer /* inner */";
+        assert!(lexer().parse(input).has_errors());
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        let input = "/// Adds one\n///to x\n// not a doc\nfn add";
+        let (tokens, docs) = split_docs(lexer().parse(input).unwrap());
+        assert_eq!(
+            remove_span(tokens),
+            vec![Token::Newline, kw!(fn), ident!(add)]
+        );
+        assert_eq!(
+            docs,
+            vec![
+                ("Adds one".to_string(), Span::from(0..12)),
+                ("to x".to_string(), Span::from(13..20)),
+            ]
+        );
+    }
 }
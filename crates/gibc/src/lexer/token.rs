@@ -12,6 +12,16 @@ pub enum Token {
     Op(String),
     Punct(char),
     Newline,
+    /// The text of an interpolated string up to its first `{`
+    InterpStart(String),
+    /// The text between a `}` and the next `{` of an interpolated string
+    InterpMid(String),
+    /// The text of an interpolated string after its last `}`
+    InterpEnd(String),
+    /// A `///` comment, kept as trivia to document the declaration below it
+    Doc(String),
+    /// A loop label such as `'outer`, without the quote
+    Label(String),
 }
 
 impl Display for Token {
@@ -23,6 +33,11 @@ impl Display for Token {
             Token::Op(o) => write!(f, "{o}"),
             Token::Punct(p) => write!(f, "{p}"),
             Token::Newline => write!(f, "newline"),
+            Token::InterpStart(s) => write!(f, "\"{s}{{"),
+            Token::InterpMid(s) => write!(f, "}}{s}{{"),
+            Token::InterpEnd(s) => write!(f, "}}{s}\""),
+            Token::Doc(s) => write!(f, "///{s}"),
+            Token::Label(s) => write!(f, "'{s}"),
         }
     }
 }
@@ -1,8 +1,8 @@
 use async_lsp::lsp_types::{
-    CompletionOptions, HoverProviderCapability, InitializeResult, OneOf, SemanticTokenType,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
-    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
-    TextDocumentSyncKind,
+    CodeActionProviderCapability, CompletionOptions, HoverProviderCapability, InitializeResult,
+    OneOf, SemanticTokenType, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, SemanticTokensServerCapabilities, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
 };
 
 pub fn capabilities() -> InitializeResult {
@@ -38,6 +38,7 @@ pub fn capabilities() -> InitializeResult {
                 }),
             ),
             document_formatting_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             completion_provider: Some(CompletionOptions {
                 trigger_characters: Some(vec![":".to_string(), ".".to_string()]),
                 ..Default::default()
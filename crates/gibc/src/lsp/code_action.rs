@@ -0,0 +1,97 @@
+use std::{collections::HashMap, future::Future};
+
+use async_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    TextEdit, WorkspaceEdit,
+};
+
+use crate::{
+    check::check_project,
+    db::{err::Diagnostic, input::Db as _},
+    range::{position_to_offset, span_to_range_str},
+};
+
+use super::ServerState;
+
+/// Offers the fixes of the diagnostics in the requested range as quick-fixes
+pub fn code_action(
+    st: &mut ServerState,
+    msg: CodeActionParams,
+) -> impl Future<Output = Result<Option<CodeActionResponse>, async_lsp::ResponseError>> {
+    let mut db = st.db.clone();
+    async move {
+        let path = msg.text_document.uri.to_file_path().unwrap();
+        let file = db.input(&path);
+        let text = file.text(&db);
+        let start = position_to_offset(msg.range.start, text);
+        let end = position_to_offset(msg.range.end, text);
+        let diags = check_project::accumulated::<Diagnostic>(&db, db.vfs.unwrap());
+        let actions = diags
+            .iter()
+            .filter(|diag| diag.path == path && diag.span.start <= end && start <= diag.span.end)
+            .filter_map(|diag| {
+                let fix = diag.fix.as_ref()?;
+                let edit = TextEdit {
+                    range: span_to_range_str(fix.span.into(), text),
+                    new_text: fix.text.clone(),
+                };
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(msg.text_document.uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect();
+        Ok(Some(actions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_lsp::{
+        lsp_types::{
+            CodeActionContext, CodeActionOrCommand, CodeActionParams, PartialResultParams,
+            Position, Range, TextDocumentIdentifier, TextEdit, Url, WorkDoneProgressParams,
+        },
+        ClientSocket,
+    };
+
+    use crate::{check::tests::source_db, lsp::ServerState};
+
+    use super::code_action;
+
+    #[tokio::test]
+    async fn test_make_mutable_quick_fix() {
+        let mut st = ServerState {
+            client: ClientSocket::new_closed(),
+            counter: 0,
+            db: source_db("use std::Int\n\nfn main() {\n    let count = 0\n    count = 1\n}\n"),
+        };
+        let uri = Url::from_file_path("/test/main.gib").unwrap();
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range: Range::new(Position::new(4, 4), Position::new(4, 9)),
+            context: CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let actions = code_action(&mut st, params).await.unwrap().unwrap();
+        let [CodeActionOrCommand::CodeAction(action)] = &actions[..] else {
+            panic!("Expected a single code action")
+        };
+        assert_eq!(action.title, "Make 'count' mutable");
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(
+            changes[&uri],
+            vec![TextEdit {
+                range: Range::new(Position::new(3, 8), Position::new(3, 8)),
+                new_text: "mut ".to_string(),
+            }]
+        );
+    }
+}
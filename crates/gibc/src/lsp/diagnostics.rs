@@ -5,7 +5,10 @@ use crate::{
     db::{err::Diagnostic, input::Db as _},
     range::span_to_range_str,
 };
-use async_lsp::lsp_types::{notification, DiagnosticSeverity, PublishDiagnosticsParams, Url};
+use async_lsp::lsp_types::{
+    notification, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
+    PublishDiagnosticsParams, Url,
+};
 
 use super::ServerState;
 
@@ -28,18 +31,31 @@ impl ServerState {
             let file = self.db.input(path);
             let text = file.text(&self.db);
             let mut found = vec![];
+            let uri = Url::parse(format!("file://{}", path.display()).as_str()).unwrap();
             for diag in diags {
                 let range = span_to_range_str(diag.span.into(), text);
+                let related = diag
+                    .related
+                    .iter()
+                    .map(|(message, span)| DiagnosticRelatedInformation {
+                        location: Location {
+                            uri: uri.clone(),
+                            range: span_to_range_str((*span).into(), text),
+                        },
+                        message: message.clone(),
+                    })
+                    .collect::<Vec<_>>();
                 found.push(async_lsp::lsp_types::Diagnostic {
                     range,
                     severity: Some(DiagnosticSeverity::ERROR),
                     message: diag.message.clone(),
+                    related_information: (!related.is_empty()).then_some(related),
                     ..Default::default()
                 });
             }
             self.client
                 .notify::<notification::PublishDiagnostics>(PublishDiagnosticsParams {
-                    uri: Url::parse(format!("file://{}", path.display()).as_str()).unwrap(),
+                    uri,
                     diagnostics: found,
                     version: None,
                 })
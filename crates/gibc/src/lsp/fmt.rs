@@ -1,5 +1,5 @@
 use crate::db::input::Db;
-use crate::item::pretty_format;
+use crate::item::{item_docs, pretty_format};
 use crate::parser::parse_file;
 use async_lsp::lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
 use async_lsp::{ErrorCode, ResponseError};
@@ -24,7 +24,11 @@ pub fn format(
             info!("parse errors");
             return Err(ResponseError::new(ErrorCode::PARSE_ERROR, "Parse errors"));
         }
-        let formatted = pretty_format::<_, ()>(ast.tops(&db), &BoxAllocator)
+        let Some(docs) = item_docs(text, ast.tops(&db), ast.docs(&db)) else {
+            info!("doc comments that aren't above anything");
+            return Ok(None);
+        };
+        let formatted = pretty_format::<_, ()>(ast.tops(&db), &docs, &BoxAllocator)
             .1
             .pretty(70)
             .to_string();
@@ -1,4 +1,5 @@
 mod capabilities;
+mod code_action;
 mod completions;
 mod definition;
 mod diagnostics;
@@ -72,6 +73,7 @@ pub async fn main_loop() {
             .request::<request::GotoDefinition, _>(definition::goto_definition)
             .request::<request::DocumentSymbolRequest, _>(document_symbols::get_document_symbols)
             .request::<request::Formatting, _>(fmt::format)
+            .request::<request::CodeActionRequest, _>(code_action::code_action)
             .notification::<notification::Initialized>(|_, _| ControlFlow::Continue(()))
             .notification::<notification::DidChangeConfiguration>(|_, _| ControlFlow::Continue(()))
             .notification::<notification::DidOpenTextDocument>(did_open)
@@ -15,7 +15,7 @@ pub fn get_semantic_tokens(mut tokens: Vec<SemanticToken>, text: &str) -> Option
         for (index, char) in text.chars().enumerate() {
             if current.span.start == index {
                 let ty = match current.kind {
-                    TokenKind::Var => Some(1),
+                    TokenKind::Var | TokenKind::Const => Some(1),
                     TokenKind::Func => Some(2),
                     TokenKind::Param => Some(7),
                     TokenKind::Property => Some(8),
@@ -24,7 +24,7 @@ pub fn get_semantic_tokens(mut tokens: Vec<SemanticToken>, text: &str) -> Option
                     TokenKind::Member => Some(11),
                     TokenKind::Trait => Some(12),
                     TokenKind::Module => Some(13),
-                    TokenKind::Generic => Some(6),
+                    TokenKind::Generic | TokenKind::TypeAlias => Some(6),
                 };
                 if let Some(ty) = ty {
                     found.push(LspSemanticToken {
@@ -0,0 +1,32 @@
+use chumsky::{error::Rich, extra, primitive::just, select, Parser};
+
+use crate::{
+    lexer::token::{punct, Token},
+    util::{ParserInput, Span, Spanned},
+};
+
+#[must_use]
+pub fn label_parser<'tokens, 'src: 'tokens>() -> impl Parser<
+    'tokens,
+    ParserInput<'tokens, 'src>,
+    Spanned<String>,
+    extra::Full<Rich<'tokens, Token, Span>, u32, ()>,
+> + Clone
+       + 'tokens {
+    select! {
+        Token::Label(label) => label,
+    }
+    .map_with(|label, e| (label, e.span()))
+}
+
+/// The optional `'label:` in front of a loop
+#[must_use]
+pub fn loop_label_parser<'tokens, 'src: 'tokens>() -> impl Parser<
+    'tokens,
+    ParserInput<'tokens, 'src>,
+    Option<Spanned<String>>,
+    extra::Full<Rich<'tokens, Token, Span>, u32, ()>,
+> + Clone
+       + 'tokens {
+    label_parser().then_ignore(just(punct(':'))).or_not()
+}
@@ -1,6 +1,7 @@
 pub mod generic_arg;
 pub mod generic_args;
 pub mod ident;
+pub mod label;
 pub mod optional_newline;
 pub mod pattern;
 pub mod type_;
@@ -21,6 +21,8 @@ pub enum Access {
         args: Vec<Spanned<Expr>>,
     },
     Field(Spanned<String>),
+    Index(Box<Spanned<Expr>>),
+    Try,
 }
 
 pub fn access_parser<'tokens, 'src: 'tokens>(
@@ -28,6 +30,7 @@ pub fn access_parser<'tokens, 'src: 'tokens>(
     lambda: AstParser!(Expr),
 ) -> AstParser!(Access) {
     let args = expr
+        .clone()
         .map_with(|ex, e| (ex, e.span()))
         .separated_by(just(punct(',')).padded_by(optional_newline()))
         .collect::<Vec<_>>()
@@ -54,11 +57,13 @@ pub fn access_parser<'tokens, 'src: 'tokens>(
         .recover_with(via_parser(just(punct('.')).map_with(|_, e| {
             Access::Field((String::new(), Span::to_end(&e.span())))
         })));
-    choice((member, field))
+    let try_ = just(punct('?')).to(Access::Try);
+    choice((member, field, index_parser(expr), try_))
 }
 
 pub fn basic_access_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(Access) {
     let args = expr
+        .clone()
         .map_with(|ex, e| (ex, e.span()))
         .separated_by(just(punct(',')).padded_by(optional_newline()))
         .collect::<Vec<_>>()
@@ -77,5 +82,11 @@ pub fn basic_access_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> As
         .recover_with(via_parser(just(punct('.')).map_with(|_, e| {
             Access::Field((String::new(), Span::to_end(&e.span())))
         })));
-    choice((member, field))
+    let try_ = just(punct('?')).to(Access::Try);
+    choice((member, field, index_parser(expr), try_))
+}
+
+fn index_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(Access) {
+    expr.map_with(|ex, e| Access::Index(Box::new((ex, e.span()))))
+        .delimited_by(just(punct('[')), just(punct(']')))
 }
@@ -3,7 +3,10 @@ use chumsky::{primitive::just, Parser};
 use crate::{
     kw,
     parser::{
-        common::pattern::{pattern_parser, Pattern},
+        common::{
+            label::loop_label_parser,
+            pattern::{pattern_parser, Pattern},
+        },
         stmt::Stmt,
     },
     util::Spanned,
@@ -17,6 +20,7 @@ use super::{
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct For {
+    pub label: Option<Spanned<String>>,
     pub pattern: Spanned<Pattern>,
     pub expr: Box<Spanned<Expr>>,
     pub block: Spanned<CodeBlock>,
@@ -26,13 +30,15 @@ pub fn for_parser<'tokens, 'src: 'tokens>(
     expr: AstParser!(Expr),
     stmt: AstParser!(Stmt),
 ) -> AstParser!(Expr) {
-    just(kw!(for))
-        .ignore_then(pattern_parser().map_with(|p, e| (p, e.span())))
+    loop_label_parser()
+        .then_ignore(just(kw!(for)))
+        .then(pattern_parser().map_with(|p, e| (p, e.span())))
         .then_ignore(just(kw!(in)))
         .then(expr.map_with(|ex, e| (ex, e.span())).map(Box::new))
         .then(code_block_parser(stmt).map_with(|ex, e| (ex, e.span())))
-        .map(|((pattern, expr), block)| {
+        .map(|(((label, pattern), expr), block)| {
             Expr::For(For {
+                label,
                 pattern,
                 expr,
                 block,
@@ -0,0 +1,8 @@
+use super::Expr;
+use crate::util::Spanned;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Index {
+    pub expr: Box<Spanned<Expr>>,
+    pub index: Box<Spanned<Expr>>,
+}
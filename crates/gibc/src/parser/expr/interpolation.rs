@@ -0,0 +1,37 @@
+use chumsky::{select, IterParser as _, Parser};
+
+use crate::{lexer::token::Token, util::Spanned, AstParser};
+
+use super::Expr;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Interpolation {
+    pub parts: Vec<InterpolationPart>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum InterpolationPart {
+    Text(String),
+    Expr(Spanned<Expr>),
+}
+
+pub fn interpolation_parser<'tokens, 'src: 'tokens>(
+    expr: AstParser!(Expr),
+) -> AstParser!(Interpolation) {
+    let start = select! { Token::InterpStart(text) => text };
+    let mid = select! { Token::InterpMid(text) => text };
+    let end = select! { Token::InterpEnd(text) => text };
+    let expr = expr.map_with(|ex, e| (ex, e.span()));
+    start
+        .then(expr.clone().then(mid).repeated().collect::<Vec<_>>())
+        .then(expr.then(end))
+        .map(|((start, middle), last)| {
+            let mut parts = vec![InterpolationPart::Text(start)];
+            for (expr, text) in middle.into_iter().chain([last]) {
+                parts.push(InterpolationPart::Expr(expr));
+                parts.push(InterpolationPart::Text(text));
+            }
+            parts.retain(|part| !matches!(part, InterpolationPart::Text(text) if text.is_empty()));
+            Interpolation { parts }
+        })
+}
@@ -0,0 +1,26 @@
+use chumsky::{primitive::just, Parser};
+
+use crate::{
+    kw,
+    parser::{common::label::loop_label_parser, stmt::Stmt},
+    util::Spanned,
+    AstParser,
+};
+
+use super::{
+    code_block::{code_block_parser, CodeBlock},
+    Expr,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Loop {
+    pub label: Option<Spanned<String>>,
+    pub block: Spanned<CodeBlock>,
+}
+
+pub fn loop_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!(Expr) {
+    loop_label_parser()
+        .then_ignore(just(kw!(loop)))
+        .then(code_block_parser(stmt).map_with(|ex, e| (ex, e.span())))
+        .map(|(label, block)| Expr::Loop(Loop { label, block }))
+}
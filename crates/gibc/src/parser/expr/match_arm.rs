@@ -1,7 +1,7 @@
-use chumsky::{primitive::just, Parser};
+use chumsky::{primitive::just, IterParser, Parser};
 
 use crate::{
-    op,
+    kw, op,
     parser::{
         common::pattern::{pattern_parser, Pattern},
         expr::Expr,
@@ -15,7 +15,9 @@ use super::code_block::code_block_parser;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct MatchArm {
-    pub pattern: Spanned<Pattern>,
+    /// The alternatives separated by `|`, any of which selects the arm
+    pub patterns: Vec<Spanned<Pattern>>,
+    pub guard: Option<Spanned<Expr>>,
     pub expr: Spanned<Expr>,
 }
 
@@ -23,16 +25,24 @@ pub fn match_arm_parser<'tokens, 'src: 'tokens>(
     expr: AstParser!(Expr),
     stmt: AstParser!(Stmt),
 ) -> AstParser!(MatchArm) {
+    let guard = just(kw!(if))
+        .ignore_then(expr.clone().map_with(|e, s| (e, s.span())))
+        .or_not();
     let expr = code_block_parser(stmt.clone())
         .map(Expr::CodeBlock)
         .or(expr)
         .map_with(|e, s| (e, s.span()));
     pattern_parser()
         .map_with(|p, s| (p, s.span()))
+        .separated_by(just(op!(|)))
+        .at_least(1)
+        .collect()
+        .then(guard)
         .then_ignore(just(op!(=>)))
         .then(expr)
-        .map(|(p, e)| MatchArm {
-            pattern: p,
-            expr: e,
+        .map(|((patterns, guard), expr)| MatchArm {
+            patterns,
+            guard,
+            expr,
         })
 }
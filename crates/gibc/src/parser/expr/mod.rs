@@ -9,8 +9,14 @@ use field::Field;
 
 use for_::{for_parser, For};
 use gvm::format::literal::Literal;
+use index::Index;
+use interpolation::{interpolation_parser, Interpolation};
 use lambda::{lambda_parser, Lambda};
+use loop_::{loop_parser, Loop};
 use op::{op_parser, Op};
+use range::Range;
+use try_::Try;
+use vec_lit::{vec_lit_parser, VecLit};
 use while_::{while_parser, While};
 
 use crate::{
@@ -36,12 +42,18 @@ pub mod code_block;
 pub mod field;
 pub mod for_;
 pub mod if_else;
+pub mod index;
+pub mod interpolation;
 pub mod lambda;
+pub mod loop_;
 pub mod match_;
 pub mod match_arm;
 pub mod member;
 pub mod op;
 pub mod qualified_name;
+pub mod range;
+pub mod try_;
+pub mod vec_lit;
 pub mod while_;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -58,7 +70,13 @@ pub enum Expr {
     Op(Op),
     Lambda(Lambda),
     While(While),
+    Loop(Loop),
     For(For),
+    Try(Try),
+    Interpolation(Interpolation),
+    VecLit(VecLit),
+    Index(Index),
+    Range(Range),
     Error,
 }
 
@@ -87,9 +105,11 @@ pub fn expr_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!
         let atom = select! {
             Token::Literal(lit) => Expr::Literal(lit),
         }
+        .or(interpolation_parser(expr.clone()).map(Expr::Interpolation))
         .or(qualified_name_parser().map(Expr::Ident))
         .or(bracketed)
-        .or(tuple);
+        .or(tuple)
+        .or(vec_lit_parser(expr.clone()).map(Expr::VecLit));
 
         let call = call_parser(atom.clone(), expr.clone(), lambda.clone()).map(Expr::Call);
         let basic_call = basic_call_parser(atom.clone(), expr.clone()).map(Expr::Call);
@@ -117,6 +137,14 @@ pub fn expr_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!
                         }),
                         e.span(),
                     ),
+                    Access::Index(index) => (
+                        Expr::Index(Index {
+                            expr: Box::new(ex),
+                            index,
+                        }),
+                        e.span(),
+                    ),
+                    Access::Try => (Expr::Try(Try { expr: Box::new(ex) }), e.span()),
                 },
             )
             .map(|(ex, _)| ex)
@@ -143,6 +171,14 @@ pub fn expr_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!
                         }),
                         e.span(),
                     ),
+                    Access::Index(index) => (
+                        Expr::Index(Index {
+                            expr: Box::new(ex),
+                            index,
+                        }),
+                        e.span(),
+                    ),
+                    Access::Try => (Expr::Try(Try { expr: Box::new(ex) }), e.span()),
                 },
             )
             .map(|(ex, _)| ex)
@@ -159,10 +195,11 @@ pub fn expr_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!
 
         let while_ = while_parser(basic_op.clone(), stmt.clone());
         let for_ = for_parser(basic_op.clone(), stmt.clone());
+        let loop_ = loop_parser(stmt.clone());
 
         let if_else = if_else_parser(basic_op, stmt).map(Expr::IfElse);
 
-        choice((if_else, match_, for_, while_, lambda, op))
+        choice((if_else, match_, for_, while_, loop_, lambda, op))
             .boxed()
             .labelled("Expression")
     })
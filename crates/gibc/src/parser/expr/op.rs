@@ -4,7 +4,7 @@ use chumsky::{select, Parser as _};
 
 use crate::{lexer::token::Token, util::Spanned, AstParser};
 
-use super::Expr;
+use super::{range::Range, Expr};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Op {
@@ -204,5 +204,23 @@ pub fn op_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(E
         )
         .map(|(a, _)| a)
         .boxed();
-    or
+
+    let range_op = select! {
+        Token::Op(op) if op == ".." => false,
+        Token::Op(op) if op == "..=" => true,
+    };
+
+    // Ranges bind the loosest, so `0..n + 1` ends at `n + 1`
+    or.clone()
+        .map_with(|a, e| (a, e.span()))
+        .then(range_op.then(or.map_with(|a, e| (a, e.span()))).or_not())
+        .map(|(start, end)| match end {
+            Some((inclusive, end)) => Expr::Range(Range {
+                start: Box::new(start),
+                end: Box::new(end),
+                inclusive,
+            }),
+            None => start.0,
+        })
+        .boxed()
 }
@@ -0,0 +1,10 @@
+use super::Expr;
+use crate::util::Spanned;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Range {
+    pub start: Box<Spanned<Expr>>,
+    pub end: Box<Spanned<Expr>>,
+    /// Whether the end is part of the range, as in `a..=b`
+    pub inclusive: bool,
+}
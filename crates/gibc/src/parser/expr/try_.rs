@@ -0,0 +1,7 @@
+use super::Expr;
+use crate::util::Spanned;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Try {
+    pub expr: Box<Spanned<Expr>>,
+}
@@ -0,0 +1,32 @@
+use chumsky::{primitive::just, IterParser, Parser};
+
+use crate::{
+    lexer::token::punct,
+    parser::common::optional_newline::optional_newline,
+    util::{Span, Spanned},
+    AstParser,
+};
+
+use super::Expr;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct VecLit {
+    pub items: Vec<Spanned<Expr>>,
+    /// Where the item type is inferred, which matters for an empty literal
+    pub span: Span,
+}
+
+pub fn vec_lit_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(VecLit) {
+    expr.map_with(|ex, e| (ex, e.span()))
+        .separated_by(just(punct(',')).padded_by(optional_newline()))
+        .allow_trailing()
+        .collect()
+        .delimited_by(
+            just(punct('[')).then(optional_newline()),
+            optional_newline().then(just(punct(']'))),
+        )
+        .map_with(|items, e| VecLit {
+            items,
+            span: e.span(),
+        })
+}
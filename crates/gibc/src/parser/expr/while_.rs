@@ -2,7 +2,10 @@ use chumsky::{primitive::just, Parser};
 
 use crate::{
     kw,
-    parser::stmt::{let_::let_parser, Stmt},
+    parser::{
+        common::label::loop_label_parser,
+        stmt::{let_::let_parser, Stmt},
+    },
     util::Spanned,
     AstParser,
 };
@@ -15,6 +18,7 @@ use super::{
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct While {
+    pub label: Option<Spanned<String>>,
     pub condition: Box<Spanned<Condition>>,
     pub block: Spanned<CodeBlock>,
 }
@@ -28,8 +32,15 @@ pub fn while_parser<'tokens, 'src: 'tokens>(
         .or(expr.map(Condition::Expr))
         .map_with(|c, e| (c, e.span()))
         .map(Box::new);
-    just(kw!(while))
-        .ignore_then(condition)
+    loop_label_parser()
+        .then_ignore(just(kw!(while)))
+        .then(condition)
         .then(code_block_parser(stmt).map_with(|ex, e| (ex, e.span())))
-        .map(|(condition, block)| Expr::While(While { condition, block }))
+        .map(|((label, condition), block)| {
+            Expr::While(While {
+                label,
+                condition,
+                block,
+            })
+        })
 }
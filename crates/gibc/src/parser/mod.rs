@@ -21,7 +21,10 @@ use salsa::Accumulator;
 use tracing::info;
 
 use crate::{
-    lexer::{parser::lexer, token::newline},
+    lexer::{
+        parser::{lexer, split_docs},
+        token::newline,
+    },
     util::{Span, Spanned},
     AstParser,
 };
@@ -45,6 +48,9 @@ pub struct Ast<'db> {
     pub valid: bool,
     #[return_ref]
     pub expected: Vec<Spanned<Vec<Keyword>>>,
+    /// The `///` comments of the file, attached to declarations when resolving
+    #[return_ref]
+    pub docs: Vec<Spanned<String>>,
 }
 
 #[must_use]
@@ -164,6 +170,7 @@ pub fn parse_file<'db>(db: &'db dyn Db, file: SourceFile) -> Ast<'db> {
     let (tokens, errors) = lexer().parse(text).into_output_errors();
     let len = text.len();
     let mut found = vec![];
+    let mut docs = vec![];
     for error in errors {
         valid = false;
         info!("Lexer error: {:?}", error);
@@ -173,10 +180,14 @@ pub fn parse_file<'db>(db: &'db dyn Db, file: SourceFile) -> Ast<'db> {
             level: Level::Error,
             path: file.path(db),
             file,
+            related: vec![],
+            fix: None,
         }
         .accumulate(db);
     }
     if let Some(tokens) = tokens {
+        let (tokens, file_docs) = split_docs(tokens);
+        docs = file_docs;
         let eoi = Span::splat(len);
         let input = tokens.spanned(eoi);
         let (ast, errors) = file_parser().parse(input).into_output_errors();
@@ -198,6 +209,8 @@ pub fn parse_file<'db>(db: &'db dyn Db, file: SourceFile) -> Ast<'db> {
                 level: Level::Error,
                 path: file.path(db),
                 file,
+                related: vec![],
+                fix: None,
             }
             .accumulate(db);
             info!("Expected all: {:?}", e);
@@ -207,7 +220,7 @@ pub fn parse_file<'db>(db: &'db dyn Db, file: SourceFile) -> Ast<'db> {
             found = ast;
         }
     }
-    Ast::new(db, found, valid, expected)
+    Ast::new(db, found, valid, expected, docs)
 }
 
 #[macro_export]
@@ -1,34 +1,60 @@
-use chumsky::{primitive::just, recovery::via_parser, span::Span as _, Parser as _};
+use chumsky::{error::Rich, extra, recovery::via_parser, select, span::Span as _, Parser};
 
 use crate::{
-    op,
-    parser::{common::optional_newline::optional_newline, expr::Expr},
-    util::{Span, Spanned},
+    lexer::token::Token,
+    parser::{
+        common::optional_newline::optional_newline,
+        expr::{op::OpKind, Expr},
+    },
+    util::{ParserInput, Span, Spanned},
     AstParser,
 };
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Assign {
     pub refr: Spanned<Expr>,
+    /// The operator of a compound assignment like `x += 1`
+    pub op: Option<OpKind>,
     pub value: Spanned<Expr>,
 }
 
+/// `=`, or the operator of a compound assignment followed by `=`
+fn assign_op_parser<'tokens, 'src: 'tokens>() -> impl Parser<
+    'tokens,
+    ParserInput<'tokens, 'src>,
+    Option<OpKind>,
+    extra::Full<Rich<'tokens, Token, Span>, u32, ()>,
+> + Clone
+       + 'tokens {
+    select! {
+        Token::Op(op) if op == "=" => None,
+        Token::Op(op) if op == "+=" => Some(OpKind::Add),
+        Token::Op(op) if op == "-=" => Some(OpKind::Sub),
+        Token::Op(op) if op == "*=" => Some(OpKind::Mul),
+        Token::Op(op) if op == "/=" => Some(OpKind::Div),
+        Token::Op(op) if op == "%=" => Some(OpKind::Mod),
+        Token::Op(op) if op == "&&=" => Some(OpKind::And),
+        Token::Op(op) if op == "||=" => Some(OpKind::Or),
+    }
+}
+
 pub fn assign_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(Assign) {
     let missing_expr = expr
         .clone()
         .map_with(|p, e| (p, e.span()))
-        .then_ignore(just(op!(=)))
-        .map_with(|refr, e| Assign {
+        .then(assign_op_parser())
+        .map_with(|(refr, op), e| Assign {
             refr,
+            op,
             value: (Expr::Error, Span::to_end(&e.span())),
         });
 
     let valid = expr
         .clone()
         .map_with(|p, e| (p, e.span()))
-        .then_ignore(just(op!(=)).padded_by(optional_newline()))
+        .then(assign_op_parser().padded_by(optional_newline()))
         .then(expr.map_with(|e, s| (e, s.span())))
-        .map(|(refr, value)| Assign { refr, value });
+        .map(|((refr, op), value)| Assign { refr, op, value });
 
     valid.recover_with(via_parser(missing_expr))
 }
@@ -0,0 +1,33 @@
+use chumsky::{primitive::just, Parser as _};
+
+use crate::{
+    kw,
+    parser::{common::label::label_parser, expr::Expr},
+    util::Spanned,
+    AstParser,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Break {
+    pub label: Option<Spanned<String>>,
+    /// The value a `loop` evaluates to
+    pub value: Option<Spanned<Expr>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Continue {
+    pub label: Option<Spanned<String>>,
+}
+
+pub fn break_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(Break) {
+    just(kw!(break))
+        .ignore_then(label_parser().or_not())
+        .then(expr.map_with(|e, s| (e, s.span())).or_not())
+        .map(|(label, value)| Break { label, value })
+}
+
+pub fn continue_parser<'tokens, 'src: 'tokens>() -> AstParser!(Continue) {
+    just(kw!(continue))
+        .ignore_then(label_parser().or_not())
+        .map(|label| Continue { label })
+}
@@ -18,6 +18,8 @@ use crate::{
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct LetStatement {
+    /// Whether it's a `let mut`, so the bindings can be assigned to
+    pub mutable: bool,
     pub pattern: Spanned<Pattern>,
     pub ty: Option<Spanned<Type>>,
     pub value: Spanned<Expr>,
@@ -29,22 +31,32 @@ pub fn let_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(
         .ignore_then(type_parser().map_with(|t, e| (t, e.span())))
         .or_not();
 
+    let mutable = just(kw!(mut)).or_not().map(|mutable| mutable.is_some());
+
     let missing_expr = just(kw!(let))
-        .ignore_then(pattern_parser().map_with(|p, e| (p, e.span())))
+        .ignore_then(mutable.clone())
+        .then(pattern_parser().map_with(|p, e| (p, e.span())))
         .then(ty.clone())
         .then_ignore(optional_newline().then(just(op!(=))).or_not())
-        .map_with(|(pattern, ty), e| LetStatement {
+        .map_with(|((mutable, pattern), ty), e| LetStatement {
+            mutable,
             pattern,
             ty,
             value: (Expr::Error, Span::to_end(&e.span())),
         });
 
     let valid = just(kw!(let))
-        .ignore_then(pattern_parser().map_with(|p, e| (p, e.span())))
+        .ignore_then(mutable)
+        .then(pattern_parser().map_with(|p, e| (p, e.span())))
         .then(ty)
         .then_ignore(just(op!(=)).padded_by(optional_newline()))
         .then(expr.map_with(|e, s| (e, s.span())))
-        .map(|((pattern, ty), value)| LetStatement { pattern, ty, value });
+        .map(|(((mutable, pattern), ty), value)| LetStatement {
+            mutable,
+            pattern,
+            ty,
+            value,
+        });
 
     valid.recover_with(via_parser(missing_expr))
 }
@@ -1,46 +1,52 @@
 use assign::{assign_parser, Assign};
-use chumsky::{
-    primitive::{choice, just},
-    recursive::recursive,
-    Parser,
-};
+use chumsky::{primitive::choice, recursive::recursive, Parser};
 
-use crate::{
-    kw,
-    util::{Span, Spanned},
-    AstParser,
-};
+use crate::{util::Spanned, AstParser};
 
-use self::let_::{let_parser, LetStatement};
+use self::{
+    break_::{break_parser, continue_parser, Break, Continue},
+    let_::{let_parser, LetStatement},
+    yield_::{yield_parser, Yield},
+};
 
 use super::expr::{expr_parser, Expr};
 
 pub mod assign;
+pub mod break_;
 pub mod let_;
+pub mod yield_;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum Stmt {
     Let(Spanned<LetStatement>),
     Assign(Spanned<Assign>),
     Expr(Spanned<Expr>),
-    Break(Span),
-    Continue(Span),
+    Break(Spanned<Break>),
+    Continue(Spanned<Continue>),
+    Yield(Spanned<Yield>),
 }
 
 #[must_use]
 pub fn stmt_parser<'tokens, 'src: 'tokens>() -> AstParser!(Stmt) {
     recursive(|stmt| {
-        let break_ = just(kw!(break)).map_with(|_, e| Stmt::Break(e.span()));
-        let continue_ = just(kw!(continue)).map_with(|_, e| Stmt::Continue(e.span()));
+        let break_ = break_parser(expr_parser(stmt.clone()))
+            .map_with(|s, e| (s, e.span()))
+            .map(Stmt::Break);
+        let continue_ = continue_parser()
+            .map_with(|s, e| (s, e.span()))
+            .map(Stmt::Continue);
         let let_ = let_parser(expr_parser(stmt.clone()))
             .map_with(|s, e| (s, e.span()))
             .map(Stmt::Let);
+        let yield_ = yield_parser(expr_parser(stmt.clone()))
+            .map_with(|s, e| (s, e.span()))
+            .map(Stmt::Yield);
         let assign = assign_parser(expr_parser(stmt.clone()))
             .map_with(|s, e| (s, e.span()))
             .map(Stmt::Assign);
         let expr = expr_parser(stmt)
             .map_with(|s, e| (s, e.span()))
             .map(Stmt::Expr);
-        choice((break_, continue_, let_, assign, expr))
+        choice((break_, continue_, yield_, let_, assign, expr))
     })
 }
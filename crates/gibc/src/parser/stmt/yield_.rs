@@ -0,0 +1,14 @@
+use chumsky::{primitive::just, Parser as _};
+
+use crate::{kw, parser::expr::Expr, util::Spanned, AstParser};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Yield {
+    pub value: Spanned<Expr>,
+}
+
+pub fn yield_parser<'tokens, 'src: 'tokens>(expr: AstParser!(Expr)) -> AstParser!(Yield) {
+    just(kw!(yield))
+        .ignore_then(expr.map_with(|e, s| (e, s.span())))
+        .map(|value| Yield { value })
+}
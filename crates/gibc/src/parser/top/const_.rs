@@ -0,0 +1,38 @@
+use chumsky::{primitive::just, Parser};
+
+use crate::{
+    kw,
+    lexer::token::punct,
+    op,
+    parser::{
+        common::{
+            ident::spanned_ident_parser,
+            optional_newline::optional_newline,
+            type_::{type_parser, Type},
+        },
+        expr::{expr_parser, Expr},
+        stmt::Stmt,
+    },
+    util::Spanned,
+    AstParser,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Const {
+    pub name: Spanned<String>,
+    pub ty: Spanned<Type>,
+    pub value: Spanned<Expr>,
+}
+
+#[must_use]
+pub fn const_parser<'tokens, 'src: 'tokens>(stmt: AstParser!(Stmt)) -> AstParser!(Const) {
+    let ty = just(punct(':'))
+        .padded_by(optional_newline())
+        .ignore_then(type_parser().map_with(|t, e| (t, e.span())));
+    just(kw!(const))
+        .ignore_then(spanned_ident_parser())
+        .then(ty)
+        .then_ignore(just(op!(=)).padded_by(optional_newline()))
+        .then(expr_parser(stmt).map_with(|e, s| (e, s.span())))
+        .map(|((name, ty), value)| Const { name, ty, value })
+}
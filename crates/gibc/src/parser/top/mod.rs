@@ -3,11 +3,14 @@ use salsa::Update;
 
 use crate::{parser::stmt::stmt_parser, util::Span, AstParser};
 
-use self::{enum_::Enum, func::Func, struct_::Struct, trait_::Trait};
+use self::{
+    const_::Const, enum_::Enum, func::Func, struct_::Struct, trait_::Trait, type_alias::TypeAlias,
+};
 
 use super::expr::qualified_name::SpannedQualifiedName;
 
 pub mod arg;
+pub mod const_;
 pub mod enum_;
 pub mod enum_member;
 pub mod func;
@@ -16,6 +19,7 @@ pub mod struct_;
 pub mod struct_body;
 pub mod struct_field;
 pub mod trait_;
+pub mod type_alias;
 pub mod use_;
 
 #[derive(Debug, PartialEq, Clone, Update)]
@@ -26,6 +30,8 @@ pub enum Top {
     Trait(trait_::Trait),
     Impl(impl_::Impl),
     Use(SpannedQualifiedName),
+    TypeAlias(TypeAlias),
+    Const(Const),
 }
 
 pub fn top_parser<'tokens, 'src: 'tokens>() -> AstParser!(Top) {
@@ -36,6 +42,8 @@ pub fn top_parser<'tokens, 'src: 'tokens>() -> AstParser!(Top) {
         trait_::trait_parser(stmt_parser()).map(Top::Trait),
         impl_::impl_parser(stmt_parser()).map(Top::Impl),
         use_::use_parser().map(Top::Use),
+        type_alias::type_alias_parser().map(Top::TypeAlias),
+        const_::const_parser(stmt_parser()).map(Top::Const),
     ))
 }
 
@@ -46,7 +54,9 @@ impl Top {
             Top::Func(Func { name, .. })
             | Top::Trait(Trait { name, .. })
             | Top::Struct(Struct { name, .. })
-            | Top::Enum(Enum { name, .. }) => Some(&name.0),
+            | Top::Enum(Enum { name, .. })
+            | Top::TypeAlias(TypeAlias { name, .. })
+            | Top::Const(Const { name, .. }) => Some(&name.0),
             Top::Use(_) | Top::Impl(_) => None,
         }
     }
@@ -57,7 +67,9 @@ impl Top {
             Top::Func(Func { name, .. })
             | Top::Trait(Trait { name, .. })
             | Top::Struct(Struct { name, .. })
-            | Top::Enum(Enum { name, .. }) => name.1,
+            | Top::Enum(Enum { name, .. })
+            | Top::TypeAlias(TypeAlias { name, .. })
+            | Top::Const(Const { name, .. }) => name.1,
             Top::Impl(_) => unimplemented!("Impl statement doesn't have a name"),
             Top::Use(_) => unimplemented!("Use statement doesn't have a name"),
         }
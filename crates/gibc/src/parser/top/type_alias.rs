@@ -0,0 +1,32 @@
+use chumsky::{primitive::just, Parser};
+
+use crate::{
+    kw, op,
+    parser::common::{
+        generic_args::{generic_args_parser, GenericArgs},
+        ident::spanned_ident_parser,
+        optional_newline::optional_newline,
+        type_::{type_parser, Type},
+    },
+    util::Spanned,
+    AstParser,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAlias {
+    pub name: Spanned<String>,
+    pub generics: Spanned<GenericArgs>,
+    pub ty: Spanned<Type>,
+}
+
+#[must_use]
+pub fn type_alias_parser<'tokens, 'src: 'tokens>() -> AstParser!(TypeAlias) {
+    let name = spanned_ident_parser();
+    let generics = generic_args_parser().map_with(|t, s| (t, s.span()));
+    just(kw!(type))
+        .ignore_then(name)
+        .then(generics)
+        .then_ignore(just(op!(=)).padded_by(optional_newline()))
+        .then(type_parser().map_with(|t, s| (t, s.span())))
+        .map(|((name, generics), ty)| TypeAlias { name, generics, ty })
+}
@@ -17,10 +17,15 @@ impl NamedType {
             }
         };
         let decl = state.get_decl(&self.name);
-        Ty::Named(Named {
-            name: decl,
-            args: self.args.iter().map(|ty| ty.0.resolve(state)).collect(),
-        })
+        let args = self
+            .args
+            .iter()
+            .map(|ty| ty.0.resolve(state))
+            .collect::<Vec<_>>();
+        if let Some(ty) = state.expand_alias(decl, &args) {
+            return ty;
+        }
+        Ty::Named(Named { name: decl, args })
     }
 }
 
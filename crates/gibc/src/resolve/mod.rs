@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{
     db::{
         decl::{impl_::ImplForDecl, Decl, DeclKind},
@@ -21,7 +23,7 @@ pub fn resolve_file<'db>(db: &'db dyn Db, file: SourceFile) -> Decl<'db> {
     let decls = ast
         .tops(db)
         .iter()
-        .filter_map(|item| item.0.resolve(&mut state))
+        .filter_map(|item| item.0.resolve(&mut state, item.1))
         .collect();
     Decl::new(
         db,
@@ -30,9 +32,48 @@ pub fn resolve_file<'db>(db: &'db dyn Db, file: SourceFile) -> Decl<'db> {
         DeclKind::Module(decls),
         Some(file),
         ModulePath::new(db, state.path.clone()),
+        None,
+        HashMap::new(),
     )
 }
 
+/// Resolves the type alias at `path`, if that's what it names, and whether it expands back to
+/// one of the aliases in `expanding`. Errors are reported when the alias's own file is resolved
+#[salsa::tracked]
+pub fn resolve_alias<'db>(
+    db: &'db dyn Db,
+    path: ModulePath<'db>,
+    expanding: Vec<ModulePath<'db>>,
+) -> Option<(Decl<'db>, bool)> {
+    let (file, (top, span)) = state::find_top(db, path)?;
+    if !matches!(top, Top::TypeAlias(_)) {
+        return None;
+    }
+    let mut state = ResolveState::nested(db, file);
+    state.aliases = expanding;
+    let decl = top.resolve(&mut state, *span)?;
+    Some((decl, state.found_cycle))
+}
+
+/// Resolves the constant at `path`, if that's what it names, folding its value once however many
+/// constants use it, and whether it depends on one of the constants in `evaluating`. Errors are
+/// reported when the constant's own file is resolved
+#[salsa::tracked]
+pub fn resolve_const<'db>(
+    db: &'db dyn Db,
+    path: ModulePath<'db>,
+    evaluating: Vec<ModulePath<'db>>,
+) -> Option<(Decl<'db>, bool)> {
+    let (file, (top, span)) = state::find_top(db, path)?;
+    if !matches!(top, Top::Const(_)) {
+        return None;
+    }
+    let mut state = ResolveState::nested(db, file);
+    state.consts = evaluating;
+    let decl = top.resolve(&mut state, *span)?;
+    Some((decl, state.found_cycle))
+}
+
 #[salsa::tracked]
 pub fn resolve_impls<'db>(db: &'db dyn Db, file: SourceFile) -> Vec<ImplForDecl<'db>> {
     let mut state = ResolveState::from_file(db, file);
@@ -75,6 +116,8 @@ pub fn resolve_vfs<'db>(db: &'db dyn Db, vfs: Vfs, path: ModulePath<'db>) -> Dec
                 DeclKind::Module(modules),
                 None,
                 ModulePath::new(db, path),
+                None,
+                HashMap::new(),
             )
         }
     }
@@ -1,24 +1,38 @@
 use std::collections::HashMap;
 
+use gvm::format::literal::Literal;
 use salsa::Accumulator;
 
 use crate::{
     check::err::{CheckError, Error},
     db::{
+        decl::DeclKind,
         input::{Db, SourceFile},
         path::ModulePath,
     },
-    parser::{common::variance::Variance, expr::qualified_name::SpannedQualifiedName, parse_file},
+    parser::{
+        common::variance::Variance, expr::qualified_name::SpannedQualifiedName, parse_file,
+        top::Top,
+    },
     ty::{Generic, Ty},
     util::{Span, Spanned},
 };
 
+use super::{resolve_alias, resolve_const};
+
 pub struct ResolveState<'db> {
     pub db: &'db dyn Db,
     imports: HashMap<String, ModulePath<'db>>,
     generics: Vec<HashMap<String, Generic<'db>>>,
     pub file_data: SourceFile,
     pub path: Vec<String>,
+    pub should_error: bool,
+    /// The type aliases being expanded, outermost first
+    pub aliases: Vec<ModulePath<'db>>,
+    /// The constants being evaluated, outermost first
+    pub consts: Vec<ModulePath<'db>>,
+    /// Whether an alias or constant was found to depend on itself
+    pub found_cycle: bool,
 }
 
 impl<'db> ResolveState<'db> {
@@ -39,6 +53,10 @@ impl<'db> ResolveState<'db> {
             generics: vec![],
             file_data,
             path: file_data.module_path(db).name(db).clone(),
+            should_error: true,
+            aliases: vec![],
+            consts: vec![],
+            found_cycle: false,
         };
         let mut path = file_data.module_path(db).name(db).clone();
         for top in parse_file(db, file_data).tops(db) {
@@ -51,6 +69,26 @@ impl<'db> ResolveState<'db> {
         state
     }
 
+    /// The `///` comments directly above the item at `span`, one line each
+    pub fn doc(&self, span: Span) -> Option<String> {
+        let text = self.file_data.text(self.db);
+        let docs = parse_file(self.db, self.file_data).docs(self.db);
+        let mut lines = vec![];
+        let mut start = span.start;
+        for (doc, doc_span) in docs.iter().rev().skip_while(|doc| doc.1.end > span.start) {
+            if !text[doc_span.end..start].trim().is_empty() {
+                break;
+            }
+            lines.push(doc.as_str());
+            start = doc_span.start;
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+
     pub fn add_import(&mut self, name: String, path: ModulePath<'db>) {
         self.imports.insert(name, path);
     }
@@ -64,7 +102,9 @@ impl<'db> ResolveState<'db> {
     }
 
     pub fn error(&mut self, error: CheckError) {
-        Error { inner: error }.accumulate(self.db);
+        if self.should_error {
+            Error { inner: error }.accumulate(self.db);
+        }
     }
 
     pub fn get_decl(&self, path: &[Spanned<String>]) -> ModulePath<'db> {
@@ -102,6 +142,123 @@ impl<'db> ResolveState<'db> {
     pub fn module_path(&self) -> ModulePath<'db> {
         ModulePath::new(self.db, self.path.clone())
     }
+
+    /// The file and top-level item that `path` refers to, if it's one
+    pub fn find_top(&self, path: ModulePath<'db>) -> Option<(SourceFile, &'db Top)> {
+        let (file, top) = find_top(self.db, path)?;
+        Some((file, &top.0))
+    }
+
+    /// A state for resolving an item in `file` out of order, with all of the file's imports.
+    /// Errors aren't reported, as they are when the item itself is resolved
+    pub fn nested(db: &'db dyn Db, file: SourceFile) -> ResolveState<'db> {
+        let mut state = ResolveState::from_file(db, file);
+        for (top, _) in parse_file(db, file).tops(db) {
+            if let Top::Use(use_) = top {
+                state.import(use_);
+            }
+        }
+        state.should_error = false;
+        state
+    }
+
+    /// Expands `path` if it names a type alias, with `args` substituted for its generics
+    pub fn expand_alias(&mut self, path: ModulePath<'db>, args: &[Ty<'db>]) -> Option<Ty<'db>> {
+        if self.aliases.contains(&path) {
+            self.found_cycle = true;
+            return Some(Ty::Unknown);
+        }
+        let (decl, found_cycle) = resolve_alias(self.db, path, self.aliases.clone())?;
+        self.found_cycle |= found_cycle;
+        let DeclKind::TypeAlias { generics, ty } = decl.kind(self.db) else {
+            return None;
+        };
+        // The wrong number of arguments is reported when the type is checked
+        if generics.len() != args.len() {
+            return Some(Ty::Unknown);
+        }
+        let generics = generics
+            .iter()
+            .map(|generic| generic.name.0.clone())
+            .zip(args.iter().cloned())
+            .collect::<HashMap<_, _>>();
+        Some(ty.parameterize(&generics))
+    }
+
+    /// The value of the constant at `path`
+    pub fn eval_const(&mut self, path: ModulePath<'db>) -> Option<Literal> {
+        if self.consts.contains(&path) {
+            self.found_cycle = true;
+            return None;
+        }
+        let (decl, found_cycle) = resolve_const(self.db, path, self.consts.clone())?;
+        self.found_cycle |= found_cycle;
+        let DeclKind::Const { value, .. } = decl.kind(self.db) else {
+            return None;
+        };
+        value
+    }
+}
+
+/// The file and top-level item that `path` refers to, if it's one
+pub fn find_top<'db>(
+    db: &'db dyn Db,
+    path: ModulePath<'db>,
+) -> Option<(SourceFile, &'db Spanned<Top>)> {
+    let (name, module) = path.name(db).split_last()?;
+    let file = *db
+        .files()
+        .into_iter()
+        .find(|file| file.module(db) == module)?;
+    let top = parse_file(db, file)
+        .tops(db)
+        .iter()
+        .find(|(top, _)| top.get_name() == Some(name.as_str()))?;
+    Some((file, top))
+}
+
+#[cfg(test)]
+mod doc_tests {
+    use std::path::Path;
+
+    use crate::{check::tests::source_db, db::input::Db as _, util::Span};
+
+    use super::ResolveState;
+
+    const SRC: &str = "/// The first line
+///
+/// The last line
+
+fn documented() {}
+
+/// Not about the function below
+// A regular comment
+fn undocumented() {}
+
+fn bare() {}
+";
+
+    fn doc_of(name: &str) -> Option<String> {
+        let mut db = source_db(SRC);
+        let file = db.input(Path::new("/test/main.gib"));
+        let state = ResolveState::from_file(&db, file);
+        let start = SRC.find(&format!("fn {name}")).unwrap();
+        state.doc(Span::from(start..start + name.len() + 3))
+    }
+
+    #[test]
+    fn test_doc_collects_lines_above() {
+        assert_eq!(
+            doc_of("documented").as_deref(),
+            Some("The first line\n\nThe last line")
+        );
+    }
+
+    #[test]
+    fn test_doc_stops_at_other_text() {
+        assert_eq!(doc_of("undocumented"), None);
+        assert_eq!(doc_of("bare"), None);
+    }
 }
 
 // #[cfg(test)]
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use gvm::format::literal::Literal;
+
+use crate::{
+    check::err::{simple::Simple, CheckError},
+    db::decl::{Decl, DeclKind},
+    parser::{
+        expr::{op::OpKind, Expr},
+        top::{const_::Const, Top},
+    },
+    resolve::state::ResolveState,
+    util::Span,
+};
+
+impl Const {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
+        let ty = self.ty.0.resolve(state);
+        state.found_cycle = false;
+        state.consts.push(state.module_path());
+        let mut value = self.value.0.eval(state, self.value.1);
+        state.consts.pop();
+        if state.found_cycle {
+            state.error(CheckError::Simple(Simple {
+                message: format!("Constant '{}' depends on itself", self.name.0),
+                span: self.name.1,
+                file: state.file_data,
+            }));
+            // Left set, so a constant that depends on this one knows it's part of the cycle
+            value = None;
+        }
+        let kind = DeclKind::Const { ty, value };
+        Decl::new(
+            state.db,
+            self.name.0.clone(),
+            self.name.1,
+            kind,
+            Some(state.file_data),
+            state.module_path(),
+            state.doc(span),
+            HashMap::new(),
+        )
+    }
+}
+
+impl Expr {
+    /// Folds a constant's value, which can only be made of literals, operators and other
+    /// constants. Gives `None` once the reason it can't be folded has been reported
+    pub fn eval(&self, state: &mut ResolveState<'_>, span: Span) -> Option<Literal> {
+        match self {
+            Expr::Literal(literal) => Some(literal.clone()),
+            Expr::Ident(name) => {
+                let path = state.get_decl(name);
+                match state.find_top(path) {
+                    Some((_, Top::Const(_))) => state.eval_const(path),
+                    Some(_) => {
+                        not_const(state, span);
+                        None
+                    }
+                    // A member of another item, as unresolved names are reported when checked
+                    None if state.find_top(path.get_parent(state.db)).is_some() => {
+                        not_const(state, span);
+                        None
+                    }
+                    None => None,
+                }
+            }
+            Expr::Op(op) => {
+                let left = op.left.0.eval(state, op.left.1);
+                let right = op.right.0.eval(state, op.right.1);
+                match fold(&op.kind, left?, right?) {
+                    Ok(value) => Some(value),
+                    Err(message) => {
+                        state.error(CheckError::Simple(Simple {
+                            message,
+                            span,
+                            file: state.file_data,
+                        }));
+                        None
+                    }
+                }
+            }
+            Expr::Error => None,
+            _ => {
+                not_const(state, span);
+                None
+            }
+        }
+    }
+}
+
+fn not_const(state: &mut ResolveState<'_>, span: Span) {
+    state.error(CheckError::Simple(Simple {
+        message: "Constants can only be made of literals, operators and other constants"
+            .to_string(),
+        span,
+        file: state.file_data,
+    }));
+}
+
+/// Applies `kind` the way the VM would, or gives the reason it can't be done at compile time
+fn fold(kind: &OpKind, left: Literal, right: Literal) -> Result<Literal, String> {
+    match (kind, left, right) {
+        (OpKind::Div | OpKind::Mod, Literal::Int(_), Literal::Int(0)) => {
+            Err("Division by zero in constant".to_string())
+        }
+        (
+            OpKind::Add | OpKind::Sub | OpKind::Mul | OpKind::Div | OpKind::Mod,
+            Literal::Int(a),
+            Literal::Int(b),
+        ) => {
+            let value = match kind {
+                OpKind::Add => a.checked_add(b),
+                OpKind::Sub => a.checked_sub(b),
+                OpKind::Mul => a.checked_mul(b),
+                OpKind::Div => a.checked_div(b),
+                _ => a.checked_rem(b),
+            };
+            value
+                .map(Literal::Int)
+                .ok_or_else(|| "Integer overflow in constant".to_string())
+        }
+        (OpKind::Add, Literal::Float(a), Literal::Float(b)) => Ok(Literal::Float(a + b)),
+        (OpKind::Sub, Literal::Float(a), Literal::Float(b)) => Ok(Literal::Float(a - b)),
+        (OpKind::Mul, Literal::Float(a), Literal::Float(b)) => Ok(Literal::Float(a * b)),
+        (OpKind::Div, Literal::Float(a), Literal::Float(b)) => Ok(Literal::Float(a / b)),
+        (OpKind::Lt | OpKind::Gt | OpKind::Lte | OpKind::Gte, Literal::Int(a), Literal::Int(b)) => {
+            Ok(Literal::Bool(compare(kind, a, b)))
+        }
+        (
+            OpKind::Lt | OpKind::Gt | OpKind::Lte | OpKind::Gte,
+            Literal::Float(a),
+            Literal::Float(b),
+        ) => Ok(Literal::Bool(compare(kind, a, b))),
+        (OpKind::And, Literal::Bool(a), Literal::Bool(b)) => Ok(Literal::Bool(a && b)),
+        (OpKind::Or, Literal::Bool(a), Literal::Bool(b)) => Ok(Literal::Bool(a || b)),
+        // Strings are compared by reference at runtime, so folding them could change the result
+        (OpKind::Eq | OpKind::Neq, Literal::String(_), _) => {
+            Err("Strings can't be compared in a constant".to_string())
+        }
+        (OpKind::Eq, a, b) => Ok(Literal::Bool(a == b)),
+        (OpKind::Neq, a, b) => Ok(Literal::Bool(a != b)),
+        _ => Err(format!(
+            "'{kind}' can't be used on these values in a constant"
+        )),
+    }
+}
+
+fn compare<T: PartialOrd>(kind: &OpKind, a: T, b: T) -> bool {
+    match kind {
+        OpKind::Lt => a < b,
+        OpKind::Gt => a > b,
+        OpKind::Lte => a <= b,
+        _ => a >= b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gvm::format::literal::Literal;
+
+    use crate::{
+        check::tests::{check_src, run_src},
+        parser::expr::op::OpKind,
+    };
+
+    use super::fold;
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(
+            fold(&OpKind::Mul, Literal::Int(60), Literal::Int(24)),
+            Ok(Literal::Int(1440))
+        );
+        assert_eq!(
+            fold(&OpKind::Mod, Literal::Int(-7), Literal::Int(3)),
+            Ok(Literal::Int(-1))
+        );
+        assert_eq!(
+            fold(&OpKind::Add, Literal::Float(1.5), Literal::Float(2.0)),
+            Ok(Literal::Float(3.5))
+        );
+        assert_eq!(
+            fold(&OpKind::Lte, Literal::Int(3), Literal::Int(3)),
+            Ok(Literal::Bool(true))
+        );
+        assert_eq!(
+            fold(&OpKind::Or, Literal::Bool(false), Literal::Bool(true)),
+            Ok(Literal::Bool(true))
+        );
+        assert_eq!(
+            fold(&OpKind::Neq, Literal::Char('a'), Literal::Char('b')),
+            Ok(Literal::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_fold_overflow() {
+        let overflow = Err("Integer overflow in constant".to_string());
+        assert_eq!(
+            fold(&OpKind::Add, Literal::Int(i32::MAX), Literal::Int(1)),
+            overflow
+        );
+        assert_eq!(
+            fold(&OpKind::Sub, Literal::Int(i32::MIN), Literal::Int(1)),
+            overflow
+        );
+        assert_eq!(
+            fold(&OpKind::Mul, Literal::Int(65536), Literal::Int(65536)),
+            overflow
+        );
+        assert_eq!(
+            fold(&OpKind::Div, Literal::Int(i32::MIN), Literal::Int(-1)),
+            overflow
+        );
+    }
+
+    #[test]
+    fn test_fold_division_by_zero() {
+        let by_zero = Err("Division by zero in constant".to_string());
+        assert_eq!(
+            fold(&OpKind::Div, Literal::Int(1), Literal::Int(0)),
+            by_zero
+        );
+        assert_eq!(
+            fold(&OpKind::Mod, Literal::Int(1), Literal::Int(0)),
+            by_zero
+        );
+    }
+
+    #[test]
+    fn test_fold_mismatched_values() {
+        assert_eq!(
+            fold(&OpKind::Add, Literal::Int(1), Literal::Float(1.0)),
+            Err("'+' can't be used on these values in a constant".to_string())
+        );
+        assert_eq!(
+            fold(
+                &OpKind::Eq,
+                Literal::String("a".to_string()),
+                Literal::String("a".to_string())
+            ),
+            Err("Strings can't be compared in a constant".to_string())
+        );
+    }
+
+    #[test]
+    fn test_const_cycles() {
+        let mut errors = check_src(
+            "use std::Int
+
+const SELF: Int = SELF + 1
+const FIRST: Int = SECOND * 2
+const SECOND: Int = FIRST
+const FINE: Int = 60 * 60
+",
+        );
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "Constant 'FIRST' depends on itself".to_string(),
+                "Constant 'SECOND' depends on itself".to_string(),
+                "Constant 'SELF' depends on itself".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_const_chain_folds_each_once() {
+        // Each constant uses the one before three times, which would take 3^40 evaluations if
+        // they were folded again for every use
+        let mut src = "use std::Int\nuse std::println\n\nconst C0: Int = 1\n".to_string();
+        for i in 1..=40 {
+            let prev = format!("C{}", i - 1);
+            src.push_str(&format!("const C{i}: Int = {prev} + {prev} - {prev}\n"));
+        }
+        src.push_str("\nfn main() {\n    println(C40)\n}\n");
+        assert_eq!(run_src(&src), "1\n");
+    }
+}
@@ -1,16 +1,19 @@
+use std::collections::HashMap;
+
 use crate::{
     db::decl::{Decl, DeclKind},
     parser::top::enum_::Enum,
     resolve::state::ResolveState,
+    util::Span,
 };
 
 impl Enum {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>) -> Decl<'db> {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
         let generics = self.generics.0.resolve(state);
         let mut variants = vec![];
         for m in &self.members {
             state.path.push(m.0.name.0.clone());
-            let decl = m.0.resolve(state);
+            let decl = m.0.resolve(state, m.1);
             variants.push(decl);
             state.path.pop();
         }
@@ -22,6 +25,8 @@ impl Enum {
             kind,
             Some(state.file_data),
             state.module_path(),
+            state.doc(span),
+            HashMap::new(),
         )
     }
 }
@@ -2,10 +2,11 @@ use crate::{
     db::decl::{Decl, DeclKind},
     parser::top::enum_member::EnumMember,
     resolve::state::ResolveState,
+    util::Span,
 };
 
 impl EnumMember {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>) -> Decl<'db> {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
         let kind = DeclKind::Member {
             body: self.body.0.resolve(state),
         };
@@ -17,6 +18,8 @@ impl EnumMember {
             kind,
             Some(state.file_data),
             state.module_path(),
+            state.doc(span),
+            self.body.0.field_docs(state),
         )
     }
 }
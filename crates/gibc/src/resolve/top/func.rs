@@ -1,12 +1,20 @@
+use std::collections::HashMap;
+
 use crate::{
     db::decl::{func::Function, Decl, DeclKind},
     parser::top::func::Func,
     resolve::state::ResolveState,
     ty::Ty,
+    util::Span,
 };
 
 impl Func {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, virtual_: bool) -> Decl<'db> {
+    pub fn resolve<'db>(
+        &self,
+        state: &mut ResolveState<'db>,
+        virtual_: bool,
+        span: Span,
+    ) -> Decl<'db> {
         let name = self.name.clone();
         let generics = self.generics.0.resolve(state);
         let receiver = self.receiver.as_ref().map(|(rec, _)| rec.resolve(state));
@@ -32,6 +40,8 @@ impl Func {
             kind,
             Some(state.file_data),
             state.module_path(),
+            state.doc(span),
+            HashMap::new(),
         )
     }
 }
@@ -9,7 +9,7 @@ impl Impl {
         let mut functions = Vec::new();
         for func in &self.body {
             state.enter_scope();
-            let decl = func.0.resolve(state, false);
+            let decl = func.0.resolve(state, false, func.1);
             functions.push(decl);
             state.exit_scope();
         }
@@ -1,7 +1,8 @@
-use crate::{db::decl::Decl, parser::top::Top};
+use crate::{db::decl::Decl, parser::top::Top, util::Span};
 
 use super::state::ResolveState;
 
+pub mod const_;
 pub mod enum_;
 pub mod enum_member;
 pub mod func;
@@ -10,19 +11,22 @@ pub mod impl_;
 pub mod struct_;
 pub mod struct_body;
 pub mod trait_;
+pub mod type_alias;
 
 impl Top {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>) -> Option<Decl<'db>> {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Option<Decl<'db>> {
         let name = self.get_name();
         if let Some(name) = name {
             state.path.push(name.to_string());
             state.enter_scope();
         }
         let res = match self {
-            Top::Func(f) => Some(f.resolve(state, false)),
-            Top::Struct(s) => Some(s.resolve(state)),
-            Top::Enum(e) => Some(e.resolve(state)),
-            Top::Trait(t) => Some(t.resolve(state)),
+            Top::Func(f) => Some(f.resolve(state, false, span)),
+            Top::Struct(s) => Some(s.resolve(state, span)),
+            Top::Enum(e) => Some(e.resolve(state, span)),
+            Top::Trait(t) => Some(t.resolve(state, span)),
+            Top::TypeAlias(t) => Some(t.resolve(state, span)),
+            Top::Const(c) => Some(c.resolve(state, span)),
             Top::Use(u) => {
                 state.import(u);
                 None
@@ -1,12 +1,15 @@
-use crate::{db::decl::DeclKind, parser::top::struct_::Struct, resolve::state::ResolveState};
+use crate::{
+    db::decl::DeclKind, parser::top::struct_::Struct, resolve::state::ResolveState, util::Span,
+};
 
 use super::Decl;
 
 impl Struct {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>) -> Decl<'db> {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
         let generics = self.generics.0.resolve(state);
         let name = self.name.clone();
         let body = self.body.0.resolve(state);
+        let field_docs = self.body.0.field_docs(state);
         let kind = DeclKind::Struct { generics, body };
         Decl::new(
             state.db,
@@ -15,6 +18,8 @@ impl Struct {
             kind,
             Some(state.file_data),
             state.module_path(),
+            state.doc(span),
+            field_docs,
         )
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{
     db::decl::struct_::StructDecl,
     parser::top::{struct_body::StructBody, struct_field::StructField},
@@ -21,6 +23,17 @@ impl StructBody {
             }
         }
     }
+
+    /// The docs of the documented fields, by field name
+    pub fn field_docs(&self, state: &ResolveState<'_>) -> HashMap<String, String> {
+        let StructBody::Fields(fields) = self else {
+            return HashMap::new();
+        };
+        fields
+            .iter()
+            .filter_map(|(field, span)| Some((field.name.0.clone(), state.doc(*span)?)))
+            .collect()
+    }
 }
 
 impl StructField {
@@ -1,14 +1,17 @@
+use std::collections::HashMap;
+
 use crate::{
     db::{decl::DeclKind, path::ModulePath},
     parser::top::trait_::Trait,
     resolve::state::ResolveState,
     ty::{Named, Ty},
+    util::Span,
 };
 
 use super::Decl;
 
 impl Trait {
-    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>) -> Decl<'db> {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
         let generics = self.generics.0.resolve(state);
         state.add_self_ty(
             Ty::Named(Named {
@@ -22,7 +25,7 @@ impl Trait {
         for func in &self.body {
             state.enter_scope();
             state.path.push(func.0.name.0.clone());
-            let decl = func.0.resolve(state, true);
+            let decl = func.0.resolve(state, true, func.1);
             body.push(decl);
             state.path.pop();
             state.exit_scope();
@@ -35,6 +38,8 @@ impl Trait {
             kind,
             Some(state.file_data),
             state.module_path(),
+            state.doc(span),
+            HashMap::new(),
         )
     }
 }
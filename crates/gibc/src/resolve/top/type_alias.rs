@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use crate::{
+    check::err::{simple::Simple, CheckError},
+    db::decl::{Decl, DeclKind},
+    parser::top::type_alias::TypeAlias,
+    resolve::state::ResolveState,
+    ty::Ty,
+    util::Span,
+};
+
+impl TypeAlias {
+    pub fn resolve<'db>(&self, state: &mut ResolveState<'db>, span: Span) -> Decl<'db> {
+        let generics = self.generics.0.resolve(state);
+        state.found_cycle = false;
+        state.aliases.push(state.module_path());
+        let mut ty = self.ty.0.resolve(state);
+        state.aliases.pop();
+        if state.found_cycle {
+            state.error(CheckError::Simple(Simple {
+                message: format!("Type alias '{}' expands to itself", self.name.0),
+                span: self.name.1,
+                file: state.file_data,
+            }));
+            // Left set, so an alias that expands to this one knows it's part of the cycle
+            ty = Ty::Unknown;
+        }
+        let kind = DeclKind::TypeAlias { generics, ty };
+        Decl::new(
+            state.db,
+            self.name.0.clone(),
+            self.name.1,
+            kind,
+            Some(state.file_data),
+            state.module_path(),
+            state.doc(span),
+            HashMap::new(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::tests::check_src;
+
+    #[test]
+    fn test_alias_expands_with_args() {
+        let errors = check_src(
+            "use std::Int
+use std::Option
+
+type Maybe[T] = Option[T]
+type Found = Maybe[Int]
+
+fn main() {
+    let found: Found = Option::Some(3)
+    let wrong: Maybe[Int] = 3
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec!["Expected Option[Int] but found Int".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_alias_arg_count() {
+        let errors = check_src(
+            "use std::Int
+use std::Option
+
+type Maybe[T] = Option[T]
+type Step = (Int) -> Int
+
+fn main() {
+    let missing: Maybe = Option::Some(3)
+    let extra: Step[Int] = 3
+}
+",
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Expected 1 type argument(s) for 'Maybe', but found 0".to_string(),
+                "Expected 0 type argument(s) for 'Step', but found 1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alias_cycles() {
+        let mut errors = check_src(
+            "use std::Int
+
+type Loop = Loop
+type Ping = (Pong, Int)
+type Pong = Ping
+type Fine = Int
+",
+        );
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "Type alias 'Loop' expands to itself".to_string(),
+                "Type alias 'Ping' expands to itself".to_string(),
+                "Type alias 'Pong' expands to itself".to_string(),
+            ]
+        );
+    }
+}
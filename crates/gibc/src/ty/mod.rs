@@ -1,6 +1,10 @@
 use salsa::Update;
 
-use crate::{db::path::ModulePath, parser::common::variance::Variance, util::Spanned};
+use crate::{
+    db::{input::Db, path::ModulePath},
+    parser::common::variance::Variance,
+    util::Spanned,
+};
 
 pub mod func;
 pub mod imply;
@@ -56,4 +60,24 @@ impl<'db> Ty<'db> {
             false
         }
     }
+
+    /// The type arguments of `std::{name}`, if this is that type
+    pub fn std_args(&self, db: &'db dyn Db, name: &str) -> Option<&[Ty<'db>]> {
+        if let Ty::Named(Named { name: path, args }) = self {
+            if path.name(db) == &["std", name] {
+                return Some(args);
+            }
+        }
+        None
+    }
+
+    /// The item type `T` if this is `std::Generator[T]`
+    pub fn generator_item(&self, db: &'db dyn Db) -> Option<Ty<'db>> {
+        if let Ty::Named(Named { name, args }) = self {
+            if name.name(db) == &["std", "Generator"] {
+                return args.first().cloned();
+            }
+        }
+        None
+    }
 }
@@ -0,0 +1,380 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::format::{func::FuncDef, instr::ByteCode, literal::Literal, sorted, ByteCodeFile};
+
+use super::CompileError;
+
+const RUNTIME: &str = include_str!("runtime.c");
+
+/// Translates a program into a single C source file, including the runtime it needs.
+///
+/// Every function reachable from the entry point becomes a C function working on the runtime's
+/// value stack, with jumps turned into `goto`s and `DynCall`s dispatched through a generated
+/// vtable lookup
+pub fn generate(file: &ByteCodeFile) -> Result<String, CompileError> {
+    let entry = file.entry.ok_or(CompileError::NoEntry)?;
+    let reachable = reachable(file, entry);
+    let funcs = sorted(&file.funcs)
+        .into_iter()
+        .filter(|(id, _)| reachable.contains(id))
+        .collect::<Vec<_>>();
+    let mut out = String::from(RUNTIME);
+
+    out.push('\n');
+    for (id, func) in &funcs {
+        let file_name = file.file_names.get(&func.file).map_or("", String::as_str);
+        writeln!(
+            out,
+            "static const FuncInfo info_{id} = {{{}, {}}};",
+            c_string(func.name.as_bytes()),
+            c_string(file_name.as_bytes())
+        )
+        .unwrap();
+        writeln!(out, "static Value f_{id}(Value *args);").unwrap();
+    }
+
+    out.push_str("\nstatic Value rt_call(uint32_t id, Value *args) {\n    switch (id) {\n");
+    for (id, _) in &funcs {
+        writeln!(out, "    case {id}u:\n        return f_{id}(args);").unwrap();
+    }
+    out.push_str("    }\n    rt_fail(\"Unknown function\");\n    return rt_none();\n}\n");
+
+    out.push_str(
+        "\nstatic uint32_t rt_vtable(uint64_t table, uint32_t func) {\n    switch (table) {\n",
+    );
+    for (table_id, table) in sorted(&file.tables) {
+        writeln!(out, "    case {table_id}ull:\n        switch (func) {{").unwrap();
+        for (trait_func, impl_func) in sorted(table) {
+            writeln!(
+                out,
+                "        case {trait_func}u:\n            return {impl_func}u;"
+            )
+            .unwrap();
+        }
+        out.push_str("        }\n        break;\n");
+    }
+    out.push_str(
+        "    }\n    rt_fail(\"No implementation of trait function\");\n    return 0;\n}\n",
+    );
+
+    for (id, func) in &funcs {
+        out.push('\n');
+        gen_func(&mut out, file, **id, func)?;
+    }
+
+    if !file.funcs.contains_key(&entry) {
+        return Err(CompileError::Missing {
+            func: "<entry>".to_string(),
+            id: entry,
+        });
+    }
+    writeln!(
+        out,
+        "\nint main(int argc, char **argv) {{\n    Value args[1];\n    rt_init(argc, argv);\n    f_{entry}(args);\n    return 0;\n}}"
+    )
+    .unwrap();
+    Ok(out)
+}
+
+/// The functions the entry point can call, so the parts of `std` compiled programs don't support
+/// are only rejected when they're used
+fn reachable(file: &ByteCodeFile, entry: u32) -> BTreeSet<u32> {
+    let mut found = BTreeSet::new();
+    let mut todo = vec![entry];
+    while let Some(id) = todo.pop() {
+        if !found.insert(id) {
+            continue;
+        }
+        let Some(func) = file.funcs.get(&id) else {
+            continue;
+        };
+        for instr in &func.body {
+            match instr {
+                ByteCode::Call(callee) | ByteCode::TailCall(callee) | ByteCode::Spawn(callee) => {
+                    todo.push(*callee);
+                }
+                ByteCode::DynCall(trait_func) | ByteCode::TailDynCall(trait_func) => {
+                    todo.extend(
+                        file.tables
+                            .values()
+                            .filter_map(|table| table.get(trait_func)),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+fn gen_func(
+    out: &mut String,
+    file: &ByteCodeFile,
+    id: u32,
+    func: &FuncDef,
+) -> Result<(), CompileError> {
+    let targets = func
+        .body
+        .iter()
+        .filter_map(|instr| match instr {
+            ByteCode::Je(target) | ByteCode::Jne(target) | ByteCode::Jmp(target) => {
+                Some(*target as usize)
+            }
+            // Calls to itself in tail position loop back to the start
+            ByteCode::TailCall(callee) if *callee == id => Some(0),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    let locals = func
+        .body
+        .iter()
+        .filter_map(|instr| match instr {
+            ByteCode::NewLocal(id) | ByteCode::GetLocal(id) | ByteCode::SetLocal(id) => {
+                Some(*id as usize + 1)
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(1);
+
+    writeln!(out, "static Value f_{id}(Value *args) {{").unwrap();
+    writeln!(out, "    Frame frame;").unwrap();
+    writeln!(
+        out,
+        "    rt_enter(&frame, &info_{id}, {}, {});",
+        func.pos.0, func.pos.1
+    )
+    .unwrap();
+    writeln!(out, "    size_t base = rt_sp;").unwrap();
+    writeln!(out, "    Value locals[{locals}];").unwrap();
+    writeln!(out, "    (void)args;\n    (void)locals;").unwrap();
+
+    // The position is updated wherever it can change, matching `ProgramState::scope_pos`
+    for (index, instr) in func.body.iter().enumerate() {
+        let is_target = targets.contains(&index);
+        if is_target {
+            writeln!(out, "L_{index}:;").unwrap();
+        }
+        if is_target || func.marks.iter().any(|mark| mark.0 == index) {
+            let (line, col) = func
+                .marks
+                .iter()
+                .rev()
+                .find(|mark| mark.0 <= index)
+                .map_or(func.pos, |mark| mark.1);
+            writeln!(out, "    frame.line = {line};\n    frame.col = {col};").unwrap();
+        }
+        gen_instr(out, file, id, func, instr)?;
+    }
+    if targets.contains(&func.body.len()) {
+        writeln!(out, "L_{}:;", func.body.len()).unwrap();
+    }
+    writeln!(out, "    return rt_return(base);\n}}").unwrap();
+    Ok(())
+}
+
+fn gen_instr(
+    out: &mut String,
+    file: &ByteCodeFile,
+    func_id: u32,
+    func: &FuncDef,
+    instr: &ByteCode,
+) -> Result<(), CompileError> {
+    let arg_count = |id: &u32| {
+        file.funcs
+            .get(id)
+            .map(|func| func.args as usize)
+            .ok_or_else(|| CompileError::Missing {
+                func: func.name.clone(),
+                id: *id,
+            })
+    };
+    let line = match instr {
+        ByteCode::Push(lit) => match lit {
+            Literal::Int(num) => format!("rt_push(rt_int({num}));"),
+            Literal::Float(num) => format!("rt_push(rt_float_bits({:#x}u));", num.to_bits()),
+            Literal::Char(c) => format!("rt_push(rt_char({}u));", *c as u32),
+            Literal::Bool(val) => format!("rt_push(rt_bool({val}));"),
+            Literal::String(text) => format!(
+                "rt_push_string({}, {});",
+                c_string(text.as_bytes()),
+                text.len()
+            ),
+        },
+        ByteCode::Copy => "rt_copy();".to_string(),
+        ByteCode::Pop => "rt_pop();".to_string(),
+        ByteCode::Print => "rt_print();".to_string(),
+        ByteCode::Panic => "rt_panic();".to_string(),
+        ByteCode::Construct { id, len } => format!("rt_construct({id}u, {len}u);"),
+        ByteCode::Dyn(id) => format!("rt_dyn({id}ull);"),
+        ByteCode::TailCall(id) if *id == func_id => {
+            let args = arg_count(id)?;
+            format!(
+                "for (int i = {args} - 1; i >= 0; i--) {{\n        args[i] = rt_pop();\n    }}\n    rt_sp = base;\n    goto L_0;"
+            )
+        }
+        // Other tail calls are left to the C compiler, as the following `Return` ends the function
+        ByteCode::Call(id) | ByteCode::TailCall(id) => {
+            let args = arg_count(id)?;
+            format!(
+                "{{\n        Value a[{}];\n        for (int i = {args} - 1; i >= 0; i--) {{\n            a[i] = rt_pop();\n        }}\n        Value r = f_{id}(a);\n        if (r.tag != T_NONE) {{\n            rt_push(r);\n        }}\n    }}",
+                args.max(1)
+            )
+        }
+        ByteCode::DynCall(id) | ByteCode::TailDynCall(id) => {
+            let args = arg_count(id)?;
+            format!(
+                "{{\n        Value a[{}];\n        uint64_t table;\n        for (int i = {args} - 1; i > 0; i--) {{\n            a[i] = rt_pop();\n        }}\n        a[0] = rt_undyn(rt_pop(), &table);\n        Value r = rt_call(rt_vtable(table, {id}u), a);\n        if (r.tag != T_NONE) {{\n            rt_push(r);\n        }}\n    }}",
+                args.max(1)
+            )
+        }
+        ByteCode::Return => "return rt_return(base);".to_string(),
+        ByteCode::Index(index) => format!("rt_index({index}u);"),
+        ByteCode::SetIndex(index) => format!("rt_set_index({index}u);"),
+        ByteCode::VecGet => "rt_vec_get();".to_string(),
+        ByteCode::VecSet => "rt_vec_set();".to_string(),
+        ByteCode::VecPush => "rt_vec_push();".to_string(),
+        ByteCode::VecPop => "rt_vec_pop();".to_string(),
+        ByteCode::VecPeak => "rt_vec_peak();".to_string(),
+        ByteCode::VecInsert => "rt_vec_insert();".to_string(),
+        ByteCode::VecRemove => "rt_vec_remove();".to_string(),
+        ByteCode::VecLen => "rt_vec_len();".to_string(),
+        ByteCode::NewLocal(id) | ByteCode::SetLocal(id) => format!("locals[{id}] = rt_pop();"),
+        ByteCode::GetLocal(id) => format!("rt_push(locals[{id}]);"),
+        ByteCode::Param(id) => format!("rt_push(args[{id}]);"),
+        ByteCode::Je(target) => format!("if (rt_pop_bool()) {{\n        goto L_{target};\n    }}"),
+        ByteCode::Jne(target) => {
+            format!("if (!rt_pop_bool()) {{\n        goto L_{target};\n    }}")
+        }
+        ByteCode::Jmp(target) => format!("goto L_{target};"),
+        ByteCode::Mul => "rt_mul();".to_string(),
+        ByteCode::Div => "rt_div();".to_string(),
+        ByteCode::Add => "rt_add();".to_string(),
+        ByteCode::Sub => "rt_sub();".to_string(),
+        ByteCode::Mod => "rt_mod();".to_string(),
+        ByteCode::Lt => "rt_lt();".to_string(),
+        ByteCode::Gt => "rt_gt();".to_string(),
+        ByteCode::Lte => "rt_lte();".to_string(),
+        ByteCode::Gte => "rt_gte();".to_string(),
+        ByteCode::Eq => "rt_eq();".to_string(),
+        ByteCode::Neq => "rt_neq();".to_string(),
+        ByteCode::Or => "rt_or();".to_string(),
+        ByteCode::And => "rt_and();".to_string(),
+        ByteCode::Not => "rt_not();".to_string(),
+        ByteCode::Match(id) => format!("rt_match({id}u);"),
+        ByteCode::Clone => "rt_clone();".to_string(),
+        ByteCode::Sqrt => "rt_sqrt();".to_string(),
+        ByteCode::Pow => "rt_pow();".to_string(),
+        ByteCode::Abs => "rt_abs();".to_string(),
+        ByteCode::Floor => "rt_floor();".to_string(),
+        ByteCode::Min => "rt_min();".to_string(),
+        ByteCode::Max => "rt_max();".to_string(),
+        ByteCode::ToInt => "rt_to_int();".to_string(),
+        ByteCode::ToFloat => "rt_to_float();".to_string(),
+        ByteCode::ToString => "rt_to_string();".to_string(),
+        ByteCode::Concat(count) => format!("rt_concat({count}u);"),
+        ByteCode::ParseInt { some, none } => format!("rt_parse_int({some}u, {none}u);"),
+        ByteCode::ParseFloat { some, none } => format!("rt_parse_float({some}u, {none}u);"),
+        ByteCode::ReadLine { some, none } => format!("rt_read_line({some}u, {none}u);"),
+        ByteCode::Time => "rt_time();".to_string(),
+        ByteCode::Random => "rt_random();".to_string(),
+        ByteCode::Args => "rt_args();".to_string(),
+        ByteCode::Spawn(_)
+        | ByteCode::Channel
+        | ByteCode::Send
+        | ByteCode::Receive
+        | ByteCode::Generator
+        | ByteCode::Yield(_)
+        | ByteCode::Resume(_) => {
+            return Err(CompileError::Unsupported {
+                func: func.name.clone(),
+                instr: instr.clone(),
+            })
+        }
+    };
+    writeln!(out, "    {line}").unwrap();
+    Ok(())
+}
+
+/// A C string literal, using octal escapes so the following byte can't extend them
+fn c_string(bytes: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for byte in bytes {
+        match byte {
+            b'"' | b'\\' => {
+                out.push('\\');
+                out.push(*byte as char);
+            }
+            b' '..=b'~' if *byte != b'?' => out.push(*byte as char),
+            _ => write!(out, "\\{byte:03o}").unwrap(),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::{func::FuncDef, instr::ByteCode, literal::Literal, ByteCodeFile};
+
+    use super::{c_string, generate};
+    use crate::aot::CompileError;
+
+    #[test]
+    fn test_c_string_escapes() {
+        assert_eq!(c_string(b"a\"b\\c\n"), "\"a\\\"b\\\\c\\012\"");
+        assert_eq!(c_string("é1".as_bytes()), "\"\\303\\2511\"");
+    }
+
+    #[test]
+    fn test_generate_rejects_threads() {
+        let mut file = ByteCodeFile {
+            entry: Some(0),
+            ..ByteCodeFile::default()
+        };
+        file.funcs.insert(
+            0,
+            FuncDef {
+                name: "main".to_string(),
+                args: 0,
+                pos: (1, 1),
+                file: 0,
+                body: vec![ByteCode::Push(Literal::Int(1)), ByteCode::Channel],
+                marks: vec![],
+            },
+        );
+        assert!(matches!(
+            generate(&file),
+            Err(CompileError::Unsupported {
+                instr: ByteCode::Channel,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate_skips_unreachable() {
+        let mut file = ByteCodeFile {
+            entry: Some(0),
+            ..ByteCodeFile::default()
+        };
+        for (id, name, body) in [
+            (0, "main", vec![ByteCode::Call(1), ByteCode::Return]),
+            (1, "used", vec![ByteCode::Return]),
+            (2, "unused", vec![ByteCode::Channel, ByteCode::Return]),
+        ] {
+            let func = FuncDef {
+                name: name.to_string(),
+                args: 0,
+                pos: (1, 1),
+                file: 0,
+                body,
+                marks: vec![],
+            };
+            file.funcs.insert(id, func);
+        }
+        let source = generate(&file).unwrap();
+        assert!(source.contains("static Value f_1(Value *args) {"));
+        assert!(!source.contains("f_2"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    env, fs, io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+use thiserror::Error;
+
+use crate::format::{instr::ByteCode, ByteCodeFile};
+
+pub mod c;
+
+#[derive(Error, Debug)]
+pub enum CompileError {
+    #[error("The program has no entry point")]
+    NoEntry,
+
+    #[error("'{func}' uses {instr:?}, which isn't supported by compiled programs")]
+    Unsupported { func: String, instr: ByteCode },
+
+    #[error("'{func}' refers to function {id}, which isn't defined")]
+    Missing { func: String, id: u32 },
+
+    #[error("Could not run the C compiler '{cc}': {source}")]
+    Spawn { cc: String, source: io::Error },
+
+    #[error("The C compiler failed ({0})")]
+    Compiler(ExitStatus),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Compiles a program to a native executable using the system C compiler.
+///
+/// The compiler is taken from `$CC`, falling back to `cc`.
+/// The generated source is written next to the output while it is built
+pub fn compile_c(file: &ByteCodeFile, output: &Path) -> Result<(), CompileError> {
+    let source = c::generate(file)?;
+    let source_path = output.with_extension("c");
+    fs::write(&source_path, source)?;
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&cc)
+        .arg("-O2")
+        .arg("-o")
+        .arg(output)
+        .arg(&source_path)
+        .arg("-lm")
+        .status()
+        .map_err(|source| CompileError::Spawn { cc, source });
+    fs::remove_file(&source_path)?;
+    let status = status?;
+    if !status.success() {
+        return Err(CompileError::Compiler(status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process::Command};
+
+    use crate::{text::decode::parser::parse_text_file, vm::state::ProgramState};
+
+    use super::{compile_c, CompileError};
+
+    #[test]
+    fn test_compiled_output_matches_vm() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 1 1 0
+            push 0 new 0
+            get 0 push 5 lt jne 17
+            get 0 push 2 mul print push ", " print
+            get 0 push 1 add set 0 jmp 2
+            push 1.5 push 2.25 add print
+            push true print return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables.clone(),
+            file.file_names.clone(),
+            file.type_names.clone(),
+        );
+        prog.captured = Some(String::new());
+        prog.run();
+        let expected = prog.captured.unwrap();
+        assert_eq!(expected, "0, 2, 4, 6, 8, 3.75true");
+
+        let exe = env::temp_dir().join(format!("gvm-aot-test-{}", std::process::id()));
+        match compile_c(&file, &exe) {
+            Err(CompileError::Spawn { cc, .. }) => {
+                eprintln!("Skipping, no C compiler ('{cc}')");
+                return;
+            }
+            res => res.unwrap(),
+        }
+        let output = Command::new(&exe).output().unwrap();
+        std::fs::remove_file(&exe).unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+    }
+}
@@ -0,0 +1,904 @@
+// Runtime for programs compiled by `gvm compile --target c`.
+// Mirrors the behaviour of the interpreter in `vm/exec.rs`, including how values are printed.
+#include <math.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <time.h>
+
+typedef enum { T_NONE, T_INT, T_FLOAT, T_CHAR, T_BOOL, T_HEAP } Tag;
+
+typedef struct Obj Obj;
+
+typedef struct {
+    Tag tag;
+    union {
+        int32_t i;
+        float f;
+        uint32_t c;
+        bool b;
+        Obj *h;
+    };
+} Value;
+
+typedef enum { O_OBJECT, O_STRING, O_DYN } ObjKind;
+
+struct Obj {
+    ObjKind kind;
+    // O_OBJECT: type id and fields, O_DYN: vtable id and the wrapped value
+    uint32_t type_id;
+    uint64_t table_id;
+    Value *items;
+    size_t len;
+    size_t cap;
+    // O_STRING: utf-8 bytes
+    char *text;
+    size_t text_len;
+    Value inner;
+};
+
+typedef struct {
+    const char *name;
+    const char *file;
+} FuncInfo;
+
+typedef struct Frame {
+    const FuncInfo *info;
+    uint16_t line;
+    uint16_t col;
+    struct Frame *prev;
+} Frame;
+
+static Value *rt_stack;
+static size_t rt_sp;
+static size_t rt_cap;
+static Frame *rt_frames;
+
+typedef struct {
+    char *data;
+    size_t len;
+    size_t cap;
+} Buf;
+
+static void rt_print_trace(void);
+
+static void rt_fail(const char *message) {
+    fprintf(stderr, "%s\n", message);
+    rt_print_trace();
+    exit(101);
+}
+
+static void *rt_alloc(size_t size) {
+    void *ptr = calloc(1, size);
+    if (!ptr) {
+        rt_fail("Out of memory");
+    }
+    return ptr;
+}
+
+static void buf_push(Buf *buf, const char *data, size_t len) {
+    if (buf->len + len + 1 > buf->cap) {
+        buf->cap = (buf->len + len + 1) * 2;
+        buf->data = realloc(buf->data, buf->cap);
+        if (!buf->data) {
+            rt_fail("Out of memory");
+        }
+    }
+    memcpy(buf->data + buf->len, data, len);
+    buf->len += len;
+    buf->data[buf->len] = 0;
+}
+
+static void buf_str(Buf *buf, const char *data) { buf_push(buf, data, strlen(data)); }
+
+static Value rt_int(int32_t i) { return (Value){.tag = T_INT, .i = i}; }
+
+static Value rt_float(float f) { return (Value){.tag = T_FLOAT, .f = f}; }
+
+static Value rt_float_bits(uint32_t bits) {
+    float f;
+    memcpy(&f, &bits, sizeof f);
+    return rt_float(f);
+}
+
+static Value rt_char(uint32_t c) { return (Value){.tag = T_CHAR, .c = c}; }
+
+static Value rt_bool(bool b) { return (Value){.tag = T_BOOL, .b = b}; }
+
+static Value rt_heap(Obj *h) { return (Value){.tag = T_HEAP, .h = h}; }
+
+static Value rt_none(void) { return (Value){.tag = T_NONE}; }
+
+static void rt_push(Value value) {
+    if (rt_sp == rt_cap) {
+        rt_cap = rt_cap ? rt_cap * 2 : 1024;
+        rt_stack = realloc(rt_stack, rt_cap * sizeof(Value));
+        if (!rt_stack) {
+            rt_fail("Out of memory");
+        }
+    }
+    rt_stack[rt_sp++] = value;
+}
+
+static Value rt_pop(void) {
+    if (rt_sp == 0) {
+        rt_fail("Stack underflow");
+    }
+    return rt_stack[--rt_sp];
+}
+
+static Value rt_peak(void) {
+    if (rt_sp == 0) {
+        rt_fail("Stack underflow");
+    }
+    return rt_stack[rt_sp - 1];
+}
+
+static void rt_copy(void) { rt_push(rt_peak()); }
+
+static void rt_enter(Frame *frame, const FuncInfo *info, uint16_t line, uint16_t col) {
+    frame->info = info;
+    frame->line = line;
+    frame->col = col;
+    frame->prev = rt_frames;
+    rt_frames = frame;
+}
+
+// The top of a function's own stack is its return value, anything below it is discarded
+static Value rt_return(size_t base) {
+    Value ret = rt_sp > base ? rt_stack[rt_sp - 1] : rt_none();
+    rt_sp = base;
+    rt_frames = rt_frames->prev;
+    return ret;
+}
+
+static void rt_print_trace(void) {
+    size_t count = 0;
+    for (Frame *frame = rt_frames; frame; frame = frame->prev) {
+        count++;
+    }
+    Frame **frames = rt_alloc((count + 1) * sizeof(Frame *));
+    size_t i = count;
+    for (Frame *frame = rt_frames; frame; frame = frame->prev) {
+        frames[--i] = frame;
+    }
+    for (i = 0; i < count; i++) {
+        if (i > 0) {
+            printf("\n");
+        }
+        printf("  -> %s:%u:%u (%s)", frames[i]->info->file, frames[i]->line, frames[i]->col,
+               frames[i]->info->name);
+    }
+    printf("\n");
+    free(frames);
+}
+
+static Obj *rt_obj(ObjKind kind) {
+    Obj *obj = rt_alloc(sizeof(Obj));
+    obj->kind = kind;
+    return obj;
+}
+
+static Obj *rt_expect_obj(Value value) {
+    if (value.tag != T_HEAP) {
+        rt_fail("Expected heap obj");
+    }
+    return value.h;
+}
+
+static Obj *rt_expect_vec(Value value) {
+    Obj *obj = rt_expect_obj(value);
+    if (obj->kind != O_OBJECT) {
+        rt_fail("Expected vec");
+    }
+    return obj;
+}
+
+static void rt_vec_reserve(Obj *obj, size_t len) {
+    if (len > obj->cap) {
+        obj->cap = len * 2;
+        obj->items = realloc(obj->items, obj->cap * sizeof(Value));
+        if (!obj->items) {
+            rt_fail("Out of memory");
+        }
+    }
+}
+
+static Value rt_string(const char *text, size_t len) {
+    Obj *obj = rt_obj(O_STRING);
+    obj->text = rt_alloc(len + 1);
+    memcpy(obj->text, text, len);
+    obj->text_len = len;
+    return rt_heap(obj);
+}
+
+static void rt_push_string(const char *text, size_t len) { rt_push(rt_string(text, len)); }
+
+static void rt_construct(uint32_t id, uint32_t len) {
+    Obj *obj = rt_obj(O_OBJECT);
+    obj->type_id = id;
+    rt_vec_reserve(obj, len);
+    for (uint32_t i = 0; i < len; i++) {
+        obj->items[i] = rt_pop();
+    }
+    obj->len = len;
+    rt_push(rt_heap(obj));
+}
+
+static void rt_construct_option(bool found, Value value, uint32_t some, uint32_t none) {
+    if (found) {
+        rt_push(value);
+        rt_construct(some, 1);
+    } else {
+        rt_construct(none, 0);
+    }
+}
+
+static void rt_dyn(uint64_t table_id) {
+    Obj *obj = rt_obj(O_DYN);
+    obj->table_id = table_id;
+    obj->inner = rt_pop();
+    rt_push(rt_heap(obj));
+}
+
+static Value rt_undyn(Value value, uint64_t *table_id) {
+    Obj *obj = rt_expect_obj(value);
+    if (obj->kind != O_DYN) {
+        rt_fail("Expected dyn");
+    }
+    *table_id = obj->table_id;
+    return obj->inner;
+}
+
+static void rt_utf8(Buf *buf, uint32_t c) {
+    char bytes[4];
+    size_t len;
+    if (c < 0x80) {
+        bytes[0] = (char)c;
+        len = 1;
+    } else if (c < 0x800) {
+        bytes[0] = (char)(0xC0 | (c >> 6));
+        bytes[1] = (char)(0x80 | (c & 0x3F));
+        len = 2;
+    } else if (c < 0x10000) {
+        bytes[0] = (char)(0xE0 | (c >> 12));
+        bytes[1] = (char)(0x80 | ((c >> 6) & 0x3F));
+        bytes[2] = (char)(0x80 | (c & 0x3F));
+        len = 3;
+    } else {
+        bytes[0] = (char)(0xF0 | (c >> 18));
+        bytes[1] = (char)(0x80 | ((c >> 12) & 0x3F));
+        bytes[2] = (char)(0x80 | ((c >> 6) & 0x3F));
+        bytes[3] = (char)(0x80 | (c & 0x3F));
+        len = 4;
+    }
+    buf_push(buf, bytes, len);
+}
+
+// Formats a float the way Rust's `Display` does: the shortest digits that read back
+// as the same value, written out in full without an exponent
+static void rt_float_text(Buf *buf, float f) {
+    if (isnan(f)) {
+        buf_str(buf, "NaN");
+        return;
+    }
+    if (isinf(f)) {
+        buf_str(buf, f > 0 ? "inf" : "-inf");
+        return;
+    }
+    if (signbit(f)) {
+        buf_str(buf, "-");
+        f = -f;
+    }
+    if (f == 0) {
+        buf_str(buf, "0");
+        return;
+    }
+    char tmp[32];
+    for (int precision = 0; precision < 9; precision++) {
+        snprintf(tmp, sizeof tmp, "%.*e", precision, (double)f);
+        if (strtof(tmp, NULL) == f) {
+            break;
+        }
+    }
+    char digits[16];
+    size_t len = 0;
+    char *p = tmp;
+    for (; *p && *p != 'e'; p++) {
+        if (*p != '.') {
+            digits[len++] = *p;
+        }
+    }
+    int exp = atoi(p + 1);
+    if (exp >= 0) {
+        size_t whole = (size_t)exp + 1;
+        if (len <= whole) {
+            buf_push(buf, digits, len);
+            for (size_t i = len; i < whole; i++) {
+                buf_str(buf, "0");
+            }
+        } else {
+            buf_push(buf, digits, whole);
+            buf_str(buf, ".");
+            buf_push(buf, digits + whole, len - whole);
+        }
+    } else {
+        buf_str(buf, "0.");
+        for (int i = -1; i > exp; i--) {
+            buf_str(buf, "0");
+        }
+        buf_push(buf, digits, len);
+    }
+}
+
+static void rt_text(Buf *buf, Value value) {
+    char tmp[32];
+    switch (value.tag) {
+    case T_INT:
+        snprintf(tmp, sizeof tmp, "%d", value.i);
+        buf_str(buf, tmp);
+        break;
+    case T_FLOAT:
+        rt_float_text(buf, value.f);
+        break;
+    case T_CHAR:
+        rt_utf8(buf, value.c);
+        break;
+    case T_BOOL:
+        buf_str(buf, value.b ? "true" : "false");
+        break;
+    case T_HEAP:
+        switch (value.h->kind) {
+        case O_OBJECT:
+            snprintf(tmp, sizeof tmp, "Object(%u)", value.h->type_id);
+            buf_str(buf, tmp);
+            for (size_t i = 0; i < value.h->len; i++) {
+                rt_text(buf, value.h->items[i]);
+                buf_str(buf, ", ");
+            }
+            break;
+        case O_STRING:
+            buf_push(buf, value.h->text, value.h->text_len);
+            break;
+        case O_DYN:
+            snprintf(tmp, sizeof tmp, "Dyn(%llu, ", (unsigned long long)value.h->table_id);
+            buf_str(buf, tmp);
+            rt_text(buf, value.h->inner);
+            buf_str(buf, ")");
+            break;
+        }
+        break;
+    case T_NONE:
+        break;
+    }
+}
+
+static void rt_print(void) {
+    Buf buf = {0};
+    rt_text(&buf, rt_pop());
+    if (buf.len) {
+        fwrite(buf.data, 1, buf.len, stdout);
+    }
+    free(buf.data);
+}
+
+static void rt_panic(void) {
+    rt_print();
+    printf("\n");
+    rt_print_trace();
+    fflush(stdout);
+    exit(1);
+}
+
+static bool rt_pop_bool(void) {
+    Value value = rt_pop();
+    if (value.tag != T_BOOL) {
+        rt_fail("Expected condition to be a boolean");
+    }
+    return value.b;
+}
+
+static int32_t rt_pop_int(void) {
+    Value value = rt_pop();
+    if (value.tag != T_INT) {
+        rt_fail("Expected index to be an int");
+    }
+    return value.i;
+}
+
+// Integer arithmetic wraps on overflow, like the interpreter's release build
+static int32_t wrap_add(int32_t a, int32_t b) { return (int32_t)((uint32_t)a + (uint32_t)b); }
+
+static int32_t wrap_sub(int32_t a, int32_t b) { return (int32_t)((uint32_t)a - (uint32_t)b); }
+
+static int32_t wrap_mul(int32_t a, int32_t b) { return (int32_t)((uint32_t)a * (uint32_t)b); }
+
+static void rt_add(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        rt_push(rt_int(wrap_add(a.i, b.i)));
+    } else if (a.tag == T_FLOAT && b.tag == T_FLOAT) {
+        rt_push(rt_float(a.f + b.f));
+    } else if (a.tag == T_HEAP && b.tag == T_HEAP && a.h->kind == O_OBJECT &&
+               b.h->kind == O_OBJECT) {
+        if (a.h->type_id != b.h->type_id) {
+            rt_fail("Object types must match");
+        }
+        Obj *obj = rt_obj(O_OBJECT);
+        obj->type_id = a.h->type_id;
+        rt_vec_reserve(obj, a.h->len + b.h->len);
+        memcpy(obj->items, a.h->items, a.h->len * sizeof(Value));
+        memcpy(obj->items + a.h->len, b.h->items, b.h->len * sizeof(Value));
+        obj->len = a.h->len + b.h->len;
+        rt_push(rt_heap(obj));
+    } else {
+        rt_fail("Can only add numbers, strings or vectors");
+    }
+}
+
+static void rt_sub(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        rt_push(rt_int(wrap_sub(a.i, b.i)));
+    } else if (a.tag == T_FLOAT && b.tag == T_FLOAT) {
+        rt_push(rt_float(a.f - b.f));
+    } else {
+        rt_fail("Cannot 'sub' non-numbers");
+    }
+}
+
+static float as_float(Value value, const char *message) {
+    if (value.tag == T_INT) {
+        return (float)value.i;
+    }
+    if (value.tag != T_FLOAT) {
+        rt_fail(message);
+    }
+    return value.f;
+}
+
+static void rt_mul(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        rt_push(rt_int(wrap_mul(a.i, b.i)));
+    } else {
+        const char *message = "Cannot 'mul' non-numbers";
+        rt_push(rt_float(as_float(a, message) * as_float(b, message)));
+    }
+}
+
+static void rt_div(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        if (b.i == 0) {
+            rt_fail("attempt to divide by zero");
+        }
+        rt_push(rt_int(a.i == INT32_MIN && b.i == -1 ? INT32_MIN : a.i / b.i));
+    } else {
+        const char *message = "Cannot 'div' non-numbers";
+        rt_push(rt_float(as_float(a, message) / as_float(b, message)));
+    }
+}
+
+static void rt_mod(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag != T_INT || b.tag != T_INT) {
+        rt_fail("Cannot 'mod' non-int");
+    }
+    if (b.i == 0) {
+        rt_fail("attempt to calculate the remainder with a divisor of zero");
+    }
+    rt_push(rt_int(b.i == -1 ? 0 : a.i % b.i));
+}
+
+static void rt_compare(int op) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    double x, y;
+    if (a.tag == T_INT && b.tag == T_INT) {
+        x = a.i;
+        y = b.i;
+    } else if (a.tag == T_FLOAT && b.tag == T_FLOAT) {
+        x = a.f;
+        y = b.f;
+    } else {
+        rt_fail("Cannot compare non-numbers");
+        return;
+    }
+    bool res = op == 0 ? x < y : op == 1 ? x > y : op == 2 ? x <= y : x >= y;
+    rt_push(rt_bool(res));
+}
+
+static void rt_lt(void) { rt_compare(0); }
+
+static void rt_gt(void) { rt_compare(1); }
+
+static void rt_lte(void) { rt_compare(2); }
+
+static void rt_gte(void) { rt_compare(3); }
+
+static bool rt_equal(Value a, Value b) {
+    if (a.tag != b.tag) {
+        return false;
+    }
+    switch (a.tag) {
+    case T_INT:
+        return a.i == b.i;
+    case T_FLOAT:
+        return a.f == b.f;
+    case T_CHAR:
+        return a.c == b.c;
+    case T_BOOL:
+        return a.b == b.b;
+    case T_HEAP:
+        return a.h == b.h;
+    case T_NONE:
+        return true;
+    }
+    return false;
+}
+
+static void rt_eq(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    rt_push(rt_bool(rt_equal(a, b)));
+}
+
+static void rt_neq(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    rt_push(rt_bool(!rt_equal(a, b)));
+}
+
+static void rt_logic(bool and_) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag != T_BOOL || b.tag != T_BOOL) {
+        rt_fail(and_ ? "Cannot 'and' non-bools" : "Cannot 'or' non-bools");
+    }
+    rt_push(rt_bool(and_ ? a.b && b.b : a.b || b.b));
+}
+
+static void rt_and(void) { rt_logic(true); }
+
+static void rt_or(void) { rt_logic(false); }
+
+static void rt_not(void) { rt_push(rt_bool(!rt_pop_bool())); }
+
+static void rt_match(uint32_t id) {
+    Obj *obj = rt_expect_vec(rt_pop());
+    rt_push(rt_bool(obj->type_id == id));
+}
+
+static void rt_clone(void) {
+    Value value = rt_pop();
+    if (value.tag != T_HEAP) {
+        rt_push(value);
+        return;
+    }
+    Obj *obj = rt_obj(value.h->kind);
+    *obj = *value.h;
+    if (obj->items) {
+        obj->items = rt_alloc(obj->cap * sizeof(Value));
+        memcpy(obj->items, value.h->items, obj->len * sizeof(Value));
+    }
+    if (obj->text) {
+        obj->text = rt_alloc(obj->text_len + 1);
+        memcpy(obj->text, value.h->text, obj->text_len);
+    }
+    rt_push(rt_heap(obj));
+}
+
+static void rt_index(uint32_t index) {
+    Obj *obj = rt_expect_vec(rt_pop());
+    if (index >= obj->len) {
+        rt_fail("Index out of bounds");
+    }
+    rt_push(obj->items[index]);
+}
+
+static void rt_set_index(uint32_t index) {
+    Value value = rt_pop();
+    Obj *obj = rt_expect_vec(rt_pop());
+    if (index >= obj->len) {
+        rt_fail("Index out of bounds");
+    }
+    obj->items[index] = value;
+}
+
+static size_t rt_checked_index(Obj *obj, int32_t index, size_t len) {
+    if (index < 0 || (size_t)index >= len) {
+        rt_fail("Index out of bounds");
+    }
+    (void)obj;
+    return (size_t)index;
+}
+
+static void rt_vec_get(void) {
+    int32_t index = rt_pop_int();
+    Obj *obj = rt_expect_vec(rt_pop());
+    rt_push(obj->items[rt_checked_index(obj, index, obj->len)]);
+}
+
+static void rt_vec_set(void) {
+    int32_t index = rt_pop_int();
+    Value value = rt_pop();
+    Obj *obj = rt_expect_vec(rt_pop());
+    obj->items[rt_checked_index(obj, index, obj->len)] = value;
+}
+
+static void rt_vec_push(void) {
+    Value value = rt_pop();
+    Obj *obj = rt_expect_vec(rt_pop());
+    rt_vec_reserve(obj, obj->len + 1);
+    obj->items[obj->len++] = value;
+}
+
+static void rt_vec_pop(void) {
+    Obj *obj = rt_expect_vec(rt_pop());
+    if (obj->len == 0) {
+        rt_fail("Cannot pop from an empty vec");
+    }
+    rt_push(obj->items[--obj->len]);
+}
+
+static void rt_vec_peak(void) {
+    Obj *obj = rt_expect_vec(rt_pop());
+    if (obj->len == 0) {
+        rt_fail("Cannot peak an empty vec");
+    }
+    rt_push(obj->items[obj->len - 1]);
+}
+
+static void rt_vec_insert(void) {
+    int32_t index = rt_pop_int();
+    Value value = rt_pop();
+    Obj *obj = rt_expect_vec(rt_pop());
+    size_t at = rt_checked_index(obj, index, obj->len + 1);
+    rt_vec_reserve(obj, obj->len + 1);
+    memmove(obj->items + at + 1, obj->items + at, (obj->len - at) * sizeof(Value));
+    obj->items[at] = value;
+    obj->len++;
+}
+
+static void rt_vec_remove(void) {
+    int32_t index = rt_pop_int();
+    Obj *obj = rt_expect_vec(rt_pop());
+    size_t at = rt_checked_index(obj, index, obj->len);
+    Value value = obj->items[at];
+    memmove(obj->items + at, obj->items + at + 1, (obj->len - at - 1) * sizeof(Value));
+    obj->len--;
+    rt_push(value);
+}
+
+static void rt_vec_len(void) {
+    Obj *obj = rt_expect_vec(rt_pop());
+    rt_push(rt_int((int32_t)obj->len));
+}
+
+static void rt_sqrt(void) {
+    rt_push(rt_float(sqrtf(as_float(rt_pop(), "Cannot 'sqrt' non-numbers"))));
+}
+
+static void rt_pow(void) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        if (b.i < 0) {
+            rt_fail("Cannot raise an int to a negative power");
+        }
+        int32_t res = 1;
+        for (int32_t i = 0; i < b.i; i++) {
+            if (__builtin_mul_overflow(res, a.i, &res)) {
+                rt_fail("Integer overflow in 'pow'");
+            }
+        }
+        rt_push(rt_int(res));
+    } else {
+        const char *message = "Cannot 'pow' non-numbers";
+        rt_push(rt_float(powf(as_float(a, message), as_float(b, message))));
+    }
+}
+
+static void rt_abs(void) {
+    Value a = rt_pop();
+    if (a.tag == T_INT) {
+        if (a.i == INT32_MIN) {
+            rt_fail("Integer overflow in 'abs'");
+        }
+        rt_push(rt_int(a.i < 0 ? -a.i : a.i));
+    } else {
+        rt_push(rt_float(fabsf(as_float(a, "Cannot 'abs' non-numbers"))));
+    }
+}
+
+static void rt_floor(void) {
+    Value a = rt_pop();
+    if (a.tag == T_INT) {
+        rt_push(a);
+    } else {
+        rt_push(rt_float(floorf(as_float(a, "Cannot 'floor' non-numbers"))));
+    }
+}
+
+static void rt_min_max(bool min) {
+    Value b = rt_pop();
+    Value a = rt_pop();
+    if (a.tag == T_INT && b.tag == T_INT) {
+        rt_push(rt_int(min ? (a.i < b.i ? a.i : b.i) : (a.i > b.i ? a.i : b.i)));
+    } else if (a.tag == T_FLOAT && b.tag == T_FLOAT) {
+        rt_push(rt_float(min ? fminf(a.f, b.f) : fmaxf(a.f, b.f)));
+    } else {
+        rt_fail(min ? "Cannot 'min' non-numbers" : "Cannot 'max' non-numbers");
+    }
+}
+
+static void rt_min(void) { rt_min_max(true); }
+
+static void rt_max(void) { rt_min_max(false); }
+
+static void rt_to_int(void) {
+    Value a = rt_pop();
+    if (a.tag == T_INT) {
+        rt_push(a);
+    } else if (a.tag == T_CHAR) {
+        rt_push(rt_int((int32_t)a.c));
+    } else if (a.tag == T_FLOAT) {
+        // Saturates like Rust's `as`
+        float f = a.f;
+        int32_t res = isnan(f) ? 0 : f >= 2147483647.0f ? INT32_MAX : f <= -2147483648.0f ? INT32_MIN : (int32_t)f;
+        rt_push(rt_int(res));
+    } else {
+        rt_fail("Cannot convert to int");
+    }
+}
+
+static void rt_to_float(void) {
+    Value a = rt_pop();
+    if (a.tag == T_INT) {
+        rt_push(rt_float((float)a.i));
+    } else if (a.tag == T_FLOAT) {
+        rt_push(a);
+    } else {
+        rt_fail("Cannot convert to float");
+    }
+}
+
+static void rt_to_string(void) {
+    Buf buf = {0};
+    rt_text(&buf, rt_pop());
+    rt_push(rt_string(buf.data ? buf.data : "", buf.len));
+    free(buf.data);
+}
+
+static void rt_concat(uint32_t count) {
+    if (rt_sp < count) {
+        rt_fail("Stack underflow");
+    }
+    Buf buf = {0};
+    for (size_t i = rt_sp - count; i < rt_sp; i++) {
+        rt_text(&buf, rt_stack[i]);
+    }
+    rt_sp -= count;
+    rt_push(rt_string(buf.data ? buf.data : "", buf.len));
+    free(buf.data);
+}
+
+// Copies the popped string without the whitespace `str::trim` would remove
+static char *rt_pop_trimmed(void) {
+    Obj *obj = rt_expect_obj(rt_pop());
+    if (obj->kind != O_STRING) {
+        rt_fail("Expected string");
+    }
+    size_t start = 0;
+    size_t end = obj->text_len;
+    while (start < end && strchr(" \t\n\r\f\v", obj->text[start])) {
+        start++;
+    }
+    while (end > start && strchr(" \t\n\r\f\v", obj->text[end - 1])) {
+        end--;
+    }
+    char *text = rt_alloc(end - start + 1);
+    memcpy(text, obj->text + start, end - start);
+    return text;
+}
+
+static void rt_parse_int(uint32_t some, uint32_t none) {
+    char *text = rt_pop_trimmed();
+    const char *p = text;
+    bool negative = *p == '-';
+    if (*p == '-' || *p == '+') {
+        p++;
+    }
+    bool found = *p != 0;
+    int64_t value = 0;
+    for (; *p && found; p++) {
+        if (*p < '0' || *p > '9') {
+            found = false;
+            break;
+        }
+        value = value * 10 + (*p - '0');
+        if (value > (int64_t)INT32_MAX + 1) {
+            found = false;
+        }
+    }
+    value = negative ? -value : value;
+    found = found && value >= INT32_MIN && value <= INT32_MAX;
+    free(text);
+    rt_construct_option(found, rt_int((int32_t)value), some, none);
+}
+
+static void rt_parse_float(uint32_t some, uint32_t none) {
+    char *text = rt_pop_trimmed();
+    char *end = NULL;
+    float value = strtof(text, &end);
+    // Rust doesn't accept hexadecimal floats
+    bool found = *text != 0 && *end == 0 && !strpbrk(text, "xX");
+    free(text);
+    rt_construct_option(found, rt_float(value), some, none);
+}
+
+static int rt_argc;
+static char **rt_argv;
+static struct timespec rt_start;
+static uint64_t rt_rng;
+
+static void rt_init(int argc, char **argv) {
+    rt_argc = argc;
+    rt_argv = argv;
+    timespec_get(&rt_start, TIME_UTC);
+    // xorshift gets stuck at 0
+    rt_rng = ((uint64_t)rt_start.tv_sec * 1000000007u) ^ (uint64_t)rt_start.tv_nsec ^ (uintptr_t)&argc;
+    rt_rng |= 1;
+}
+
+static void rt_read_line(uint32_t some, uint32_t none) {
+    Buf buf = {0};
+    bool found = false;
+    int c;
+    while ((c = getchar()) != EOF) {
+        found = true;
+        if (c == '\n') {
+            break;
+        }
+        char byte = (char)c;
+        buf_push(&buf, &byte, 1);
+    }
+    while (buf.len > 0 && buf.data[buf.len - 1] == '\r') {
+        buf.len--;
+    }
+    Value line = found ? rt_string(buf.data ? buf.data : "", buf.len) : rt_none();
+    free(buf.data);
+    rt_construct_option(found, line, some, none);
+}
+
+static void rt_time(void) {
+    struct timespec now;
+    timespec_get(&now, TIME_UTC);
+    int64_t ms = (int64_t)(now.tv_sec - rt_start.tv_sec) * 1000 + (now.tv_nsec - rt_start.tv_nsec) / 1000000;
+    rt_push(rt_int(ms > INT32_MAX ? INT32_MAX : (int32_t)ms));
+}
+
+static void rt_random(void) {
+    rt_rng ^= rt_rng << 13;
+    rt_rng ^= rt_rng >> 7;
+    rt_rng ^= rt_rng << 17;
+    rt_push(rt_int((int32_t)(uint32_t)(rt_rng >> 32)));
+}
+
+static void rt_args(void) {
+    Obj *obj = rt_obj(O_OBJECT);
+    obj->type_id = 0;
+    rt_vec_reserve(obj, (size_t)rt_argc);
+    for (int i = 1; i < rt_argc; i++) {
+        obj->items[obj->len++] = rt_string(rt_argv[i], strlen(rt_argv[i]));
+    }
+    rt_push(rt_heap(obj));
+}
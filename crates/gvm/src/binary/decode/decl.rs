@@ -63,3 +63,33 @@ pub fn decode_file_name<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>, into: &
     }
     into.file_names.insert(id, name);
 }
+
+pub fn decode_type_name<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>, into: &mut ByteCodeFile) {
+    let id = decode_small(bytes);
+    let len = decode_small(bytes);
+    let mut name = String::new();
+    for _ in 0..len {
+        name.push(bytes.next().unwrap() as char);
+    }
+    into.type_names.insert(id, name);
+}
+
+pub fn decode_func_name<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>, into: &mut ByteCodeFile) {
+    let id = decode_small(bytes);
+    let len = decode_small(bytes);
+    let mut name = String::new();
+    for _ in 0..len {
+        name.push(bytes.next().unwrap() as char);
+    }
+    into.func_names.insert(id, name);
+}
+
+pub fn decode_table_name<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>, into: &mut ByteCodeFile) {
+    let id = decode_big(bytes);
+    let len = decode_small(bytes);
+    let mut name = String::new();
+    for _ in 0..len {
+        name.push(bytes.next().unwrap() as char);
+    }
+    into.table_names.insert(id, name);
+}
@@ -1,6 +1,10 @@
 use std::iter::Peekable;
 
-use decl::{decode_file_name, decode_func, decode_table};
+use decl::{
+    decode_file_name, decode_func, decode_func_name, decode_table, decode_table_name,
+    decode_type_name,
+};
+use util::decode_small;
 
 use crate::format::ByteCodeFile;
 
@@ -14,7 +18,11 @@ pub fn decode_file<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>) -> ByteCodeF
         match code {
             0 => decode_func(bytes, &mut file),
             1 => decode_table(bytes, &mut file),
+            48 => decode_type_name(bytes, &mut file),
             49 => decode_file_name(bytes, &mut file),
+            70 => file.entry = Some(decode_small(bytes)),
+            71 => decode_func_name(bytes, &mut file),
+            72 => decode_table_name(bytes, &mut file),
             _ => panic!("Invalid byte code header"),
         }
     }
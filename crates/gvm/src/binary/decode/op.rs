@@ -225,6 +225,118 @@ pub fn decode_code<T: Iterator<Item = u8>>(bytes: &mut Peekable<T>) -> Option<By
                 bytes.next();
                 Some(ByteCode::Mod)
             }
+            52 => {
+                bytes.next();
+                Some(ByteCode::Sqrt)
+            }
+            53 => {
+                bytes.next();
+                Some(ByteCode::Pow)
+            }
+            54 => {
+                bytes.next();
+                Some(ByteCode::Abs)
+            }
+            55 => {
+                bytes.next();
+                Some(ByteCode::Floor)
+            }
+            56 => {
+                bytes.next();
+                Some(ByteCode::Min)
+            }
+            57 => {
+                bytes.next();
+                Some(ByteCode::Max)
+            }
+            58 => {
+                bytes.next();
+                Some(ByteCode::ToInt)
+            }
+            59 => {
+                bytes.next();
+                Some(ByteCode::ToFloat)
+            }
+            60 => {
+                bytes.next();
+                Some(ByteCode::ToString)
+            }
+            61 => {
+                bytes.next();
+                let some = decode_small(bytes);
+                let none = decode_small(bytes);
+                Some(ByteCode::ParseInt { some, none })
+            }
+            62 => {
+                bytes.next();
+                let some = decode_small(bytes);
+                let none = decode_small(bytes);
+                Some(ByteCode::ParseFloat { some, none })
+            }
+            63 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::Spawn(small))
+            }
+            64 => {
+                bytes.next();
+                Some(ByteCode::Channel)
+            }
+            65 => {
+                bytes.next();
+                Some(ByteCode::Send)
+            }
+            66 => {
+                bytes.next();
+                Some(ByteCode::Receive)
+            }
+            67 => {
+                bytes.next();
+                Some(ByteCode::Generator)
+            }
+            68 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::Yield(small))
+            }
+            69 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::Resume(small))
+            }
+            73 => {
+                bytes.next();
+                let some = decode_small(bytes);
+                let none = decode_small(bytes);
+                Some(ByteCode::ReadLine { some, none })
+            }
+            74 => {
+                bytes.next();
+                Some(ByteCode::Time)
+            }
+            75 => {
+                bytes.next();
+                Some(ByteCode::Random)
+            }
+            76 => {
+                bytes.next();
+                Some(ByteCode::Args)
+            }
+            77 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::TailCall(small))
+            }
+            78 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::TailDynCall(small))
+            }
+            79 => {
+                bytes.next();
+                let small = decode_small(bytes);
+                Some(ByteCode::Concat(small))
+            }
             _ => None,
         }
     } else {
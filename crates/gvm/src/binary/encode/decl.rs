@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::format::func::FuncDef;
+use crate::format::{func::FuncDef, sorted};
 
 impl FuncDef {
     pub fn get_bytes(&self, id: u32) -> Vec<u8> {
@@ -35,13 +35,46 @@ pub fn get_table_bytes(id: u64, items: &HashMap<u32, u32>) -> Vec<u8> {
     bytes.extend_from_slice(&id.to_be_bytes());
     let len: u32 = items.len() as u32;
     bytes.extend_from_slice(&len.to_be_bytes());
-    for (k, v) in items {
+    for (k, v) in sorted(items) {
         bytes.extend_from_slice(&k.to_be_bytes());
         bytes.extend_from_slice(&v.to_be_bytes());
     }
     bytes
 }
 
+pub fn get_entry_bytes(id: u32) -> Vec<u8> {
+    let mut bytes = vec![70];
+    bytes.extend_from_slice(&id.to_be_bytes());
+    bytes
+}
+
+pub fn get_type_name_bytes(id: u32, name: &str) -> Vec<u8> {
+    let mut bytes = vec![48];
+    bytes.extend_from_slice(&id.to_be_bytes());
+    let len: u32 = name.len() as u32;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes
+}
+
+pub fn get_func_name_bytes(id: u32, name: &str) -> Vec<u8> {
+    let mut bytes = vec![71];
+    bytes.extend_from_slice(&id.to_be_bytes());
+    let len: u32 = name.len() as u32;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes
+}
+
+pub fn get_table_name_bytes(id: u64, name: &str) -> Vec<u8> {
+    let mut bytes = vec![72];
+    bytes.extend_from_slice(&id.to_be_bytes());
+    let len: u32 = name.len() as u32;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes
+}
+
 pub fn get_file_name_bytes(id: u32, name: &str) -> Vec<u8> {
     let mut bytes = vec![49];
     bytes.extend_from_slice(&id.to_be_bytes());
@@ -1,19 +1,35 @@
-use decl::{get_file_name_bytes, get_table_bytes};
+use decl::{
+    get_entry_bytes, get_file_name_bytes, get_func_name_bytes, get_table_bytes,
+    get_table_name_bytes, get_type_name_bytes,
+};
 
-use crate::format::ByteCodeFile;
+use crate::format::{sorted, ByteCodeFile};
 
 mod decl;
 mod op;
 
 pub fn encode_program(prog: &ByteCodeFile) -> Vec<u8> {
     let mut bytes = vec![];
-    for (id, name) in &prog.file_names {
+    if let Some(entry) = prog.entry {
+        bytes.extend_from_slice(&get_entry_bytes(entry));
+    }
+    // Everything is written in id order so the same program always encodes to the same bytes
+    for (id, name) in sorted(&prog.file_names) {
         bytes.extend_from_slice(&get_file_name_bytes(*id, name));
     }
-    for (id, items) in &prog.tables {
+    for (id, name) in sorted(&prog.type_names) {
+        bytes.extend_from_slice(&get_type_name_bytes(*id, name));
+    }
+    for (id, name) in sorted(&prog.func_names) {
+        bytes.extend_from_slice(&get_func_name_bytes(*id, name));
+    }
+    for (id, name) in sorted(&prog.table_names) {
+        bytes.extend_from_slice(&get_table_name_bytes(*id, name));
+    }
+    for (id, items) in sorted(&prog.tables) {
         bytes.extend_from_slice(&get_table_bytes(*id, items));
     }
-    for (id, func) in &prog.funcs {
+    for (id, func) in sorted(&prog.funcs) {
         bytes.extend_from_slice(&func.get_bytes(*id));
     }
     bytes
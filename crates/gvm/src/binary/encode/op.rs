@@ -52,6 +52,31 @@ impl ByteCode {
             },
             ByteCode::Div => 50,
             ByteCode::Mod => 51,
+            ByteCode::Sqrt => 52,
+            ByteCode::Pow => 53,
+            ByteCode::Abs => 54,
+            ByteCode::Floor => 55,
+            ByteCode::Min => 56,
+            ByteCode::Max => 57,
+            ByteCode::ToInt => 58,
+            ByteCode::ToFloat => 59,
+            ByteCode::ToString => 60,
+            ByteCode::ParseInt { .. } => 61,
+            ByteCode::ParseFloat { .. } => 62,
+            ByteCode::Spawn(_) => 63,
+            ByteCode::Channel => 64,
+            ByteCode::Send => 65,
+            ByteCode::Receive => 66,
+            ByteCode::Generator => 67,
+            ByteCode::Yield(_) => 68,
+            ByteCode::Resume(_) => 69,
+            ByteCode::ReadLine { .. } => 73,
+            ByteCode::Time => 74,
+            ByteCode::Random => 75,
+            ByteCode::Args => 76,
+            ByteCode::TailCall(_) => 77,
+            ByteCode::TailDynCall(_) => 78,
+            ByteCode::Concat(_) => 79,
         }
     }
 
@@ -63,6 +88,14 @@ impl ByteCode {
                 bytes.extend_from_slice(&len.to_be_bytes());
                 bytes
             }
+            ByteCode::ParseInt { some, none }
+            | ByteCode::ParseFloat { some, none }
+            | ByteCode::ReadLine { some, none } => {
+                let mut bytes = vec![self.get_code()];
+                bytes.extend_from_slice(&some.to_be_bytes());
+                bytes.extend_from_slice(&none.to_be_bytes());
+                bytes
+            }
             ByteCode::Copy
             | ByteCode::Pop
             | ByteCode::Print
@@ -90,6 +123,22 @@ impl ByteCode {
             | ByteCode::Gt
             | ByteCode::Lte
             | ByteCode::Gte
+            | ByteCode::Sqrt
+            | ByteCode::Pow
+            | ByteCode::Abs
+            | ByteCode::Floor
+            | ByteCode::Min
+            | ByteCode::Max
+            | ByteCode::ToInt
+            | ByteCode::ToFloat
+            | ByteCode::ToString
+            | ByteCode::Channel
+            | ByteCode::Send
+            | ByteCode::Receive
+            | ByteCode::Generator
+            | ByteCode::Time
+            | ByteCode::Random
+            | ByteCode::Args
             | ByteCode::VecLen => {
                 vec![self.get_code()]
             }
@@ -123,13 +172,19 @@ impl ByteCode {
             }
             ByteCode::Call(small)
             | ByteCode::DynCall(small)
+            | ByteCode::TailCall(small)
+            | ByteCode::TailDynCall(small)
             | ByteCode::Index(small)
+            | ByteCode::Concat(small)
             | ByteCode::Match(small)
             | ByteCode::SetIndex(small)
             | ByteCode::NewLocal(small)
             | ByteCode::GetLocal(small)
             | ByteCode::SetLocal(small)
             | ByteCode::Param(small)
+            | ByteCode::Spawn(small)
+            | ByteCode::Yield(small)
+            | ByteCode::Resume(small)
             | ByteCode::Jmp(small)
             | ByteCode::Jne(small)
             | ByteCode::Je(small) => {
@@ -0,0 +1,68 @@
+use std::{
+    env,
+    fs::File,
+    io::{Read as _, Seek as _, SeekFrom},
+};
+
+/// Ends every executable with a program appended by `bundle`
+const MAGIC: [u8; 8] = *b"gibbundl";
+
+/// The payload length (`u64`, little endian) followed by `MAGIC`
+const TRAILER_LEN: usize = 16;
+
+/// Appends an encoded program to a copy of the VM executable, so running it runs the program.
+/// Any program already bundled into `runtime` is replaced
+pub fn bundle(runtime: &[u8], program: &[u8]) -> Vec<u8> {
+    let existing = embedded(runtime).map_or(0, |old| old.len() + TRAILER_LEN);
+    let runtime = &runtime[..runtime.len() - existing];
+    let mut out = Vec::with_capacity(runtime.len() + program.len() + TRAILER_LEN);
+    out.extend_from_slice(runtime);
+    out.extend_from_slice(program);
+    out.extend_from_slice(&(program.len() as u64).to_le_bytes());
+    out.extend_from_slice(&MAGIC);
+    out
+}
+
+/// The program appended to an executable by `bundle`
+pub fn embedded(exe: &[u8]) -> Option<&[u8]> {
+    let (rest, trailer) = exe.split_at(exe.len().checked_sub(TRAILER_LEN)?);
+    let len = parse_trailer(trailer.try_into().ok()?)?;
+    rest.get(rest.len().checked_sub(usize::try_from(len).ok()?)?..)
+}
+
+/// Reads the program bundled into the running executable, without loading the rest of it
+pub fn current_program() -> Option<Vec<u8>> {
+    let mut file = File::open(env::current_exe().ok()?).ok()?;
+    let end = file.seek(SeekFrom::End(-(TRAILER_LEN as i64))).ok()?;
+    let mut trailer = [0; TRAILER_LEN];
+    file.read_exact(&mut trailer).ok()?;
+    let len = parse_trailer(&trailer)?;
+    file.seek(SeekFrom::Start(end.checked_sub(len)?)).ok()?;
+    let mut program = vec![0; usize::try_from(len).ok()?];
+    file.read_exact(&mut program).ok()?;
+    Some(program)
+}
+
+fn parse_trailer(trailer: &[u8; TRAILER_LEN]) -> Option<u64> {
+    let (len, magic) = trailer.split_at(8);
+    (magic == MAGIC).then(|| u64::from_le_bytes(len.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bundle, embedded};
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let runtime = b"\x7fELF runtime".to_vec();
+        assert_eq!(embedded(&runtime), None);
+
+        let exe = bundle(&runtime, b"program");
+        assert_eq!(embedded(&exe), Some(&b"program"[..]));
+        assert!(exe.starts_with(&runtime));
+
+        let rebundled = bundle(&exe, b"other");
+        assert_eq!(embedded(&rebundled), Some(&b"other"[..]));
+        assert_eq!(rebundled.len(), runtime.len() + 5 + 16);
+    }
+}
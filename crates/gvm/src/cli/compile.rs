@@ -0,0 +1,49 @@
+use std::{fs, path::PathBuf, process::exit};
+
+use clap::{Args, ValueEnum};
+
+use crate::{
+    aot::{c, compile_c, CompileError},
+    binary::decode::decode_file,
+};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Target {
+    /// Portable C, built with the system C compiler
+    C,
+}
+
+// Compile a bytecode file ahead of time to a native executable
+#[derive(Args)]
+pub struct CompileCommand {
+    /// The bytecode file to compile
+    pub input: PathBuf,
+
+    /// The language to compile through
+    #[clap(long, value_enum, default_value = "c")]
+    pub target: Target,
+
+    /// The file to write the executable to
+    #[clap(short, long)]
+    pub output: PathBuf,
+
+    /// Write the generated source instead of building an executable
+    #[clap(long)]
+    pub emit_source: bool,
+}
+
+impl CompileCommand {
+    pub fn run(&self) {
+        let bytes = fs::read(&self.input).unwrap();
+        let bytecode = decode_file(&mut bytes.into_iter().peekable());
+        let res = match self.target {
+            Target::C if self.emit_source => c::generate(&bytecode)
+                .and_then(|source| fs::write(&self.output, source).map_err(CompileError::from)),
+            Target::C => compile_c(&bytecode, &self.output),
+        };
+        if let Err(err) = res {
+            eprintln!("error: {err}");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::{stdin, stdout, Write as _},
+    mem::take,
+    path::PathBuf,
+};
+
+use clap::Args;
+
+use crate::{
+    binary::decode::decode_file,
+    format::{span::ByteCodeSpan, ByteCodeFile},
+    vm::{state::ProgramState, text::DebugText as _, trace::Inputs},
+};
+
+const HELP: &str = "\
+Commands:
+  break <file>:<line>  Pause when execution reaches a line (as shown by 'bt')
+  break <func>         Pause when a function is called
+  step                 Run until the next source position, entering calls
+  next                 Run until the next source position in this function
+  finish               Run until the current function returns
+  continue             Run until a breakpoint is hit or the program ends
+  back                 Return to where the previous command started
+  goto <step>          Run until the given number of instructions have executed
+  bt                   Print the call stack
+  locals               Print the locals of the current function
+  stack                Print the operand stack of the current function
+  print <local>        Print a single local
+  disasm               Print the instructions of the current function
+  quit                 Stop debugging";
+
+#[derive(Args)]
+pub struct DebugCommand {
+    /// The bytecode file to debug
+    path: PathBuf,
+
+    /// Arguments passed to the program
+    #[clap(last = true)]
+    args: Vec<String>,
+}
+
+impl DebugCommand {
+    pub fn run(&self) {
+        let bytes = fs::read(&self.path).unwrap();
+        let bytecode = decode_file(&mut bytes.into_iter().peekable());
+        Debugger::new(&bytecode, Inputs::new(self.args.clone())).repl();
+    }
+}
+
+enum Stop {
+    Breakpoint,
+    Finished,
+    Done,
+}
+
+/// Going backwards re-runs the program from the start up to an earlier step,
+/// replaying the inputs it has read so far so it takes the same path
+pub struct Debugger<'code> {
+    bytecode: &'code ByteCodeFile,
+    state: ProgramState<'code>,
+    breakpoints: HashSet<(u32, usize)>,
+    /// The number of instructions executed so far
+    steps: u64,
+    /// The step each earlier command started from
+    history: Vec<u64>,
+}
+
+impl<'code> Debugger<'code> {
+    pub fn new(bytecode: &'code ByteCodeFile, inputs: Inputs) -> Self {
+        Self {
+            bytecode,
+            state: Self::start(bytecode, inputs),
+            breakpoints: HashSet::new(),
+            steps: 0,
+            history: vec![],
+        }
+    }
+
+    fn start(bytecode: &'code ByteCodeFile, inputs: Inputs) -> ProgramState<'code> {
+        let mut state = ProgramState::new(
+            &bytecode.funcs,
+            bytecode.entry,
+            bytecode.tables.clone(),
+            bytecode.file_names.clone(),
+            bytecode.type_names.clone(),
+        );
+        state.inputs = inputs;
+        state.scopes.push(state.entry_scope());
+        state
+    }
+
+    pub fn repl(&mut self) {
+        println!("{}", self.location());
+        let mut last = String::new();
+        loop {
+            print!("(gvm) ");
+            stdout().flush().unwrap();
+            // Not holding on to stdin, as the program may read from it too
+            let mut line = String::new();
+            if stdin().read_line(&mut line).unwrap_or(0) == 0 {
+                break;
+            }
+            let line = if line.trim().is_empty() {
+                last.clone()
+            } else {
+                line.trim().to_string()
+            };
+            let mut words = line.split_whitespace();
+            let Some(cmd) = words.next() else {
+                continue;
+            };
+            let arg = words.next();
+            let start = self.steps;
+            let stop = match (cmd, arg) {
+                ("b" | "break", Some(target)) => {
+                    self.add_breakpoint(target);
+                    None
+                }
+                ("s" | "step", _) => Some(self.step()),
+                ("n" | "next", _) => Some(self.next()),
+                ("f" | "finish", _) => Some(self.finish()),
+                ("c" | "continue", _) => Some(self.run_until(|_| false)),
+                ("back", _) => {
+                    if let Some(step) = self.history.pop() {
+                        self.rewind(step);
+                        println!("{}", self.location());
+                    } else {
+                        println!("Already at the start of the program");
+                    }
+                    None
+                }
+                ("goto", Some(step)) => {
+                    let Ok(step) = step.parse::<u64>() else {
+                        println!("Invalid step '{step}'");
+                        continue;
+                    };
+                    if step < self.steps {
+                        self.rewind(step);
+                        Some(Stop::Done)
+                    } else {
+                        Some(self.run_until(|dbg| dbg.steps >= step))
+                    }
+                }
+                ("bt" | "backtrace", _) => {
+                    println!("{}", self.state.stack_trace());
+                    None
+                }
+                ("locals", _) => {
+                    self.print_locals();
+                    None
+                }
+                ("stack", _) => {
+                    self.print_stack();
+                    None
+                }
+                ("p" | "print", Some(local)) => {
+                    self.print_local(local);
+                    None
+                }
+                ("disasm", _) => {
+                    self.disasm();
+                    None
+                }
+                ("q" | "quit", _) => break,
+                _ => {
+                    println!("{HELP}");
+                    None
+                }
+            };
+            last = line;
+            if stop.is_some() {
+                self.history.push(start);
+            }
+            match stop {
+                Some(Stop::Finished) => {
+                    println!("Program finished");
+                    break;
+                }
+                Some(Stop::Breakpoint) => println!("Breakpoint hit at {}", self.location()),
+                Some(Stop::Done) => println!("{}", self.location()),
+                None => {}
+            }
+        }
+    }
+
+    fn add_breakpoint(&mut self, target: &str) {
+        let found = if let Some((file, line)) = target.rsplit_once(':') {
+            let Ok(line) = line.parse::<u16>() else {
+                println!("Invalid line number '{line}'");
+                return;
+            };
+            self.line_breakpoints(file, line)
+        } else {
+            self.state
+                .funcs
+                .iter()
+                .filter(|(_, func)| func.name == target)
+                .map(|(id, _)| (*id, 0))
+                .collect()
+        };
+        if found.is_empty() {
+            println!("No code found for '{target}'");
+            return;
+        }
+        for point in found {
+            self.breakpoints.insert(point);
+        }
+        println!("Breakpoint set at {target}");
+    }
+
+    fn line_breakpoints(&self, file: &str, line: u16) -> Vec<(u32, usize)> {
+        let Some(file_id) = self
+            .state
+            .file_names
+            .iter()
+            .find_map(|(id, name)| (name == file || name.ends_with(file)).then_some(*id))
+        else {
+            return vec![];
+        };
+        self.state
+            .funcs
+            .iter()
+            .filter(|(_, func)| func.file == file_id)
+            .filter_map(|(id, func)| {
+                func.marks
+                    .iter()
+                    .find(|(_, pos)| pos.0 == line)
+                    .map(|(index, _)| (*id, *index))
+            })
+            .collect()
+    }
+
+    fn step(&mut self) -> Stop {
+        let depth = self.state.scopes.len();
+        let pos = self.pos();
+        self.run_until(|dbg| dbg.state.scopes.len() != depth || dbg.pos() != pos)
+    }
+
+    fn next(&mut self) -> Stop {
+        let depth = self.state.scopes.len();
+        let pos = self.pos();
+        self.run_until(|dbg| {
+            let current = dbg.state.scopes.len();
+            current < depth || (current == depth && dbg.pos() != pos)
+        })
+    }
+
+    fn finish(&mut self) -> Stop {
+        let depth = self.state.scopes.len();
+        self.run_until(|dbg| dbg.state.scopes.len() < depth)
+    }
+
+    fn run_until(&mut self, done: impl Fn(&Self) -> bool) -> Stop {
+        loop {
+            if !self.step_instr() {
+                return Stop::Finished;
+            }
+            let scope = self.state.scope();
+            if self.breakpoints.contains(&(scope.id, scope.index)) {
+                return Stop::Breakpoint;
+            }
+            if done(self) {
+                return Stop::Done;
+            }
+        }
+    }
+
+    /// Executes a single instruction, returning false once the program has finished
+    fn step_instr(&mut self) -> bool {
+        let instr = self.state.next_instr();
+        self.state.execute(instr);
+        self.steps += 1;
+        self.state.can_continue() || self.state.schedule()
+    }
+
+    /// Re-runs the program up to `step`, without printing its output again
+    fn rewind(&mut self, step: u64) {
+        let old = &mut self.state.inputs;
+        let mut inputs = Inputs::new(take(&mut old.args));
+        let strict = old.strict;
+        inputs.replay(
+            take(&mut old.history)
+                .into_iter()
+                .chain(take(&mut old.replay)),
+            strict,
+        );
+        self.state = Self::start(self.bytecode, inputs);
+        self.steps = 0;
+        self.state.muted = true;
+        while self.steps < step && self.step_instr() {}
+        self.state.muted = false;
+    }
+
+    fn pos(&self) -> ByteCodeSpan {
+        self.state.scope_pos(self.state.scope())
+    }
+
+    fn location(&self) -> String {
+        let scope = self.state.scope();
+        let func = &self.state.funcs[&scope.id];
+        let file = self
+            .state
+            .file_names
+            .get(&func.file)
+            .map_or("<unknown>", String::as_str);
+        let pos = self.pos();
+        format!(
+            "{file}:{}:{} ({}) [step {}]",
+            pos.0, pos.1, func.name, self.steps
+        )
+    }
+
+    fn print_locals(&self) {
+        let scope = self.state.scope();
+        for (id, arg) in scope.args.iter().enumerate() {
+            println!("  param {id} = {}", arg.get_text(&self.state));
+        }
+        let mut locals = scope.locals.iter().collect::<Vec<_>>();
+        locals.sort_by_key(|(id, _)| **id);
+        for (id, local) in locals {
+            println!("  {id} = {}", local.get_text(&self.state));
+        }
+    }
+
+    fn print_stack(&self) {
+        for (i, item) in self.state.scope().stack.iter().enumerate().rev() {
+            println!("  [{i}] {}", item.get_text(&self.state));
+        }
+    }
+
+    fn print_local(&self, local: &str) {
+        let Ok(id) = local.parse::<u32>() else {
+            println!("Locals are referred to by id, see 'locals'");
+            return;
+        };
+        match self.state.scope().locals.get(&id) {
+            Some(item) => println!("  {id} = {}", item.get_text(&self.state)),
+            None => println!("No local with id {id}"),
+        }
+    }
+
+    fn disasm(&self) {
+        let scope = self.state.scope();
+        let func = &self.state.funcs[&scope.id];
+        println!("func {} ({})", scope.id, func.name);
+        for (i, instr) in func.body.iter().enumerate() {
+            let marker = if i == scope.index { "->" } else { "  " };
+            let bp = if self.breakpoints.contains(&(scope.id, i)) {
+                "*"
+            } else {
+                " "
+            };
+            println!("{bp}{marker} {i:>4} {instr}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        format::ByteCodeFile,
+        text::decode::parser::parse_text_file,
+        vm::{stack::StackItem, trace::Inputs},
+    };
+
+    use super::{Debugger, Stop};
+
+    const PROGRAM: &str = r#"
+        file 0 "main.gib"
+        entry 0
+        func 0 0 "main" 1 1 0
+        push 1 new 0 push 2 call 1 new 1 push 0 return
+        mark 0 2 5
+        mark 2 3 5
+        mark 5 4 5
+
+        func 1 1 "double" 10 1 0
+        param 0 push 2 mul return
+        mark 0 11 5
+    "#;
+
+    fn load() -> ByteCodeFile {
+        parse_text_file(PROGRAM).unwrap()
+    }
+
+    #[test]
+    fn test_step_enters_calls() {
+        let file = load();
+        let mut dbg = Debugger::new(&file, Inputs::new(vec![]));
+        assert!(matches!(dbg.step(), Stop::Done));
+        assert_eq!(dbg.location(), "main.gib:3:5 (main) [step 3]");
+        assert!(matches!(dbg.step(), Stop::Done));
+        assert_eq!(dbg.location(), "main.gib:11:5 (double) [step 4]");
+        assert!(matches!(dbg.finish(), Stop::Done));
+        assert_eq!(dbg.location(), "main.gib:3:5 (main) [step 8]");
+        assert_eq!(dbg.state.scope().stack, vec![StackItem::Int(4)]);
+    }
+
+    #[test]
+    fn test_next_steps_over_calls() {
+        let file = load();
+        let mut dbg = Debugger::new(&file, Inputs::new(vec![]));
+        dbg.step();
+        assert!(matches!(dbg.next(), Stop::Done));
+        assert_eq!(dbg.location(), "main.gib:4:5 (main) [step 10]");
+        assert_eq!(dbg.state.scope().locals[&1], StackItem::Int(4));
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let file = load();
+        let mut dbg = Debugger::new(&file, Inputs::new(vec![]));
+        dbg.add_breakpoint("double");
+        dbg.add_breakpoint("main.gib:4");
+        dbg.add_breakpoint("main.gib:99");
+        assert_eq!(dbg.breakpoints.len(), 2);
+        assert!(matches!(dbg.run_until(|_| false), Stop::Breakpoint));
+        assert_eq!(dbg.location(), "main.gib:11:5 (double) [step 4]");
+        assert!(matches!(dbg.run_until(|_| false), Stop::Breakpoint));
+        assert_eq!(dbg.state.scope().index, 5);
+        assert_eq!(dbg.steps, 9);
+        assert!(matches!(dbg.run_until(|_| false), Stop::Finished));
+    }
+
+    #[test]
+    fn test_rewind() {
+        let file = load();
+        let mut dbg = Debugger::new(&file, Inputs::new(vec![]));
+        dbg.run_until(|dbg| dbg.steps >= 9);
+        assert_eq!(dbg.state.scope().locals[&1], StackItem::Int(4));
+        dbg.rewind(4);
+        assert_eq!(dbg.location(), "main.gib:11:5 (double) [step 4]");
+        assert!(!dbg.state.scope().locals.contains_key(&1));
+        assert!(!dbg.state.muted);
+        dbg.rewind(0);
+        assert_eq!(dbg.location(), "main.gib:2:5 (main) [step 0]");
+    }
+}
@@ -0,0 +1,45 @@
+use std::{fs, path::PathBuf, process::exit};
+
+use clap::Args;
+
+use crate::{
+    binary::{decode::decode_file, encode::encode_program},
+    link::{link, Unit},
+};
+
+// Combine separately compiled bytecode files into one program
+#[derive(Args)]
+pub struct LinkCommand {
+    /// The binary files to link
+    #[clap(required = true)]
+    pub inputs: Vec<PathBuf>,
+
+    /// The file to write the linked program to
+    #[clap(short, long)]
+    pub output: PathBuf,
+}
+
+impl LinkCommand {
+    pub fn run(&self) {
+        let units = self
+            .inputs
+            .iter()
+            .map(|path| {
+                let bytes = fs::read(path).unwrap();
+                Unit {
+                    name: path.to_string_lossy().to_string(),
+                    file: decode_file(&mut bytes.into_iter().peekable()),
+                }
+            })
+            .collect();
+        match link(units) {
+            Ok(program) => fs::write(&self.output, encode_program(&program)).unwrap(),
+            Err(errors) => {
+                for error in &errors {
+                    eprintln!("error: {error}");
+                }
+                exit(1);
+            }
+        }
+    }
+}
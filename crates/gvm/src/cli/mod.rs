@@ -1,10 +1,20 @@
 use clap::Parser;
+use compile::CompileCommand;
+use debug::DebugCommand;
 use decode::Decode;
 use encode::Encode;
+use link::LinkCommand;
+use replay::ReplayCommand;
 use run::RunCommand;
 
+pub use run::run_bundled;
+
+mod compile;
+mod debug;
 mod decode;
 mod encode;
+mod link;
+mod replay;
 mod run;
 
 /// Giblang Virtual Machine
@@ -14,19 +24,35 @@ pub enum Command {
     /// Launch the VM
     Run(RunCommand),
 
+    /// Step through a program interactively
+    Debug(DebugCommand),
+
     /// Convert from the text format to the binary format
     Encode(Encode),
 
     /// Convert from the binary format to the text format
     Decode(Decode),
+
+    /// Combine separately compiled bytecode files into one program
+    Link(LinkCommand),
+
+    /// Compile a program ahead of time to a native executable
+    Compile(CompileCommand),
+
+    /// Re-run a program recorded with `gvm run --record`, feeding it the same inputs
+    Replay(ReplayCommand),
 }
 
 impl Command {
     pub fn run(&self) {
         match self {
             Command::Run(cmd) => cmd.run(),
+            Command::Debug(cmd) => cmd.run(),
             Command::Encode(cmd) => cmd.run(),
             Command::Decode(cmd) => cmd.run(),
+            Command::Link(cmd) => cmd.run(),
+            Command::Compile(cmd) => cmd.run(),
+            Command::Replay(cmd) => cmd.run(),
         }
     }
 }
@@ -0,0 +1,52 @@
+use std::{fs, path::PathBuf, process::exit};
+
+use clap::Args;
+
+use crate::{
+    binary::decode::decode_file,
+    vm::{
+        state::ProgramState,
+        trace::{decode_trace, Inputs},
+    },
+};
+
+use super::{debug::Debugger, run::run_to_end};
+
+#[derive(Args)]
+pub struct ReplayCommand {
+    /// The trace file written by `gvm run --record`
+    trace: PathBuf,
+
+    /// Step through the run in the debugger, which can also step backwards
+    #[clap(short, long)]
+    interactive: bool,
+}
+
+impl ReplayCommand {
+    pub fn run(&self) {
+        let bytes = fs::read(&self.trace).unwrap();
+        let (program, recorded) = match decode_trace(&bytes) {
+            Ok(trace) => trace,
+            Err(err) => {
+                eprintln!("error: {err}");
+                exit(1);
+            }
+        };
+        let bytecode = decode_file(&mut program.iter().copied().peekable());
+        let mut inputs = Inputs::default();
+        inputs.replay(recorded, true);
+        if self.interactive {
+            Debugger::new(&bytecode, inputs).repl();
+            return;
+        }
+        let mut prog = ProgramState::new(
+            &bytecode.funcs,
+            bytecode.entry,
+            bytecode.tables.clone(),
+            bytecode.file_names.clone(),
+            bytecode.type_names.clone(),
+        );
+        prog.inputs = inputs;
+        run_to_end(&mut prog, false);
+    }
+}
@@ -1,21 +1,57 @@
 use std::{
+    env,
     fs::{self},
     io::{stdin, Read},
+    panic::{catch_unwind, AssertUnwindSafe},
     path::PathBuf,
+    process::exit,
 };
 
 use clap::Args;
 
-use crate::{binary::decode::decode_file, vm::state::ProgramState};
+use crate::{
+    binary::decode::decode_file,
+    vm::{
+        jit::{Jit, HOT_CALLS},
+        state::ProgramState,
+    },
+};
 
 #[derive(Args)]
 pub struct RunCommand {
     /// The bytecode file to run (if not provided stdin will be used)
     path: Option<PathBuf>,
 
+    #[command(flatten)]
+    options: RunOptions,
+
+    /// Arguments passed to the program
+    #[clap(last = true)]
+    args: Vec<String>,
+}
+
+/// How to run a program, shared by `gvm run` and bundled executables
+#[derive(Args)]
+pub struct RunOptions {
     /// Run in debug
     #[clap(short, long)]
     debug: bool,
+
+    /// Write the call stack and reachable heap as JSON to this file if the program panics
+    #[clap(long, value_name = "FILE")]
+    dump_on_panic: Option<PathBuf>,
+
+    /// Write a Graphviz rendering of the reachable heap to this file if the program panics
+    #[clap(long, value_name = "FILE")]
+    dump_graph: Option<PathBuf>,
+
+    /// Compile frequently called functions to native code
+    #[clap(long)]
+    jit: bool,
+
+    /// Record every input the program reads to this file, to be replayed with `gvm replay`
+    #[clap(long, value_name = "FILE")]
+    record: Option<PathBuf>,
 }
 
 impl RunCommand {
@@ -27,12 +63,66 @@ impl RunCommand {
             stdin().read_to_end(&mut bytes).unwrap();
             bytes
         };
-        let bytecode = decode_file(&mut bytes.into_iter().peekable());
-        let mut prog = ProgramState::new(&bytecode.funcs, bytecode.tables, bytecode.file_names);
-        if self.debug {
+        self.options.run(bytes, self.args.clone());
+    }
+}
+
+/// Runs the program bundled into this executable by `gibc build --bundle`.
+///
+/// Every argument is passed to the program, so the VM is set up with `GVM_*` variables instead
+pub fn run_bundled(program: Vec<u8>) {
+    let options = RunOptions {
+        debug: env::var_os("GVM_DEBUG").is_some(),
+        dump_on_panic: env::var_os("GVM_DUMP_ON_PANIC").map(PathBuf::from),
+        dump_graph: env::var_os("GVM_DUMP_GRAPH").map(PathBuf::from),
+        jit: env::var_os("GVM_JIT").is_some(),
+        record: env::var_os("GVM_RECORD").map(PathBuf::from),
+    };
+    options.run(program, env::args().skip(1).collect());
+}
+
+impl RunOptions {
+    pub fn run(&self, bytes: Vec<u8>, args: Vec<String>) {
+        let bytecode = decode_file(&mut bytes.iter().copied().peekable());
+        let mut prog = ProgramState::new(
+            &bytecode.funcs,
+            bytecode.entry,
+            bytecode.tables,
+            bytecode.file_names,
+            bytecode.type_names,
+        );
+        prog.dump.json.clone_from(&self.dump_on_panic);
+        prog.dump.dot.clone_from(&self.dump_graph);
+        prog.inputs.args = args;
+        if let Some(path) = &self.record {
+            if let Err(err) = prog.inputs.record(path, &bytes) {
+                eprintln!("Could not create {}: {err}", path.display());
+                exit(1);
+            }
+        }
+        if self.jit {
+            prog.jit = Jit::new(HOT_CALLS);
+            if prog.jit.is_none() {
+                eprintln!(
+                    "warning: the JIT doesn't support this machine, falling back to the interpreter"
+                );
+            }
+        }
+        run_to_end(&mut prog, self.debug);
+    }
+}
+
+/// Runs a program, writing any requested dumps and exiting if it panics
+pub fn run_to_end(prog: &mut ProgramState, debug: bool) {
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        if debug {
             prog.run_debug();
         } else {
             prog.run();
         };
+    }));
+    if res.is_err() {
+        prog.dump_snapshot();
+        exit(101);
     }
 }
@@ -1,5 +1,6 @@
 use super::{instr::ByteCode, span::ByteCodeSpan};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct FuncDef {
     pub name: String,
     pub args: u32,
@@ -1,6 +1,6 @@
 use super::literal::Literal;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ByteCode {
     Push(Literal),
     Copy,
@@ -11,6 +11,8 @@ pub enum ByteCode {
     Dyn(u64),
     Call(u32),
     DynCall(u32),
+    TailCall(u32),
+    TailDynCall(u32),
     Return,
     Index(u32),
     SetIndex(u32),
@@ -51,4 +53,31 @@ pub enum ByteCode {
     Not,
     Match(u32),
     Clone,
+
+    Sqrt,
+    Pow,
+    Abs,
+    Floor,
+    Min,
+    Max,
+    ToInt,
+    ToFloat,
+    ToString,
+    Concat(u32),
+    ParseInt { some: u32, none: u32 },
+    ParseFloat { some: u32, none: u32 },
+
+    Spawn(u32),
+    Channel,
+    Send,
+    Receive,
+
+    Generator,
+    Yield(u32),
+    Resume(u32),
+
+    ReadLine { some: u32, none: u32 },
+    Time,
+    Random,
+    Args,
 }
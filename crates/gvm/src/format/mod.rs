@@ -9,9 +9,23 @@ pub mod literal;
 pub mod span;
 pub mod table;
 
+/// Iterates a map in key order, for output that doesn't depend on hash order
+pub fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut items = map.iter().collect::<Vec<_>>();
+    items.sort_by(|a, b| a.0.cmp(b.0));
+    items
+}
+
 #[derive(Default)]
 pub struct ByteCodeFile {
+    /// The function execution starts at
+    pub entry: Option<u32>,
     pub funcs: HashMap<u32, FuncDef>,
     pub tables: HashMap<u64, VTable>,
     pub file_names: HashMap<u32, String>,
+    pub type_names: HashMap<u32, String>,
+    /// Qualified names of the functions this file defines or refers to, used for linking
+    pub func_names: HashMap<u32, String>,
+    /// Names of the types each vtable was built for, used for linking
+    pub table_names: HashMap<u64, String>,
 }
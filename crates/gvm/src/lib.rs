@@ -1,4 +1,7 @@
+pub mod aot;
 pub mod binary;
+pub mod bundle;
 pub mod format;
+pub mod link;
 pub mod text;
 pub mod vm;
@@ -0,0 +1,430 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    hash::Hash,
+};
+
+use thiserror::Error;
+
+use crate::format::{instr::ByteCode, ByteCodeFile};
+
+/// A separately compiled file, along with the name used to refer to it in errors
+pub struct Unit {
+    pub name: String,
+    pub file: ByteCodeFile,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LinkError {
+    #[error("'{symbol}' is defined differently in {first} and {second}")]
+    Duplicate {
+        symbol: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("'{symbol}' is used by {unit} but not defined in any module")]
+    Missing { symbol: String, unit: String },
+
+    #[error("Function {id} in {unit} has no entry in the symbol table")]
+    Unnamed { id: u32, unit: String },
+
+    #[error("'{symbol}' is the name of more than one type in {unit}")]
+    DuplicateType { symbol: String, unit: String },
+
+    #[error("Both {first} and {second} define an entry point")]
+    DuplicateEntry { first: String, second: String },
+}
+
+/// Maps the ids of a single unit to the ids used in the linked output
+struct Relocation {
+    funcs: HashMap<u32, u32>,
+    types: HashMap<u32, u32>,
+    tables: HashMap<u64, u64>,
+    files: HashMap<u32, u32>,
+}
+
+impl Relocation {
+    fn func(&self, id: u32) -> u32 {
+        self.funcs[&id]
+    }
+
+    /// Types without a name (such as builtin objects) keep their id
+    fn ty(&self, id: u32) -> u32 {
+        self.types.get(&id).copied().unwrap_or(id)
+    }
+
+    fn table(&self, id: u64) -> u64 {
+        self.tables.get(&id).copied().unwrap_or(id)
+    }
+
+    fn instr(&self, instr: &mut ByteCode) {
+        match instr {
+            ByteCode::Call(id)
+            | ByteCode::DynCall(id)
+            | ByteCode::TailCall(id)
+            | ByteCode::TailDynCall(id)
+            | ByteCode::Spawn(id) => {
+                *id = self.func(*id);
+            }
+            ByteCode::Construct { id, .. }
+            | ByteCode::Match(id)
+            | ByteCode::Yield(id)
+            | ByteCode::Resume(id) => *id = self.ty(*id),
+            ByteCode::ParseInt { some, none }
+            | ByteCode::ParseFloat { some, none }
+            | ByteCode::ReadLine { some, none } => {
+                *some = self.ty(*some);
+                *none = self.ty(*none);
+            }
+            ByteCode::Dyn(id) => *id = self.table(*id),
+            _ => {}
+        }
+    }
+}
+
+/// Gives every name a new id counting up from `first`,
+/// in name order so the output doesn't depend on input order
+fn assign_ids<'a>(
+    names: impl Iterator<Item = &'a String>,
+    first: usize,
+) -> HashMap<&'a String, usize> {
+    names
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .enumerate()
+        .map(|(id, name)| (name, first + id))
+        .collect()
+}
+
+fn relocate_names<K: Copy + Eq + Hash + TryFrom<usize>>(
+    names: &HashMap<K, String>,
+    ids: &HashMap<&String, usize>,
+) -> HashMap<K, K> {
+    names
+        .iter()
+        .map(|(id, name)| {
+            let new = K::try_from(ids[name]).unwrap_or_else(|_| panic!("Too many symbols"));
+            (*id, new)
+        })
+        .collect()
+}
+
+/// The ids of every function a unit calls, including through its vtables
+fn func_refs(file: &ByteCodeFile) -> BTreeSet<u32> {
+    let mut refs = file
+        .funcs
+        .values()
+        .flat_map(|func| &func.body)
+        .filter_map(|instr| match instr {
+            ByteCode::Call(id)
+            | ByteCode::DynCall(id)
+            | ByteCode::TailCall(id)
+            | ByteCode::TailDynCall(id)
+            | ByteCode::Spawn(id) => Some(*id),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    for table in file.tables.values() {
+        refs.extend(table.keys());
+        refs.extend(table.values());
+    }
+    refs.extend(file.entry);
+    refs
+}
+
+/// Combines separately compiled files into one program.
+///
+/// Functions, types, vtables and files are matched up by name and renumbered.
+/// A function defined in more than one unit is only an error if the definitions differ,
+/// so code every unit was compiled with (such as `std`) is shared
+pub fn link(mut units: Vec<Unit>) -> Result<ByteCodeFile, Vec<LinkError>> {
+    let mut errors = vec![];
+    let names = units
+        .iter()
+        .map(|unit| unit.name.clone())
+        .collect::<Vec<_>>();
+    let refs = units
+        .iter()
+        .map(|unit| func_refs(&unit.file))
+        .collect::<Vec<_>>();
+    for (unit, refs) in units.iter().zip(&refs) {
+        let ids = refs
+            .iter()
+            .chain(unit.file.funcs.keys())
+            .collect::<BTreeSet<_>>();
+        for id in ids {
+            if !unit.file.func_names.contains_key(id) {
+                errors.push(LinkError::Unnamed {
+                    id: *id,
+                    unit: unit.name.clone(),
+                });
+            }
+        }
+        let mut types = BTreeMap::<&String, usize>::new();
+        for name in unit.file.type_names.values() {
+            *types.entry(name).or_default() += 1;
+        }
+        for (symbol, count) in types {
+            if count > 1 {
+                errors.push(LinkError::DuplicateType {
+                    symbol: symbol.clone(),
+                    unit: unit.name.clone(),
+                });
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let func_ids = assign_ids(
+        units.iter().flat_map(|unit| unit.file.func_names.values()),
+        0,
+    );
+    // Type 0 is left for `Vec`, which is constructed without a name
+    let type_ids = assign_ids(
+        units.iter().flat_map(|unit| unit.file.type_names.values()),
+        1,
+    );
+    let table_ids = assign_ids(
+        units.iter().flat_map(|unit| unit.file.table_names.values()),
+        0,
+    );
+    let file_ids = assign_ids(
+        units.iter().flat_map(|unit| unit.file.file_names.values()),
+        0,
+    );
+    let relocations = units
+        .iter()
+        .map(|unit| Relocation {
+            funcs: relocate_names(&unit.file.func_names, &func_ids),
+            types: relocate_names(&unit.file.type_names, &type_ids),
+            tables: relocate_names(&unit.file.table_names, &table_ids),
+            files: relocate_names(&unit.file.file_names, &file_ids),
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = ByteCodeFile::default();
+    let mut defined_in: HashMap<u32, usize> = HashMap::new();
+    let mut entry_in: Option<usize> = None;
+    for (index, (unit, relocation)) in units.iter_mut().zip(&relocations).enumerate() {
+        let file = &mut unit.file;
+        for (id, name) in file.func_names.drain() {
+            out.func_names.insert(relocation.func(id), name);
+        }
+        for (id, name) in file.type_names.drain() {
+            out.type_names.insert(relocation.ty(id), name);
+        }
+        for (id, name) in file.table_names.drain() {
+            out.table_names.insert(relocation.table(id), name);
+        }
+        for (id, name) in file.file_names.drain() {
+            out.file_names.insert(relocation.files[&id], name);
+        }
+        for (id, table) in file.tables.drain() {
+            let table = table
+                .into_iter()
+                .map(|(trait_func, impl_func)| {
+                    (relocation.func(trait_func), relocation.func(impl_func))
+                })
+                .collect();
+            out.tables.insert(relocation.table(id), table);
+        }
+        if let Some(entry) = file.entry {
+            if let Some(first) = entry_in {
+                errors.push(LinkError::DuplicateEntry {
+                    first: names[first].clone(),
+                    second: names[index].clone(),
+                });
+            } else {
+                entry_in = Some(index);
+                out.entry = Some(relocation.func(entry));
+            }
+        }
+        for (id, mut func) in file.funcs.drain() {
+            let id = relocation.func(id);
+            for instr in &mut func.body {
+                relocation.instr(instr);
+            }
+            func.file = relocation
+                .files
+                .get(&func.file)
+                .copied()
+                .unwrap_or(func.file);
+            match out.funcs.get(&id) {
+                Some(existing) if *existing != func => errors.push(LinkError::Duplicate {
+                    symbol: out.func_names[&id].clone(),
+                    first: names[defined_in[&id]].clone(),
+                    second: names[index].clone(),
+                }),
+                Some(_) => {}
+                None => {
+                    defined_in.insert(id, index);
+                    out.funcs.insert(id, func);
+                }
+            }
+        }
+    }
+
+    for ((refs, relocation), unit) in refs.iter().zip(&relocations).zip(&names) {
+        for id in refs {
+            let id = relocation.func(*id);
+            if !out.funcs.contains_key(&id) {
+                errors.push(LinkError::Missing {
+                    symbol: out.func_names[&id].clone(),
+                    unit: unit.clone(),
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(out)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::format::{func::FuncDef, instr::ByteCode, ByteCodeFile};
+
+    use super::{link, LinkError, Unit};
+
+    fn func(name: &str, body: Vec<ByteCode>) -> FuncDef {
+        FuncDef {
+            name: name.to_string(),
+            args: 0,
+            pos: (0, 0),
+            file: 0,
+            body,
+            marks: vec![],
+        }
+    }
+
+    fn unit(name: &str, funcs: Vec<(u32, &str, FuncDef)>, refs: Vec<(u32, &str)>) -> Unit {
+        let mut file = ByteCodeFile::default();
+        for (id, symbol, def) in funcs {
+            file.func_names.insert(id, symbol.to_string());
+            file.funcs.insert(id, def);
+        }
+        for (id, symbol) in refs {
+            file.func_names.insert(id, symbol.to_string());
+        }
+        Unit {
+            name: name.to_string(),
+            file,
+        }
+    }
+
+    #[test]
+    fn test_link_resolves_calls_by_name() {
+        let mut main = unit(
+            "main.gbc",
+            vec![(
+                7,
+                "main::main",
+                func("main", vec![ByteCode::Call(3), ByteCode::Return]),
+            )],
+            vec![(3, "lib::helper")],
+        );
+        main.file.entry = Some(7);
+        let lib = unit(
+            "lib.gbc",
+            vec![(12, "lib::helper", func("helper", vec![ByteCode::Return]))],
+            vec![],
+        );
+        let out = link(vec![main, lib]).unwrap();
+        let names = out
+            .func_names
+            .iter()
+            .map(|(id, name)| (name.as_str(), *id))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(out.entry, Some(names["main::main"]));
+        assert_eq!(
+            out.funcs[&names["main::main"]].body,
+            vec![ByteCode::Call(names["lib::helper"]), ByteCode::Return]
+        );
+    }
+
+    #[test]
+    fn test_link_reports_missing_and_duplicate() {
+        let a = unit(
+            "a.gbc",
+            vec![(1, "a::run", func("run", vec![ByteCode::Call(2)]))],
+            vec![(2, "b::missing")],
+        );
+        let b = unit(
+            "b.gbc",
+            vec![(5, "a::run", func("run", vec![ByteCode::Pop]))],
+            vec![],
+        );
+        let Err(errors) = link(vec![a, b]) else {
+            panic!("Expected the link to fail");
+        };
+        assert!(errors.contains(&LinkError::Duplicate {
+            symbol: "a::run".to_string(),
+            first: "a.gbc".to_string(),
+            second: "b.gbc".to_string(),
+        }));
+        assert!(errors.contains(&LinkError::Missing {
+            symbol: "b::missing".to_string(),
+            unit: "a.gbc".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_link_keeps_vec_type_id() {
+        let mut main = unit(
+            "main.gbc",
+            vec![(
+                7,
+                "main::main",
+                func(
+                    "main",
+                    vec![
+                        ByteCode::Construct { id: 0, len: 0 },
+                        ByteCode::Construct { id: 4, len: 0 },
+                        ByteCode::Return,
+                    ],
+                ),
+            )],
+            vec![],
+        );
+        main.file.type_names.insert(4, "main::Empty".to_string());
+        let out = link(vec![main]).unwrap();
+        let id = out.func_names.keys().next().unwrap();
+        assert_eq!(out.type_names.keys().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(
+            out.funcs[id].body,
+            vec![
+                ByteCode::Construct { id: 0, len: 0 },
+                ByteCode::Construct { id: 1, len: 0 },
+                ByteCode::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_reports_duplicate_types() {
+        let mut main = unit(
+            "main.gbc",
+            vec![(7, "main::main", func("main", vec![ByteCode::Return]))],
+            vec![],
+        );
+        main.file.type_names.insert(3, "main::Foo".to_string());
+        main.file.type_names.insert(4, "main::Foo".to_string());
+        let Err(errors) = link(vec![main]) else {
+            panic!("Expected the link to fail");
+        };
+        assert_eq!(
+            errors,
+            vec![LinkError::DuplicateType {
+                symbol: "main::Foo".to_string(),
+                unit: "main.gbc".to_string(),
+            }]
+        );
+    }
+}
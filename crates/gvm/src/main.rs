@@ -1,12 +1,19 @@
 use clap::Parser;
-use cli::Command;
+use cli::{run_bundled, Command};
+use gvm::bundle;
 
+mod aot;
 mod binary;
 mod cli;
 mod format;
+mod link;
 mod text;
 mod vm;
 
 fn main() {
-    Command::parse().run()
+    if let Some(program) = bundle::current_program() {
+        run_bundled(program);
+    } else {
+        Command::parse().run();
+    }
 }
@@ -9,6 +9,14 @@ pub enum Token<'src> {
     Type,
     #[token("file")]
     File,
+    #[token("name")]
+    Name,
+    #[token("entry")]
+    Entry,
+    #[token("symbol")]
+    Symbol,
+    #[token("vtable")]
+    VTable,
     #[token("copy")]
     Copy,
     #[token("push")]
@@ -91,12 +99,62 @@ pub enum Token<'src> {
     Dyn,
     #[token("dyn_call")]
     DynCall,
+    #[token("tail_call")]
+    TailCall,
+    #[token("tail_dyn_call")]
+    TailDynCall,
     #[token("vec_peak")]
     VecPeak,
     #[token("div")]
     Div,
     #[token("mod")]
     Mod,
+    #[token("sqrt")]
+    Sqrt,
+    #[token("pow")]
+    Pow,
+    #[token("abs")]
+    Abs,
+    #[token("floor")]
+    Floor,
+    #[token("min")]
+    Min,
+    #[token("max")]
+    Max,
+    #[token("to_int")]
+    ToInt,
+    #[token("to_float")]
+    ToFloat,
+    #[token("to_string")]
+    ToString,
+    #[token("concat")]
+    Concat,
+    #[token("parse_int")]
+    ParseInt,
+    #[token("parse_float")]
+    ParseFloat,
+    #[token("spawn")]
+    Spawn,
+    #[token("channel")]
+    Channel,
+    #[token("send")]
+    Send,
+    #[token("receive")]
+    Receive,
+    #[token("generator")]
+    Generator,
+    #[token("yield")]
+    Yield,
+    #[token("resume")]
+    Resume,
+    #[token("read_line")]
+    ReadLine,
+    #[token("time")]
+    Time,
+    #[token("random")]
+    Random,
+    #[token("args")]
+    Args,
     #[token("mark")]
     Mark,
     #[token("true")]
@@ -124,7 +182,16 @@ pub enum Token<'src> {
 
 impl<'src> Token<'src> {
     pub fn is_decl(&self) -> bool {
-        matches!(self, Token::Func | Token::Type | Token::File)
+        matches!(
+            self,
+            Token::Func
+                | Token::Type
+                | Token::File
+                | Token::Name
+                | Token::Entry
+                | Token::Symbol
+                | Token::VTable
+        )
     }
 }
 
@@ -140,11 +207,15 @@ mod tests {
 
     #[test]
     fn test_lex_keywords() {
-        let text = r#"func type file copy push pop print"#;
+        let text = r#"func type file name entry symbol vtable copy push pop print"#;
         let mut lex = super::Token::lexer(text);
         assert_eq!(lex.next(), Some(Ok(Func)));
         assert_eq!(lex.next(), Some(Ok(Type)));
         assert_eq!(lex.next(), Some(Ok(File)));
+        assert_eq!(lex.next(), Some(Ok(Name)));
+        assert_eq!(lex.next(), Some(Ok(Entry)));
+        assert_eq!(lex.next(), Some(Ok(Symbol)));
+        assert_eq!(lex.next(), Some(Ok(VTable)));
         assert_eq!(lex.next(), Some(Ok(Copy)));
         assert_eq!(lex.next(), Some(Ok(Push)));
         assert_eq!(lex.next(), Some(Ok(Pop)));
@@ -152,6 +223,24 @@ mod tests {
         assert_eq!(lex.next(), None);
     }
 
+    #[test]
+    fn test_lex_math() {
+        let text = r#"sqrt pow abs floor min max to_int to_float to_string parse_int parse_float"#;
+        let mut lex = super::Token::lexer(text);
+        assert_eq!(lex.next(), Some(Ok(Sqrt)));
+        assert_eq!(lex.next(), Some(Ok(Pow)));
+        assert_eq!(lex.next(), Some(Ok(Abs)));
+        assert_eq!(lex.next(), Some(Ok(Floor)));
+        assert_eq!(lex.next(), Some(Ok(Min)));
+        assert_eq!(lex.next(), Some(Ok(Max)));
+        assert_eq!(lex.next(), Some(Ok(ToInt)));
+        assert_eq!(lex.next(), Some(Ok(ToFloat)));
+        assert_eq!(lex.next(), Some(Ok(ToString)));
+        assert_eq!(lex.next(), Some(Ok(ParseInt)));
+        assert_eq!(lex.next(), Some(Ok(ParseFloat)));
+        assert_eq!(lex.next(), None);
+    }
+
     #[test]
     fn test_lex_string() {
         let text = r#" "Hello" "World" "#;
@@ -61,8 +61,26 @@ pub fn parse_table<'src>(lex: &mut Lex<'src>) -> Result<(u64, VTable), ParseErro
     Ok((id, items))
 }
 
+pub fn parse_type_name<'src>(lex: &mut Lex<'src>) -> Result<(u32, String), ParseError<'src>> {
+    let id = expect_num(lex, "'id' (u32)")?;
+    let name = expect_string(lex, "'name' (String)")?.0.to_string();
+    Ok((id, name))
+}
+
 pub fn parse_file_name<'src>(lex: &mut Lex<'src>) -> Result<(u32, String), ParseError<'src>> {
     let id = expect_num(lex, "'id' (u32)")?;
     let name = expect_string(lex, "'name' (String)")?.0.to_string();
     Ok((id, name))
 }
+
+pub fn parse_func_name<'src>(lex: &mut Lex<'src>) -> Result<(u32, String), ParseError<'src>> {
+    let id = expect_num(lex, "'id' (u32)")?;
+    let name = expect_string(lex, "'name' (String)")?.0.to_string();
+    Ok((id, name))
+}
+
+pub fn parse_table_name<'src>(lex: &mut Lex<'src>) -> Result<(u64, String), ParseError<'src>> {
+    let id = expect_num(lex, "'id' (u64)")?;
+    let name = expect_string(lex, "'name' (String)")?.0.to_string();
+    Ok((id, name))
+}
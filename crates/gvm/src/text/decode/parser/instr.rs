@@ -97,7 +97,62 @@ pub fn parse_instr<'src>(lex: &mut Lex<'src>) -> PResult<'src, ByteCode> {
                 let id = expect_num(lex, "'id' (u32)")?;
                 Ok(ByteCode::DynCall(id))
             }
-            Token::Func | Token::Type | Token::File => Err(ParseError::ImpliedEnd),
+            Token::TailCall => {
+                let id = expect_num(lex, "'id' (u32)")?;
+                Ok(ByteCode::TailCall(id))
+            }
+            Token::TailDynCall => {
+                let id = expect_num(lex, "'id' (u32)")?;
+                Ok(ByteCode::TailDynCall(id))
+            }
+            Token::Sqrt => Ok(ByteCode::Sqrt),
+            Token::Pow => Ok(ByteCode::Pow),
+            Token::Abs => Ok(ByteCode::Abs),
+            Token::Floor => Ok(ByteCode::Floor),
+            Token::Min => Ok(ByteCode::Min),
+            Token::Max => Ok(ByteCode::Max),
+            Token::ToInt => Ok(ByteCode::ToInt),
+            Token::ToFloat => Ok(ByteCode::ToFloat),
+            Token::ToString => Ok(ByteCode::ToString),
+            Token::Concat => {
+                let count = expect_num(lex, "'count' (u32)")?;
+                Ok(ByteCode::Concat(count))
+            }
+            Token::ParseInt => {
+                let some = expect_num(lex, "'some' (u32)")?;
+                let none = expect_num(lex, "'none' (u32)")?;
+                Ok(ByteCode::ParseInt { some, none })
+            }
+            Token::ParseFloat => {
+                let some = expect_num(lex, "'some' (u32)")?;
+                let none = expect_num(lex, "'none' (u32)")?;
+                Ok(ByteCode::ParseFloat { some, none })
+            }
+            Token::Spawn => {
+                let id = expect_num(lex, "'id' (u32)")?;
+                Ok(ByteCode::Spawn(id))
+            }
+            Token::Channel => Ok(ByteCode::Channel),
+            Token::Send => Ok(ByteCode::Send),
+            Token::Receive => Ok(ByteCode::Receive),
+            Token::Generator => Ok(ByteCode::Generator),
+            Token::Yield => {
+                let id = expect_num(lex, "'some' (u32)")?;
+                Ok(ByteCode::Yield(id))
+            }
+            Token::Resume => {
+                let id = expect_num(lex, "'none' (u32)")?;
+                Ok(ByteCode::Resume(id))
+            }
+            Token::ReadLine => {
+                let some = expect_num(lex, "'some' (u32)")?;
+                let none = expect_num(lex, "'none' (u32)")?;
+                Ok(ByteCode::ReadLine { some, none })
+            }
+            Token::Time => Ok(ByteCode::Time),
+            Token::Random => Ok(ByteCode::Random),
+            Token::Args => Ok(ByteCode::Args),
+            Token::Func | Token::Type | Token::File | Token::Name => Err(ParseError::ImpliedEnd),
             found => Err(ParseError::UnexpectedToken {
                 range: range.clone(),
                 found: found.clone(),
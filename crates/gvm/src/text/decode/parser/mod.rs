@@ -1,9 +1,11 @@
 use std::{num::ParseIntError, ops::Range};
 
-use decl::{parse_file_name, parse_func, parse_table};
+use decl::{
+    parse_file_name, parse_func, parse_func_name, parse_table, parse_table_name, parse_type_name,
+};
 use logos::Logos as _;
 use thiserror::Error;
-use util::PResult;
+use util::{expect_num, PResult};
 
 use crate::format::ByteCodeFile;
 
@@ -14,7 +16,7 @@ mod instr;
 mod mark;
 mod util;
 
-const DECL_HEADERS: &str = "one of 'func', 'type' or 'file'";
+const DECL_HEADERS: &str = "one of 'func', 'type', 'file', 'name', 'entry', 'symbol' or 'vtable'";
 
 pub fn parse_text_file(text: &str) -> PResult<'_, ByteCodeFile> {
     let mut file = ByteCodeFile::default();
@@ -34,6 +36,21 @@ pub fn parse_text_file(text: &str) -> PResult<'_, ByteCodeFile> {
                 let (id, name) = parse_file_name(&mut lex)?;
                 file.file_names.insert(id, name);
             }
+            (Ok(Token::Entry), _) => {
+                file.entry = Some(expect_num(&mut lex, "'id' (u32)")?);
+            }
+            (Ok(Token::Symbol), _) => {
+                let (id, name) = parse_func_name(&mut lex)?;
+                file.func_names.insert(id, name);
+            }
+            (Ok(Token::VTable), _) => {
+                let (id, name) = parse_table_name(&mut lex)?;
+                file.table_names.insert(id, name);
+            }
+            (Ok(Token::Name), _) => {
+                let (id, name) = parse_type_name(&mut lex)?;
+                file.type_names.insert(id, name);
+            }
             (Err(()), range) => return Err(ParseError::LexError { range }),
             (Ok(found), range) => {
                 return Err(ParseError::UnexpectedToken {
@@ -1,6 +1,6 @@
 use std::fmt::Formatter;
 
-use crate::format::{func::FuncDef, table::VTable};
+use crate::format::{func::FuncDef, sorted, table::VTable};
 
 pub fn write_func_def(id: u32, func: &FuncDef, f: &mut Formatter<'_>) -> std::fmt::Result {
     writeln!(
@@ -23,12 +23,24 @@ pub fn write_func_def(id: u32, func: &FuncDef, f: &mut Formatter<'_>) -> std::fm
 
 pub fn write_table(id: u64, table: &VTable, f: &mut Formatter<'_>) -> std::fmt::Result {
     writeln!(f, "type {id}")?;
-    for (key, value) in table {
+    for (key, value) in sorted(table) {
         writeln!(f, "    {key} {value}")?;
     }
     Ok(())
 }
 
+pub fn write_type_name(id: u32, name: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "name {id} \"{name}\"")
+}
+
 pub fn write_file_name(id: u32, name: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
     writeln!(f, "file {id} \"{name}\"")
 }
+
+pub fn write_func_name(id: u32, name: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "symbol {id} \"{name}\"")
+}
+
+pub fn write_table_name(id: u64, name: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "vtable {id} \"{name}\"")
+}
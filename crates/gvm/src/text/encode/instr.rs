@@ -46,8 +46,33 @@ impl Display for ByteCode {
             ByteCode::VecLen => write!(f, "vec_len"),
             ByteCode::Dyn(id) => write!(f, "dyn {id}"),
             ByteCode::DynCall(id) => write!(f, "dyn_call {id}"),
+            ByteCode::TailCall(id) => write!(f, "tail_call {id}"),
+            ByteCode::TailDynCall(id) => write!(f, "tail_dyn_call {id}"),
             ByteCode::Div => write!(f, "div"),
             ByteCode::Mod => write!(f, "mod"),
+            ByteCode::Sqrt => write!(f, "sqrt"),
+            ByteCode::Pow => write!(f, "pow"),
+            ByteCode::Abs => write!(f, "abs"),
+            ByteCode::Floor => write!(f, "floor"),
+            ByteCode::Min => write!(f, "min"),
+            ByteCode::Max => write!(f, "max"),
+            ByteCode::ToInt => write!(f, "to_int"),
+            ByteCode::ToFloat => write!(f, "to_float"),
+            ByteCode::ToString => write!(f, "to_string"),
+            ByteCode::Concat(count) => write!(f, "concat {count}"),
+            ByteCode::ParseInt { some, none } => write!(f, "parse_int {some} {none}"),
+            ByteCode::ParseFloat { some, none } => write!(f, "parse_float {some} {none}"),
+            ByteCode::Spawn(id) => write!(f, "spawn {id}"),
+            ByteCode::Channel => write!(f, "channel"),
+            ByteCode::Send => write!(f, "send"),
+            ByteCode::Receive => write!(f, "receive"),
+            ByteCode::Generator => write!(f, "generator"),
+            ByteCode::Yield(id) => write!(f, "yield {id}"),
+            ByteCode::Resume(id) => write!(f, "resume {id}"),
+            ByteCode::ReadLine { some, none } => write!(f, "read_line {some} {none}"),
+            ByteCode::Time => write!(f, "time"),
+            ByteCode::Random => write!(f, "random"),
+            ByteCode::Args => write!(f, "args"),
         }
     }
 }
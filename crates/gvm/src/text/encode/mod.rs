@@ -1,8 +1,11 @@
 use std::fmt::{Display, Formatter};
 
-use decl::{write_file_name, write_func_def, write_table};
+use decl::{
+    write_file_name, write_func_def, write_func_name, write_table, write_table_name,
+    write_type_name,
+};
 
-use crate::format::ByteCodeFile;
+use crate::format::{sorted, ByteCodeFile};
 
 mod decl;
 mod instr;
@@ -10,13 +13,25 @@ mod literal;
 
 impl Display for ByteCodeFile {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for (id, name) in &self.file_names {
+        if let Some(entry) = self.entry {
+            writeln!(f, "entry {entry}")?;
+        }
+        for (id, name) in sorted(&self.file_names) {
             write_file_name(*id, name, f)?;
         }
-        for (id, table) in &self.tables {
+        for (id, name) in sorted(&self.type_names) {
+            write_type_name(*id, name, f)?;
+        }
+        for (id, name) in sorted(&self.func_names) {
+            write_func_name(*id, name, f)?;
+        }
+        for (id, name) in sorted(&self.table_names) {
+            write_table_name(*id, name, f)?;
+        }
+        for (id, table) in sorted(&self.tables) {
             write_table(*id, table, f)?;
         }
-        for (id, func) in &self.funcs {
+        for (id, func) in sorted(&self.funcs) {
             write_func_def(*id, func, f)?;
         }
         Ok(())
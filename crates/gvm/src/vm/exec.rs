@@ -1,8 +1,13 @@
-use std::{collections::HashMap, process::exit};
+use std::collections::VecDeque;
 
 use crate::{format::instr::ByteCode, vm::text::DebugText as _};
 
-use super::{heap::HeapItem, scope::Scope, stack::StackItem, state::ProgramState};
+use super::{
+    heap::{HeapItem, Suspended},
+    scope::Scope,
+    stack::StackItem,
+    state::{int_abs, int_pow, ProgramState},
+};
 
 #[allow(clippy::too_many_lines)]
 impl<'code> ProgramState<'code> {
@@ -31,29 +36,37 @@ impl<'code> ProgramState<'code> {
                 self.push(*refr);
             }
             ByteCode::Print => {
-                print!("{}", self.pop().get_text(self));
+                let text = self.pop().get_text(self);
+                if let Some(captured) = &mut self.captured {
+                    captured.push_str(&text);
+                } else if !self.muted {
+                    print!("{text}");
+                }
             }
             ByteCode::Panic => {
-                println!("{}", self.pop().get_text(self));
-                println!("{}", self.stack_trace());
-                exit(1);
+                // The message stays on the stack, so it's in the snapshot
+                let message = self.peak().get_text(self);
+                self.fail(&message);
             }
             ByteCode::Call(id) => {
-                let func = &self.funcs[id];
-                let mut args = Vec::with_capacity(func.args as usize);
-                for _ in 0..func.args {
-                    args.insert(0, self.pop());
+                let args = self.pop_args(*id);
+                if self.call_jit(*id, &args) {
+                    return;
                 }
                 let scope = Scope {
                     args,
-                    locals: HashMap::new(),
-                    stack: Vec::new(),
-                    code: &func.body,
-                    index: 0,
-                    id: *id,
+                    ..Scope::from_code(&self.funcs[id].body, *id)
                 };
                 self.scopes.push(scope);
             }
+            ByteCode::TailCall(id) => {
+                let args = self.pop_args(*id);
+                if self.call_jit(*id, &args) {
+                    self.leave();
+                    return;
+                }
+                self.tail_call(*id, args);
+            }
             ByteCode::Dyn(id) => {
                 let item = self.pop();
                 let refr = self.heap.insert(HeapItem::Dyn(*id, item));
@@ -61,44 +74,18 @@ impl<'code> ProgramState<'code> {
                 self.push(res);
             }
             ByteCode::DynCall(func_id) => {
-                let trait_func = &self.funcs[func_id];
-                let mut args = Vec::with_capacity(trait_func.args as usize);
-                let mut type_id = 0;
-                for i in (0..trait_func.args).rev() {
-                    let StackItem::Heap(dyn_) = self.pop() else {
-                        panic!("Expected heap obj");
-                    };
-                    if i == 0 {
-                        let HeapItem::Dyn(id, refr) = self.heap.get(dyn_).unwrap() else {
-                            panic!("Expected dyn")
-                        };
-                        type_id = *id;
-                        args.push(*refr);
-                    } else {
-                        args.push(self.pop());
-                    }
-                }
-                let impl_func = self.get_trait_impl(*func_id, type_id).unwrap();
-                let code = &self.funcs[&impl_func].body;
+                let (impl_func, args) = self.pop_dyn_args(*func_id);
                 let scope = Scope {
                     args,
-                    locals: HashMap::new(),
-                    stack: Vec::new(),
-                    code,
-                    index: 0,
-                    id: impl_func,
+                    ..Scope::from_code(&self.funcs[&impl_func].body, impl_func)
                 };
                 self.scopes.push(scope);
             }
-            ByteCode::Return => {
-                let ret = self.scope_mut().stack.pop();
-                self.scopes.pop();
-                if let Some(ret) = ret {
-                    if !self.scopes.is_empty() {
-                        self.push(ret);
-                    }
-                }
+            ByteCode::TailDynCall(func_id) => {
+                let (impl_func, args) = self.pop_dyn_args(*func_id);
+                self.tail_call(impl_func, args);
             }
+            ByteCode::Return => self.leave(),
             ByteCode::Construct { id, len } => {
                 let mut args = Vec::new();
                 for _ in 0..*len {
@@ -377,6 +364,271 @@ impl<'code> ProgramState<'code> {
                     panic!("Cannot 'or' non-bools")
                 }
             },
+            ByteCode::Sqrt => match self.pop() {
+                StackItem::Int(a) => {
+                    let res = StackItem::Float((a as f32).sqrt());
+                    self.push(res);
+                }
+                StackItem::Float(a) => {
+                    let res = StackItem::Float(a.sqrt());
+                    self.push(res);
+                }
+                _ => {
+                    panic!("Cannot 'sqrt' non-numbers")
+                }
+            },
+            ByteCode::Pow => {
+                let b = self.pop();
+                let a = self.pop();
+                match (a, b) {
+                    (StackItem::Int(a), StackItem::Int(b)) => {
+                        let res = int_pow(a, b).unwrap_or_else(|message| self.fail(&message));
+                        self.push(StackItem::Int(res));
+                    }
+                    (StackItem::Int(a), StackItem::Float(b)) => {
+                        let res = StackItem::Float((a as f32).powf(b));
+                        self.push(res);
+                    }
+                    (StackItem::Float(a), StackItem::Int(b)) => {
+                        let res = StackItem::Float(a.powi(b));
+                        self.push(res);
+                    }
+                    (StackItem::Float(a), StackItem::Float(b)) => {
+                        let res = StackItem::Float(a.powf(b));
+                        self.push(res);
+                    }
+                    _ => {
+                        panic!("Cannot 'pow' non-numbers")
+                    }
+                }
+            }
+            ByteCode::Abs => match self.pop() {
+                StackItem::Int(a) => {
+                    let res = int_abs(a).unwrap_or_else(|message| self.fail(&message));
+                    self.push(StackItem::Int(res));
+                }
+                StackItem::Float(a) => {
+                    let res = StackItem::Float(a.abs());
+                    self.push(res);
+                }
+                _ => {
+                    panic!("Cannot 'abs' non-numbers")
+                }
+            },
+            ByteCode::Floor => match self.pop() {
+                StackItem::Int(a) => {
+                    self.push(StackItem::Int(a));
+                }
+                StackItem::Float(a) => {
+                    let res = StackItem::Float(a.floor());
+                    self.push(res);
+                }
+                _ => {
+                    panic!("Cannot 'floor' non-numbers")
+                }
+            },
+            ByteCode::Min => {
+                let b = self.pop();
+                let a = self.pop();
+                match (a, b) {
+                    (StackItem::Int(a), StackItem::Int(b)) => {
+                        let res = StackItem::Int(a.min(b));
+                        self.push(res);
+                    }
+                    (StackItem::Float(a), StackItem::Float(b)) => {
+                        let res = StackItem::Float(a.min(b));
+                        self.push(res);
+                    }
+                    _ => {
+                        panic!("Cannot 'min' non-numbers")
+                    }
+                }
+            }
+            ByteCode::Max => {
+                let b = self.pop();
+                let a = self.pop();
+                match (a, b) {
+                    (StackItem::Int(a), StackItem::Int(b)) => {
+                        let res = StackItem::Int(a.max(b));
+                        self.push(res);
+                    }
+                    (StackItem::Float(a), StackItem::Float(b)) => {
+                        let res = StackItem::Float(a.max(b));
+                        self.push(res);
+                    }
+                    _ => {
+                        panic!("Cannot 'max' non-numbers")
+                    }
+                }
+            }
+            ByteCode::ToInt => match self.pop() {
+                StackItem::Int(a) => {
+                    self.push(StackItem::Int(a));
+                }
+                StackItem::Float(a) => {
+                    let res = StackItem::Int(a as i32);
+                    self.push(res);
+                }
+                StackItem::Char(a) => {
+                    let res = StackItem::Int(a as i32);
+                    self.push(res);
+                }
+                _ => {
+                    panic!("Cannot convert to int")
+                }
+            },
+            ByteCode::ToFloat => match self.pop() {
+                StackItem::Int(a) => {
+                    let res = StackItem::Float(a as f32);
+                    self.push(res);
+                }
+                StackItem::Float(a) => {
+                    self.push(StackItem::Float(a));
+                }
+                _ => {
+                    panic!("Cannot convert to float")
+                }
+            },
+            ByteCode::ToString => {
+                let text = self.pop().get_text(self);
+                let refr = self.heap.insert(HeapItem::String(text));
+                self.push(StackItem::Heap(refr.into()));
+            }
+            ByteCode::Concat(count) => {
+                let parts = (0..*count).map(|_| self.pop()).collect::<Vec<_>>();
+                let text = parts
+                    .iter()
+                    .rev()
+                    .map(|part| part.get_text(self))
+                    .collect::<String>();
+                let refr = self.heap.insert(HeapItem::String(text));
+                self.push(StackItem::Heap(refr.into()));
+            }
+            ByteCode::ParseInt { some, none } => {
+                let parsed = self.pop_string().trim().parse::<i32>().ok();
+                self.construct_option(parsed.map(StackItem::Int), *some, *none);
+            }
+            ByteCode::ParseFloat { some, none } => {
+                let parsed = self.pop_string().trim().parse::<f32>().ok();
+                self.construct_option(parsed.map(StackItem::Float), *some, *none);
+            }
+            ByteCode::Spawn(func_id) => {
+                let StackItem::Heap(dyn_) = self.pop() else {
+                    panic!("Expected heap obj");
+                };
+                let HeapItem::Dyn(type_id, task) = self.heap.get(dyn_).unwrap() else {
+                    panic!("Expected dyn")
+                };
+                let task = *task;
+                let impl_func = self.get_trait_impl(*func_id, *type_id).unwrap();
+                self.spawn(impl_func, vec![task]);
+            }
+            ByteCode::Channel => {
+                let refr = self.heap.insert(HeapItem::Channel(VecDeque::new()));
+                self.push(StackItem::Heap(refr.into()));
+            }
+            ByteCode::Send => {
+                let value = self.pop();
+                let StackItem::Heap(refr) = self.pop() else {
+                    panic!("Expected heap obj")
+                };
+                let HeapItem::Channel(items) = self.heap.get_mut(refr).unwrap() else {
+                    panic!("Expected channel")
+                };
+                items.push_back(value);
+            }
+            ByteCode::Receive => {
+                let chan = self.pop();
+                let StackItem::Heap(refr) = chan else {
+                    panic!("Expected heap obj")
+                };
+                let HeapItem::Channel(items) = self.heap.get_mut(refr).unwrap() else {
+                    panic!("Expected channel")
+                };
+                if let Some(value) = items.pop_front() {
+                    self.push(value);
+                } else {
+                    // Block until another thread sends, then retry this instruction
+                    self.push(chan);
+                    self.scope_mut().index -= 1;
+                    self.waiting = Some(refr);
+                }
+            }
+            ByteCode::Generator => {
+                let scope = self.scopes.pop().expect("Call stack underflow");
+                let frame = Suspended {
+                    func: scope.id,
+                    args: scope.args,
+                    locals: scope.locals,
+                    stack: scope.stack,
+                    index: scope.index,
+                    done: false,
+                };
+                let refr = self.heap.insert(HeapItem::Generator(frame));
+                self.push(StackItem::Heap(refr.into()));
+            }
+            ByteCode::Yield(some) => {
+                let value = self.pop();
+                let scope = self.scopes.pop().expect("Call stack underflow");
+                let generator = scope.generator.expect("Can only yield from a generator");
+                let Some(HeapItem::Generator(frame)) = self.heap.get_mut(generator) else {
+                    panic!("Expected generator")
+                };
+                frame.locals = scope.locals;
+                frame.stack = scope.stack;
+                frame.index = scope.index;
+                let refr = self.heap.insert(HeapItem::Object(*some, vec![value]));
+                self.push(StackItem::Heap(refr.into()));
+            }
+            ByteCode::Resume(none) => {
+                let StackItem::Heap(refr) = self.pop() else {
+                    panic!("Expected heap obj")
+                };
+                let Some(HeapItem::Generator(frame)) = self.heap.get(refr) else {
+                    panic!("Expected generator")
+                };
+                if frame.done {
+                    let refr = self.heap.insert(HeapItem::Object(*none, vec![]));
+                    self.push(StackItem::Heap(refr.into()));
+                    return;
+                }
+                let scope = Scope {
+                    args: frame.args.clone(),
+                    locals: frame.locals.clone(),
+                    stack: frame.stack.clone(),
+                    code: &self.funcs[&frame.func].body,
+                    index: frame.index,
+                    id: frame.func,
+                    generator: Some(refr),
+                    elided: 0,
+                };
+                self.scopes.push(scope);
+            }
+            ByteCode::ReadLine { some, none } => {
+                let line = self.inputs.read_line().map(|line| {
+                    let refr = self.heap.insert(HeapItem::String(line));
+                    StackItem::Heap(refr.into())
+                });
+                self.construct_option(line, *some, *none);
+            }
+            ByteCode::Time => {
+                let time = self.inputs.time();
+                self.push(StackItem::Int(time));
+            }
+            ByteCode::Random => {
+                let num = self.inputs.random();
+                self.push(StackItem::Int(num));
+            }
+            ByteCode::Args => {
+                let args = self
+                    .inputs
+                    .args()
+                    .into_iter()
+                    .map(|arg| StackItem::Heap(self.heap.insert(HeapItem::String(arg)).into()))
+                    .collect();
+                let refr = self.heap.insert(HeapItem::Object(0, args));
+                self.push(StackItem::Heap(refr.into()));
+            }
             ByteCode::Index(index) => {
                 let StackItem::Heap(refr) = self.pop() else {
                     panic!("Expected heap obj")
@@ -418,7 +670,7 @@ impl<'code> ProgramState<'code> {
                 let HeapItem::Object(_, data) = self.heap.get(refr).unwrap() else {
                     panic!("Expected vec")
                 };
-                let res = data[index as usize];
+                let res = data[self.checked_index(index, data.len())];
                 self.push(res);
             }
             ByteCode::VecSet => {
@@ -429,10 +681,14 @@ impl<'code> ProgramState<'code> {
                 let StackItem::Heap(refr) = self.pop() else {
                     panic!("Expected heap obj")
                 };
+                let HeapItem::Object(_, data) = self.heap.get(refr).unwrap() else {
+                    panic!("Expected vec")
+                };
+                let index = self.checked_index(index, data.len());
                 let HeapItem::Object(_, data) = self.heap.get_mut(refr).unwrap() else {
                     panic!("Expected vec")
                 };
-                data[index as usize] = value;
+                data[index] = value;
             }
             ByteCode::VecPush => {
                 let value = self.pop();
@@ -503,3 +759,325 @@ impl<'code> ProgramState<'code> {
         };
     }
 }
+
+impl<'code> ProgramState<'code> {
+    /// Pops the arguments for a call to `id`, in parameter order
+    fn pop_args(&mut self, id: u32) -> Vec<StackItem> {
+        let count = self.funcs[&id].args as usize;
+        let stack = &mut self.scope_mut().stack;
+        let Some(start) = stack.len().checked_sub(count) else {
+            panic!("Stack underflow: {}", self.stack_trace())
+        };
+        stack.split_off(start)
+    }
+
+    /// Pops the arguments for a call to a trait function, unwrapping the receiver,
+    /// and finds the implementation to call
+    fn pop_dyn_args(&mut self, func_id: u32) -> (u32, Vec<StackItem>) {
+        let mut args = self.pop_args(func_id);
+        let StackItem::Heap(dyn_) = args[0] else {
+            panic!("Expected heap obj");
+        };
+        let HeapItem::Dyn(type_id, receiver) = self.heap.get(dyn_).unwrap() else {
+            panic!("Expected dyn")
+        };
+        let impl_func = self.get_trait_impl(func_id, *type_id).unwrap();
+        args[0] = *receiver;
+        (impl_func, args)
+    }
+
+    /// Runs `id` in place of the current frame.
+    /// Generator frames are kept, as returning from them is what finishes the generator
+    fn tail_call(&mut self, id: u32, args: Vec<StackItem>) {
+        let code = &self.funcs[&id].body;
+        let current = self.scope_mut();
+        if current.generator.is_some() {
+            let scope = Scope {
+                args,
+                ..Scope::from_code(code, id)
+            };
+            self.scopes.push(scope);
+            return;
+        }
+        *current = Scope {
+            args,
+            elided: current.elided + 1,
+            ..Scope::from_code(code, id)
+        };
+    }
+
+    /// Pops the current frame, passing the top of its stack to the caller
+    fn leave(&mut self) {
+        let ret = self.scope_mut().stack.pop();
+        let scope = self.scopes.pop();
+        if let Some(generator) = scope.and_then(|scope| scope.generator) {
+            if let Some(HeapItem::Generator(frame)) = self.heap.get_mut(generator) {
+                frame.done = true;
+            }
+        }
+        if let Some(ret) = ret {
+            if !self.scopes.is_empty() {
+                self.push(ret);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        text::decode::parser::parse_text_file,
+        vm::{
+            heap::HeapItem,
+            stack::StackItem,
+            state::{int_abs, int_pow, vec_index, ProgramState},
+        },
+    };
+
+    const PROGRAM: &str = r#"
+        file 0 "main.gib"
+        entry 0
+        func 0 0 "main" 1 1 0
+        push 100000 call 1 return
+
+        func 1 1 "down" 5 1 0
+        param 0 push 0 eq jne 6
+        push 7 return
+        param 0 push 1 sub tail_call 1 return
+    "#;
+
+    #[test]
+    fn test_tail_calls_reuse_frame() {
+        let file = parse_text_file(PROGRAM).unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        let mut steps = 0;
+        while prog.scope().id != 0 || prog.scope().index < 2 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+            assert!(prog.scopes.len() <= 2);
+            steps += 1;
+            if steps == 2 + 8 * 9 {
+                assert!(prog
+                    .stack_trace()
+                    .contains("9 frames elided by tail calls\n  -> main.gib"));
+            }
+        }
+        assert_eq!(prog.scope().stack, vec![StackItem::Int(7)]);
+    }
+
+    #[test]
+    fn test_concat() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 0 1 0
+            push "n = " push 4 push ", " push true concat 4 return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        while prog.scope().index < 5 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+        }
+        assert_eq!(prog.pop_string(), "n = 4, true");
+    }
+
+    #[test]
+    fn test_generator_suspends_and_resumes() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 1 1 0
+            call 1 new 0
+            get 0 resume 3 get 0 resume 3 get 0 resume 3 get 0 resume 3
+            return
+
+            func 1 0 "count" 5 1 0
+            generator push 0 yield 2 push 1 yield 2 construct 3 0 return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        while prog.scope().id != 0 || prog.scope().index < 10 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+        }
+        let results = prog
+            .scope()
+            .stack
+            .iter()
+            .map(|item| {
+                let StackItem::Heap(refr) = item else {
+                    panic!("Expected heap obj")
+                };
+                let Some(HeapItem::Object(id, fields)) = prog.heap.get(*refr) else {
+                    panic!("Expected object")
+                };
+                (*id, fields.clone())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                (2, vec![StackItem::Int(0)]),
+                (2, vec![StackItem::Int(1)]),
+                (3, vec![]),
+                (3, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vec_literal_get_and_set() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 0 1 0
+            construct 0 0 copy push 1 vec_push copy push 2 vec_push
+            copy push 9 push 0 vec_set copy push 1 vec_get return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        while prog.scope().index < 14 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+        }
+        let [StackItem::Heap(refr), item] = prog.scope().stack[..] else {
+            panic!("Expected a vec and an item")
+        };
+        assert_eq!(item, StackItem::Int(2));
+        let Some(HeapItem::Object(id, fields)) = prog.heap.get(refr) else {
+            panic!("Expected vec")
+        };
+        assert_eq!(
+            (*id, fields.clone()),
+            (0, vec![StackItem::Int(9), StackItem::Int(2)])
+        );
+    }
+
+    #[test]
+    fn test_vec_index_bounds() {
+        assert_eq!(vec_index(0, 2), Some(0));
+        assert_eq!(vec_index(1, 2), Some(1));
+        assert_eq!(vec_index(2, 2), None);
+        assert_eq!(vec_index(-1, 2), None);
+        assert_eq!(vec_index(0, 0), None);
+    }
+
+    #[test]
+    fn test_math_and_conversion_builtins() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 0 1 0
+            push 2 push 10 pow push 0 push 7 sub abs push 2.5 floor push 3 push 9 min
+            push 1.5 push 0.5 max push 2.5 to_int push 3 to_float push 16.0 sqrt
+            push 42 to_string push " 12 " parse_int 2 3 push "twelve" parse_int 2 3
+            push "99999999999" parse_int 2 3 push "2.5" parse_float 2 3
+            push "" parse_float 2 3 return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        while prog.scope().index < 33 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+        }
+        let mut options = (0..5)
+            .map(|_| {
+                let StackItem::Heap(refr) = prog.pop() else {
+                    panic!("Expected heap obj")
+                };
+                let Some(HeapItem::Object(id, fields)) = prog.heap.get(refr) else {
+                    panic!("Expected object")
+                };
+                (*id, fields.clone())
+            })
+            .collect::<Vec<_>>();
+        options.reverse();
+        assert_eq!(
+            options,
+            vec![
+                (2, vec![StackItem::Int(12)]),
+                (3, vec![]),
+                (3, vec![]),
+                (2, vec![StackItem::Float(2.5)]),
+                (3, vec![]),
+            ]
+        );
+        assert_eq!(prog.pop_string(), "42");
+        assert_eq!(
+            prog.scope().stack,
+            vec![
+                StackItem::Int(1024),
+                StackItem::Int(7),
+                StackItem::Float(2.0),
+                StackItem::Int(3),
+                StackItem::Float(1.5),
+                StackItem::Int(2),
+                StackItem::Float(3.0),
+                StackItem::Float(4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_int_pow_and_abs_overflow() {
+        assert_eq!(int_pow(2, 10), Ok(1024));
+        assert_eq!(int_pow(-2, 31), Ok(i32::MIN));
+        assert_eq!(
+            int_pow(2, 40),
+            Err("Integer overflow raising 2 to the power 40".to_string())
+        );
+        assert_eq!(
+            int_pow(2, -1),
+            Err("Cannot raise an int to a negative power: -1".to_string())
+        );
+        assert_eq!(int_abs(-5), Ok(5));
+        assert_eq!(
+            int_abs(i32::MIN),
+            Err("Integer overflow taking the absolute value of -2147483648".to_string())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, VecDeque};
+
 use broom::trace::Trace;
 
 use super::stack::StackItem;
@@ -7,6 +9,19 @@ pub enum HeapItem {
     Object(u32, Vec<StackItem>),
     String(String),
     Dyn(u64, StackItem),
+    Channel(VecDeque<StackItem>),
+    Generator(Suspended),
+}
+
+/// The saved frame of a generator between calls to `next`
+#[derive(PartialEq, Debug, Clone)]
+pub struct Suspended {
+    pub func: u32,
+    pub args: Vec<StackItem>,
+    pub locals: HashMap<u32, StackItem>,
+    pub stack: Vec<StackItem>,
+    pub index: usize,
+    pub done: bool,
 }
 
 impl Trace<Self> for HeapItem {
@@ -18,6 +33,21 @@ impl Trace<Self> for HeapItem {
                 }
             }
             HeapItem::Dyn(_, item) => item.trace(tracer),
+            HeapItem::Channel(items) => {
+                for item in items {
+                    item.trace(tracer);
+                }
+            }
+            HeapItem::Generator(frame) => {
+                for item in frame
+                    .args
+                    .iter()
+                    .chain(frame.locals.values())
+                    .chain(&frame.stack)
+                {
+                    item.trace(tracer);
+                }
+            }
             HeapItem::String(_) => {}
         }
     }
@@ -0,0 +1,1047 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use cranelift_codegen::{
+    ir::{
+        condcodes::{FloatCC, IntCC},
+        types, AbiParam, Block, InstBuilder, MemFlags, Signature, StackSlotData, StackSlotKind,
+        Type, Value,
+    },
+    settings::{self, Configurable as _},
+    Context,
+};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, FuncId, Linkage, Module};
+
+use crate::format::{func::FuncDef, instr::ByteCode, literal::Literal};
+
+use super::{stack::StackItem, state::ProgramState, thread::FUEL};
+
+/// How many interpreted calls make a function worth compiling
+pub const HOT_CALLS: u32 = 200;
+
+/// Compiled code deoptimises rather than recursing deeper than this on the native stack
+const MAX_DEPTH: i64 = 512;
+
+/// A compiled function that deoptimises this many times goes back to being interpreted
+const MAX_DEOPTS: u32 = 8;
+
+/// The scalar types compiled code works with, decided by the arguments a function is called with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ty {
+    Int,
+    Float,
+    Bool,
+}
+
+impl Ty {
+    fn of(item: &StackItem) -> Option<Ty> {
+        match item {
+            StackItem::Int(_) => Some(Ty::Int),
+            StackItem::Float(_) => Some(Ty::Float),
+            StackItem::Bool(_) => Some(Ty::Bool),
+            _ => None,
+        }
+    }
+
+    fn clif(self) -> Type {
+        match self {
+            Ty::Int => types::I32,
+            Ty::Float => types::F32,
+            Ty::Bool => types::I8,
+        }
+    }
+}
+
+/// `(steps, args, ret, depth) -> status`, where a non-zero status asks the caller to deoptimise
+type Entry = unsafe extern "C" fn(*mut u64, *const u64, *mut u64, i32) -> u8;
+
+enum Spec {
+    /// Being compiled, with the return type once one has been inferred
+    InProgress {
+        func: FuncId,
+        ret: Option<Option<Ty>>,
+    },
+    Ready {
+        func: FuncId,
+        ret: Option<Ty>,
+        entry: Option<Entry>,
+        deopts: u32,
+    },
+    Failed,
+}
+
+/// What a call inside compiled code resolves to
+enum Callee {
+    Known(FuncId, Option<Ty>),
+    /// A recursive call whose return type isn't known yet
+    Pending,
+}
+
+/// The outcome of running a function natively
+pub struct JitCall {
+    pub ret: Option<StackItem>,
+    /// How many instructions the interpreter would have executed
+    pub steps: u64,
+}
+
+/// A tier that compiles hot functions working only on `Int`, `Float` and `Bool` values to native code.
+///
+/// Functions are specialised on the types of their arguments.
+/// Compiled code can't touch the heap, so it has no side effects and can always bail out
+/// (on overflow, division by zero or deep recursion) by letting the interpreter redo the call
+pub struct Jit {
+    module: JITModule,
+    ctx: Context,
+    builder_ctx: FunctionBuilderContext,
+    calls: HashMap<u32, u32>,
+    specs: HashMap<(u32, Vec<Ty>), Spec>,
+    threshold: u32,
+}
+
+impl Jit {
+    /// Returns `None` if the host isn't supported by the code generator
+    pub fn new(threshold: u32) -> Option<Self> {
+        let mut flags = settings::builder();
+        flags.set("use_colocated_libcalls", "false").ok()?;
+        flags.set("is_pic", "false").ok()?;
+        flags.set("opt_level", "speed").ok()?;
+        let isa = cranelift_native::builder()
+            .ok()?
+            .finish(settings::Flags::new(flags))
+            .ok()?;
+        let module = JITModule::new(JITBuilder::with_isa(isa, default_libcall_names()));
+        Some(Self {
+            ctx: module.make_context(),
+            module,
+            builder_ctx: FunctionBuilderContext::new(),
+            calls: HashMap::new(),
+            specs: HashMap::new(),
+            threshold,
+        })
+    }
+
+    /// Runs `id` natively if it's hot and can be compiled for these arguments.
+    /// `None` means the call should be interpreted instead
+    pub fn call(
+        &mut self,
+        funcs: &HashMap<u32, FuncDef>,
+        id: u32,
+        args: &[StackItem],
+    ) -> Option<JitCall> {
+        let count = self.calls.entry(id).or_default();
+        *count = count.saturating_add(1);
+        if *count < self.threshold {
+            return None;
+        }
+        let key = (id, args.iter().map(Ty::of).collect::<Option<Vec<_>>>()?);
+        if !self.specs.contains_key(&key) {
+            self.specialize(funcs, &key, &mut vec![]);
+            self.finalize();
+        }
+        let spec = self.specs.get_mut(&key)?;
+        let Spec::Ready {
+            ret,
+            entry: Some(entry),
+            deopts,
+            ..
+        } = spec
+        else {
+            return None;
+        };
+
+        let mut slots = args.iter().map(encode).collect::<Vec<_>>();
+        slots.push(0);
+        let mut out = 0;
+        let mut steps = 0;
+        // SAFETY: the function was compiled with the `Entry` signature for exactly these argument types
+        let status = unsafe { entry(&mut steps, slots.as_ptr(), &mut out, 0) };
+        if status != 0 {
+            *deopts += 1;
+            if *deopts >= MAX_DEOPTS {
+                *spec = Spec::Failed;
+            }
+            return None;
+        }
+        Some(JitCall {
+            ret: ret.map(|ty| decode(ty, out)),
+            steps,
+        })
+    }
+
+    fn signature(&self) -> Signature {
+        let ptr = self.module.target_config().pointer_type();
+        let mut sig = self.module.make_signature();
+        sig.params.push(AbiParam::new(ptr));
+        sig.params.push(AbiParam::new(ptr));
+        sig.params.push(AbiParam::new(ptr));
+        sig.params.push(AbiParam::new(types::I32));
+        sig.returns.push(AbiParam::new(types::I8));
+        sig
+    }
+
+    /// Compiles a function for the given argument types, along with everything it calls.
+    /// `active` holds the specialisations currently being compiled, innermost last
+    fn specialize(
+        &mut self,
+        funcs: &HashMap<u32, FuncDef>,
+        key: &(u32, Vec<Ty>),
+        active: &mut Vec<(u32, Vec<Ty>)>,
+    ) -> Option<Callee> {
+        match self.specs.get(key) {
+            Some(Spec::Ready { func, ret, .. }) => return Some(Callee::Known(*func, *ret)),
+            Some(Spec::Failed) => return None,
+            // Mutual recursion isn't compiled
+            Some(Spec::InProgress { .. }) if active.last() != Some(key) => return None,
+            Some(Spec::InProgress { func, ret }) => {
+                return Some(ret.map_or(Callee::Pending, |ret| Callee::Known(*func, ret)))
+            }
+            None => {}
+        }
+        let def = funcs.get(&key.0)?;
+        if def.args as usize != key.1.len() || !def.body.iter().all(is_supported) {
+            self.specs.insert(key.clone(), Spec::Failed);
+            return None;
+        }
+        let name = format!("gvm_{}_{}", key.0, self.specs.len());
+        let func = self
+            .module
+            .declare_function(&name, Linkage::Local, &self.signature())
+            .ok()?;
+        self.specs
+            .insert(key.clone(), Spec::InProgress { func, ret: None });
+
+        active.push(key.clone());
+        let res = self
+            .infer(funcs, def, key, active)
+            .filter(|types| self.define(func, def, &key.1, types))
+            .map(|types| types.ret.flatten());
+        active.pop();
+
+        if let Some(ret) = res {
+            self.specs.insert(
+                key.clone(),
+                Spec::Ready {
+                    func,
+                    ret,
+                    entry: None,
+                    deopts: 0,
+                },
+            );
+            Some(Callee::Known(func, ret))
+        } else {
+            self.specs.insert(key.clone(), Spec::Failed);
+            None
+        }
+    }
+
+    /// Infers the type of every stack slot and local, guessing the return type of
+    /// recursive calls from the paths that don't recurse
+    fn infer(
+        &mut self,
+        funcs: &HashMap<u32, FuncDef>,
+        def: &FuncDef,
+        key: &(u32, Vec<Ty>),
+        active: &mut Vec<(u32, Vec<Ty>)>,
+    ) -> Option<Types> {
+        let types = self.infer_once(funcs, def, &key.1, active)?;
+        if !types.pending {
+            return Some(types);
+        }
+        let ret = types.ret?;
+        if let Some(Spec::InProgress { ret: guess, .. }) = self.specs.get_mut(key) {
+            *guess = Some(ret);
+        }
+        let types = self.infer_once(funcs, def, &key.1, active)?;
+        (!types.pending && types.ret == Some(ret)).then_some(types)
+    }
+
+    fn infer_once(
+        &mut self,
+        funcs: &HashMap<u32, FuncDef>,
+        def: &FuncDef,
+        args: &[Ty],
+        active: &mut Vec<(u32, Vec<Ty>)>,
+    ) -> Option<Types> {
+        let mut types = Types {
+            shapes: vec![None; def.body.len()],
+            calls: HashMap::new(),
+            ret: None,
+            pending: false,
+        };
+        let mut queue = VecDeque::from([0]);
+        types.shapes.first_mut()?.replace(Shape::default());
+        while let Some(index) = queue.pop_front() {
+            let mut shape = types.shapes[index].clone()?;
+            let instr = def.body.get(index)?;
+            let flow = self.step(funcs, instr, index, args, &mut shape, &mut types, active)?;
+            let next = match flow {
+                Flow::Next => vec![index + 1],
+                Flow::Jump(target) => vec![target as usize],
+                Flow::Branch(target) => vec![target as usize, index + 1],
+                Flow::Stop => vec![],
+            };
+            for next in next {
+                let merged = match types.shapes.get(next)? {
+                    None => shape.clone(),
+                    Some(old) => old.merge(&shape)?,
+                };
+                if types.shapes[next].as_ref() != Some(&merged) {
+                    types.shapes[next] = Some(merged);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Some(types)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn step(
+        &mut self,
+        funcs: &HashMap<u32, FuncDef>,
+        instr: &ByteCode,
+        index: usize,
+        args: &[Ty],
+        shape: &mut Shape,
+        types: &mut Types,
+        active: &mut Vec<(u32, Vec<Ty>)>,
+    ) -> Option<Flow> {
+        let stack = &mut shape.stack;
+        match instr {
+            ByteCode::Push(lit) => stack.push(match lit {
+                Literal::Int(_) => Ty::Int,
+                Literal::Float(_) => Ty::Float,
+                Literal::Bool(_) => Ty::Bool,
+                _ => return None,
+            }),
+            ByteCode::Copy => stack.push(*stack.last()?),
+            ByteCode::Pop => {
+                stack.pop()?;
+            }
+            ByteCode::NewLocal(id) | ByteCode::SetLocal(id) => {
+                let ty = stack.pop()?;
+                shape.locals.insert(*id, ty);
+            }
+            ByteCode::GetLocal(id) => stack.push(*shape.locals.get(id)?),
+            ByteCode::Param(id) => stack.push(*args.get(*id as usize)?),
+            ByteCode::Je(target) | ByteCode::Jne(target) => {
+                (stack.pop()? == Ty::Bool).then_some(())?;
+                return Some(Flow::Branch(*target));
+            }
+            ByteCode::Jmp(target) => return Some(Flow::Jump(*target)),
+            ByteCode::Add | ByteCode::Sub => {
+                let (a, b) = (stack.pop()?, stack.pop()?);
+                (a == b && a != Ty::Bool).then_some(())?;
+                stack.push(a);
+            }
+            ByteCode::Mul | ByteCode::Div => match (stack.pop()?, stack.pop()?) {
+                (Ty::Int, Ty::Int) => stack.push(Ty::Int),
+                (Ty::Int | Ty::Float, Ty::Int | Ty::Float) => stack.push(Ty::Float),
+                _ => return None,
+            },
+            ByteCode::Mod | ByteCode::Min | ByteCode::Max => {
+                (stack.pop()? == Ty::Int && stack.pop()? == Ty::Int).then_some(())?;
+                stack.push(Ty::Int);
+            }
+            ByteCode::Lt | ByteCode::Gt | ByteCode::Lte | ByteCode::Gte => {
+                let (a, b) = (stack.pop()?, stack.pop()?);
+                (a == b && a != Ty::Bool).then_some(())?;
+                stack.push(Ty::Bool);
+            }
+            ByteCode::Eq | ByteCode::Neq => {
+                stack.pop()?;
+                stack.pop()?;
+                stack.push(Ty::Bool);
+            }
+            ByteCode::And | ByteCode::Or => {
+                (stack.pop()? == Ty::Bool && stack.pop()? == Ty::Bool).then_some(())?;
+                stack.push(Ty::Bool);
+            }
+            ByteCode::Not => (*stack.last()? == Ty::Bool).then_some(())?,
+            ByteCode::Abs | ByteCode::Floor => (*stack.last()? != Ty::Bool).then_some(())?,
+            ByteCode::Sqrt | ByteCode::ToInt | ByteCode::ToFloat => {
+                (stack.pop()? != Ty::Bool).then_some(())?;
+                stack.push(match instr {
+                    ByteCode::ToInt => Ty::Int,
+                    _ => Ty::Float,
+                });
+            }
+            ByteCode::Call(id) | ByteCode::TailCall(id) => {
+                let count = funcs.get(id)?.args as usize;
+                let args = stack.split_off(stack.len().checked_sub(count)?);
+                match self.specialize(funcs, &(*id, args), active)? {
+                    Callee::Known(func, ret) => {
+                        types.calls.insert(index, (func, count, ret));
+                        if matches!(instr, ByteCode::TailCall(_)) {
+                            return types.returns(ret);
+                        }
+                        stack.extend(ret);
+                    }
+                    Callee::Pending => {
+                        types.pending = true;
+                        return Some(Flow::Stop);
+                    }
+                }
+            }
+            ByteCode::Return => return types.returns(stack.last().copied()),
+            _ => return None,
+        }
+        Some(Flow::Next)
+    }
+
+    fn define(&mut self, func: FuncId, def: &FuncDef, args: &[Ty], types: &Types) -> bool {
+        let signature = self.signature();
+        let Jit {
+            module,
+            ctx,
+            builder_ctx,
+            ..
+        } = self;
+        ctx.func.signature = signature;
+        let mut lower = Lower {
+            fb: FunctionBuilder::new(&mut ctx.func, builder_ctx),
+            vars: HashMap::new(),
+            deopt: None,
+        };
+        lower.body(module, def, args, types);
+        lower.fb.seal_all_blocks();
+        lower.fb.finalize();
+        let ok = module.define_function(func, ctx).is_ok();
+        module.clear_context(ctx);
+        ok
+    }
+
+    /// Makes every newly compiled function callable
+    fn finalize(&mut self) {
+        if self.module.finalize_definitions().is_err() {
+            return;
+        }
+        for spec in self.specs.values_mut() {
+            if let Spec::Ready { func, entry, .. } = spec {
+                if entry.is_none() {
+                    let ptr = self.module.get_finalized_function(*func);
+                    // SAFETY: every specialisation is defined with the `Entry` signature
+                    *entry = Some(unsafe { std::mem::transmute::<*const u8, Entry>(ptr) });
+                }
+            }
+        }
+    }
+}
+
+fn is_supported(instr: &ByteCode) -> bool {
+    matches!(
+        instr,
+        ByteCode::Push(Literal::Int(_) | Literal::Float(_) | Literal::Bool(_))
+            | ByteCode::Copy
+            | ByteCode::Pop
+            | ByteCode::NewLocal(_)
+            | ByteCode::GetLocal(_)
+            | ByteCode::SetLocal(_)
+            | ByteCode::Param(_)
+            | ByteCode::Je(_)
+            | ByteCode::Jne(_)
+            | ByteCode::Jmp(_)
+            | ByteCode::Add
+            | ByteCode::Sub
+            | ByteCode::Mul
+            | ByteCode::Div
+            | ByteCode::Mod
+            | ByteCode::Lt
+            | ByteCode::Gt
+            | ByteCode::Lte
+            | ByteCode::Gte
+            | ByteCode::Eq
+            | ByteCode::Neq
+            | ByteCode::And
+            | ByteCode::Or
+            | ByteCode::Not
+            | ByteCode::Sqrt
+            | ByteCode::Abs
+            | ByteCode::Floor
+            | ByteCode::Min
+            | ByteCode::Max
+            | ByteCode::ToInt
+            | ByteCode::ToFloat
+            | ByteCode::Call(_)
+            | ByteCode::TailCall(_)
+            | ByteCode::Return
+    )
+}
+
+fn encode(item: &StackItem) -> u64 {
+    let mut bytes = [0; 8];
+    match item {
+        StackItem::Int(val) => bytes[..4].copy_from_slice(&val.to_ne_bytes()),
+        StackItem::Float(val) => bytes[..4].copy_from_slice(&val.to_ne_bytes()),
+        StackItem::Bool(val) => bytes[0] = u8::from(*val),
+        _ => unreachable!("Only scalars are passed to compiled code"),
+    }
+    u64::from_ne_bytes(bytes)
+}
+
+fn decode(ty: Ty, raw: u64) -> StackItem {
+    let bytes = raw.to_ne_bytes();
+    let word = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    match ty {
+        Ty::Int => StackItem::Int(i32::from_ne_bytes(word)),
+        Ty::Float => StackItem::Float(f32::from_ne_bytes(word)),
+        Ty::Bool => StackItem::Bool(bytes[0] != 0),
+    }
+}
+
+/// The types on the stack and in locals before an instruction
+#[derive(Clone, Default, PartialEq)]
+struct Shape {
+    stack: Vec<Ty>,
+    locals: HashMap<u32, Ty>,
+}
+
+impl Shape {
+    /// Locals that disagree are dropped, but the stack has to match
+    fn merge(&self, other: &Shape) -> Option<Shape> {
+        (self.stack == other.stack).then(|| Shape {
+            stack: self.stack.clone(),
+            locals: self
+                .locals
+                .iter()
+                .filter(|(id, ty)| other.locals.get(id) == Some(ty))
+                .map(|(id, ty)| (*id, *ty))
+                .collect(),
+        })
+    }
+}
+
+struct Types {
+    /// `None` for unreachable instructions
+    shapes: Vec<Option<Shape>>,
+    /// The function and argument count of each call
+    calls: HashMap<usize, (FuncId, usize, Option<Ty>)>,
+    /// The type returned, if any return was reached
+    ret: Option<Option<Ty>>,
+    /// Whether a recursive call cut off some paths
+    pending: bool,
+}
+
+impl Types {
+    /// Records a return of `ret`, which has to match every other return
+    fn returns(&mut self, ret: Option<Ty>) -> Option<Flow> {
+        if self.ret.is_some_and(|found| found != ret) {
+            return None;
+        }
+        self.ret = Some(ret);
+        Some(Flow::Stop)
+    }
+}
+
+enum Flow {
+    Next,
+    Jump(u32),
+    Branch(u32),
+    Stop,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Slot {
+    Stack(usize),
+    Local(u32),
+}
+
+/// Translates a function body to Cranelift IR, keeping each stack slot and local in a variable
+struct Lower<'a> {
+    fb: FunctionBuilder<'a>,
+    vars: HashMap<(Slot, Ty), Variable>,
+    deopt: Option<Block>,
+}
+
+impl Lower<'_> {
+    fn var(&mut self, slot: Slot, ty: Ty) -> Variable {
+        let next = self.vars.len();
+        *self.vars.entry((slot, ty)).or_insert_with(|| {
+            let var = Variable::from_u32(next as u32);
+            self.fb.declare_var(var, ty.clif());
+            var
+        })
+    }
+
+    fn get(&mut self, slot: Slot, ty: Ty) -> Value {
+        let var = self.var(slot, ty);
+        self.fb.use_var(var)
+    }
+
+    fn set(&mut self, slot: Slot, ty: Ty, value: Value) {
+        let var = self.var(slot, ty);
+        self.fb.def_var(var, value);
+    }
+
+    /// Leaves compiled code if `cond` holds, so the interpreter redoes the call
+    fn guard(&mut self, cond: Value) {
+        let deopt = *self.deopt.get_or_insert_with(|| self.fb.create_block());
+        let cont = self.fb.create_block();
+        self.fb.ins().brif(cond, deopt, &[], cont, &[]);
+        self.fb.switch_to_block(cont);
+    }
+
+    /// Lowers an operation on the top two values, matching the interpreter's semantics
+    fn binary(&mut self, instr: &ByteCode, a: Value, a_ty: Ty, b: Value, b_ty: Ty) -> (Ty, Value) {
+        match (instr, a_ty, b_ty) {
+            (ByteCode::Add, Ty::Int, _) => {
+                let (value, overflow) = self.fb.ins().sadd_overflow(a, b);
+                self.guard(overflow);
+                (Ty::Int, value)
+            }
+            (ByteCode::Sub, Ty::Int, _) => {
+                let (value, overflow) = self.fb.ins().ssub_overflow(a, b);
+                self.guard(overflow);
+                (Ty::Int, value)
+            }
+            (ByteCode::Mul, Ty::Int, Ty::Int) => {
+                let (value, overflow) = self.fb.ins().smul_overflow(a, b);
+                self.guard(overflow);
+                (Ty::Int, value)
+            }
+            (ByteCode::Div | ByteCode::Mod, Ty::Int, Ty::Int) => {
+                // Dividing by zero panics and `i32::MIN / -1` overflows, so let the interpreter handle both
+                let zero = self.fb.ins().icmp_imm(IntCC::Equal, b, 0);
+                let minus_one = self.fb.ins().icmp_imm(IntCC::Equal, b, -1);
+                let bail = self.fb.ins().bor(zero, minus_one);
+                self.guard(bail);
+                let value = if matches!(instr, ByteCode::Div) {
+                    self.fb.ins().sdiv(a, b)
+                } else {
+                    self.fb.ins().srem(a, b)
+                };
+                (Ty::Int, value)
+            }
+            (ByteCode::Add, ..) => (Ty::Float, self.fb.ins().fadd(a, b)),
+            (ByteCode::Sub, ..) => (Ty::Float, self.fb.ins().fsub(a, b)),
+            (ByteCode::Mul | ByteCode::Div, ..) => {
+                let a = self.as_float(a, a_ty);
+                let b = self.as_float(b, b_ty);
+                let value = if matches!(instr, ByteCode::Mul) {
+                    self.fb.ins().fmul(a, b)
+                } else {
+                    self.fb.ins().fdiv(a, b)
+                };
+                (Ty::Float, value)
+            }
+            (ByteCode::Min, ..) => (Ty::Int, self.fb.ins().smin(a, b)),
+            (ByteCode::Max, ..) => (Ty::Int, self.fb.ins().smax(a, b)),
+            (ByteCode::Lt | ByteCode::Gt | ByteCode::Lte | ByteCode::Gte, Ty::Float, _) => {
+                let cc = match instr {
+                    ByteCode::Lt => FloatCC::LessThan,
+                    ByteCode::Gt => FloatCC::GreaterThan,
+                    ByteCode::Lte => FloatCC::LessThanOrEqual,
+                    _ => FloatCC::GreaterThanOrEqual,
+                };
+                (Ty::Bool, self.fb.ins().fcmp(cc, a, b))
+            }
+            (ByteCode::Lt | ByteCode::Gt | ByteCode::Lte | ByteCode::Gte, ..) => {
+                let cc = match instr {
+                    ByteCode::Lt => IntCC::SignedLessThan,
+                    ByteCode::Gt => IntCC::SignedGreaterThan,
+                    ByteCode::Lte => IntCC::SignedLessThanOrEqual,
+                    _ => IntCC::SignedGreaterThanOrEqual,
+                };
+                (Ty::Bool, self.fb.ins().icmp(cc, a, b))
+            }
+            (ByteCode::Eq | ByteCode::Neq, ..) if a_ty != b_ty => {
+                let value = self
+                    .fb
+                    .ins()
+                    .iconst(types::I8, i64::from(matches!(instr, ByteCode::Neq)));
+                (Ty::Bool, value)
+            }
+            // `NotEqual` is unordered, so `NaN != NaN` like in Rust
+            (ByteCode::Eq, Ty::Float, _) => (Ty::Bool, self.fb.ins().fcmp(FloatCC::Equal, a, b)),
+            (ByteCode::Neq, Ty::Float, _) => {
+                (Ty::Bool, self.fb.ins().fcmp(FloatCC::NotEqual, a, b))
+            }
+            (ByteCode::Eq, ..) => (Ty::Bool, self.fb.ins().icmp(IntCC::Equal, a, b)),
+            (ByteCode::Neq, ..) => (Ty::Bool, self.fb.ins().icmp(IntCC::NotEqual, a, b)),
+            (ByteCode::And, ..) => (Ty::Bool, self.fb.ins().band(a, b)),
+            (ByteCode::Or, ..) => (Ty::Bool, self.fb.ins().bor(a, b)),
+            _ => unreachable!("Operand types are checked during inference"),
+        }
+    }
+
+    fn unary(&mut self, instr: &ByteCode, value: Value, ty: Ty) -> (Ty, Value) {
+        match (instr, ty) {
+            (ByteCode::Sqrt, _) => {
+                let value = self.as_float(value, ty);
+                (Ty::Float, self.fb.ins().sqrt(value))
+            }
+            (ByteCode::Abs, Ty::Int) => {
+                let min = self
+                    .fb
+                    .ins()
+                    .icmp_imm(IntCC::Equal, value, i64::from(i32::MIN));
+                self.guard(min);
+                (Ty::Int, self.fb.ins().iabs(value))
+            }
+            (ByteCode::Abs, _) => (Ty::Float, self.fb.ins().fabs(value)),
+            (ByteCode::Floor, Ty::Float) => (Ty::Float, self.fb.ins().floor(value)),
+            // Saturates like `as`
+            (ByteCode::ToInt, Ty::Float) => {
+                (Ty::Int, self.fb.ins().fcvt_to_sint_sat(types::I32, value))
+            }
+            (ByteCode::ToFloat, _) => (Ty::Float, self.as_float(value, ty)),
+            _ => (ty, value),
+        }
+    }
+
+    fn as_float(&mut self, value: Value, ty: Ty) -> Value {
+        match ty {
+            Ty::Int => self.fb.ins().fcvt_from_sint(types::F32, value),
+            _ => value,
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn body(&mut self, module: &mut JITModule, def: &FuncDef, args: &[Ty], types: &Types) {
+        let mut leaders = BTreeSet::from([0]);
+        for (index, instr) in def.body.iter().enumerate() {
+            if types.shapes[index].is_none() {
+                continue;
+            }
+            match instr {
+                ByteCode::Je(target) | ByteCode::Jne(target) => {
+                    leaders.insert(*target as usize);
+                    leaders.insert(index + 1);
+                }
+                ByteCode::Jmp(target) => {
+                    leaders.insert(*target as usize);
+                }
+                _ => {}
+            }
+        }
+        let blocks = leaders
+            .iter()
+            .map(|index| (*index, self.fb.create_block()))
+            .collect::<HashMap<_, _>>();
+
+        let entry = self.fb.create_block();
+        self.fb.append_block_params_for_function_params(entry);
+        self.fb.switch_to_block(entry);
+        let params = self.fb.block_params(entry).to_vec();
+        let (steps, args_ptr, ret_ptr, call_depth) = (params[0], params[1], params[2], params[3]);
+        let too_deep = self
+            .fb
+            .ins()
+            .icmp_imm(IntCC::SignedGreaterThan, call_depth, MAX_DEPTH);
+        self.guard(too_deep);
+        self.fb.ins().jump(blocks[&0], &[]);
+
+        let flags = MemFlags::trusted();
+        let mut open = false;
+        for (index, instr) in def.body.iter().enumerate() {
+            let Some(shape) = &types.shapes[index] else {
+                continue;
+            };
+            if let Some(block) = blocks.get(&index) {
+                if open {
+                    self.fb.ins().jump(*block, &[]);
+                }
+                self.fb.switch_to_block(*block);
+                // Count the instructions up to the end of the block in one go
+                let len = def.body[index..]
+                    .iter()
+                    .enumerate()
+                    .position(|(offset, instr)| {
+                        matches!(
+                            instr,
+                            ByteCode::Je(_)
+                                | ByteCode::Jne(_)
+                                | ByteCode::Jmp(_)
+                                | ByteCode::TailCall(_)
+                                | ByteCode::Return
+                        ) || leaders.contains(&(index + offset + 1))
+                    })
+                    .map_or(def.body.len() - index, |end| end + 1);
+                let old = self.fb.ins().load(types::I64, flags, steps, 0);
+                let new = self.fb.ins().iadd_imm(old, len as i64);
+                self.fb.ins().store(flags, new, steps, 0);
+            }
+            open = true;
+
+            let depth = shape.stack.len();
+            let top = |n: usize| (Slot::Stack(depth - n), shape.stack[depth - n]);
+            match instr {
+                ByteCode::Push(lit) => {
+                    let (ty, value) = match lit {
+                        Literal::Int(num) => {
+                            (Ty::Int, self.fb.ins().iconst(types::I32, i64::from(*num)))
+                        }
+                        Literal::Float(num) => (Ty::Float, self.fb.ins().f32const(*num)),
+                        Literal::Bool(val) => {
+                            (Ty::Bool, self.fb.ins().iconst(types::I8, i64::from(*val)))
+                        }
+                        _ => unreachable!(),
+                    };
+                    self.set(Slot::Stack(depth), ty, value);
+                }
+                ByteCode::Copy => {
+                    let (slot, ty) = top(1);
+                    let value = self.get(slot, ty);
+                    self.set(Slot::Stack(depth), ty, value);
+                }
+                ByteCode::Pop => {}
+                ByteCode::NewLocal(id) | ByteCode::SetLocal(id) => {
+                    let (slot, ty) = top(1);
+                    let value = self.get(slot, ty);
+                    self.set(Slot::Local(*id), ty, value);
+                }
+                ByteCode::GetLocal(id) => {
+                    let ty = shape.locals[id];
+                    let value = self.get(Slot::Local(*id), ty);
+                    self.set(Slot::Stack(depth), ty, value);
+                }
+                ByteCode::Param(id) => {
+                    let ty = args[*id as usize];
+                    let value = self
+                        .fb
+                        .ins()
+                        .load(ty.clif(), flags, args_ptr, (*id * 8) as i32);
+                    self.set(Slot::Stack(depth), ty, value);
+                }
+                ByteCode::Je(target) | ByteCode::Jne(target) => {
+                    let (slot, ty) = top(1);
+                    let cond = self.get(slot, ty);
+                    let (target, next) = (blocks[&(*target as usize)], blocks[&(index + 1)]);
+                    if matches!(instr, ByteCode::Je(_)) {
+                        self.fb.ins().brif(cond, target, &[], next, &[]);
+                    } else {
+                        self.fb.ins().brif(cond, next, &[], target, &[]);
+                    }
+                    open = false;
+                }
+                ByteCode::Jmp(target) => {
+                    self.fb.ins().jump(blocks[&(*target as usize)], &[]);
+                    open = false;
+                }
+                ByteCode::Add
+                | ByteCode::Sub
+                | ByteCode::Mul
+                | ByteCode::Div
+                | ByteCode::Mod
+                | ByteCode::Min
+                | ByteCode::Max
+                | ByteCode::Lt
+                | ByteCode::Gt
+                | ByteCode::Lte
+                | ByteCode::Gte
+                | ByteCode::Eq
+                | ByteCode::Neq
+                | ByteCode::And
+                | ByteCode::Or => {
+                    let ((a_slot, a_ty), (b_slot, b_ty)) = (top(2), top(1));
+                    let a = self.get(a_slot, a_ty);
+                    let b = self.get(b_slot, b_ty);
+                    let (ty, value) = self.binary(instr, a, a_ty, b, b_ty);
+                    self.set(a_slot, ty, value);
+                }
+                ByteCode::Not => {
+                    let (slot, ty) = top(1);
+                    let value = self.get(slot, ty);
+                    let value = self.fb.ins().bxor_imm(value, 1);
+                    self.set(slot, ty, value);
+                }
+                ByteCode::Sqrt
+                | ByteCode::Abs
+                | ByteCode::Floor
+                | ByteCode::ToInt
+                | ByteCode::ToFloat => {
+                    let (slot, ty) = top(1);
+                    let value = self.get(slot, ty);
+                    let (ty, value) = self.unary(instr, value, ty);
+                    self.set(slot, ty, value);
+                }
+                ByteCode::Call(_) | ByteCode::TailCall(_) => {
+                    let (func, count, ret) = types.calls[&index];
+                    let ptr = module.target_config().pointer_type();
+                    let args_slot = self.fb.create_sized_stack_slot(StackSlotData::new(
+                        StackSlotKind::ExplicitSlot,
+                        (count.max(1) * 8) as u32,
+                        3,
+                    ));
+                    for n in 0..count {
+                        let (slot, ty) = top(count - n);
+                        let value = self.get(slot, ty);
+                        self.fb.ins().stack_store(value, args_slot, (n * 8) as i32);
+                    }
+                    let ret_slot = self.fb.create_sized_stack_slot(StackSlotData::new(
+                        StackSlotKind::ExplicitSlot,
+                        8,
+                        3,
+                    ));
+                    let args_addr = self.fb.ins().stack_addr(ptr, args_slot, 0);
+                    let ret_addr = self.fb.ins().stack_addr(ptr, ret_slot, 0);
+                    let callee = module.declare_func_in_func(func, self.fb.func);
+                    let next_depth = self.fb.ins().iadd_imm(call_depth, 1);
+                    let call = self
+                        .fb
+                        .ins()
+                        .call(callee, &[steps, args_addr, ret_addr, next_depth]);
+                    let status = self.fb.inst_results(call)[0];
+                    self.guard(status);
+                    let value = ret.map(|ty| self.fb.ins().stack_load(ty.clif(), ret_slot, 0));
+                    if let ByteCode::TailCall(_) = instr {
+                        // Native code gets no frame reuse, but returning straight away keeps the step count
+                        if let Some(value) = value {
+                            self.fb.ins().store(flags, value, ret_ptr, 0);
+                        }
+                        let ok = self.fb.ins().iconst(types::I8, 0);
+                        self.fb.ins().return_(&[ok]);
+                        open = false;
+                    } else if let (Some(ty), Some(value)) = (ret, value) {
+                        self.set(Slot::Stack(depth - count), ty, value);
+                    }
+                }
+                ByteCode::Return => {
+                    if depth > 0 {
+                        let (slot, ty) = top(1);
+                        let value = self.get(slot, ty);
+                        self.fb.ins().store(flags, value, ret_ptr, 0);
+                    }
+                    let ok = self.fb.ins().iconst(types::I8, 0);
+                    self.fb.ins().return_(&[ok]);
+                    open = false;
+                }
+                _ => unreachable!("Unsupported instructions are rejected before compiling"),
+            }
+        }
+
+        if let Some(deopt) = self.deopt {
+            self.fb.switch_to_block(deopt);
+            let status = self.fb.ins().iconst(types::I8, 1);
+            self.fb.ins().return_(&[status]);
+        }
+    }
+}
+
+impl ProgramState<'_> {
+    /// Runs a call natively if the JIT has compiled it, returning false if it should be interpreted.
+    /// Compiled code can't be preempted, so it's only used while no other threads exist
+    pub fn call_jit(&mut self, id: u32, args: &[StackItem]) -> bool {
+        if !self.threads.is_empty() {
+            return false;
+        }
+        let Some(jit) = &mut self.jit else {
+            return false;
+        };
+        let Some(call) = jit.call(self.funcs, id, args) else {
+            return false;
+        };
+        if let Some(ret) = call.ret {
+            self.push(ret);
+        }
+        // Use up fuel as if the call had been interpreted, so threads spawned later are scheduled the same
+        let used = (u64::from(FUEL - self.fuel) + call.steps) % u64::from(FUEL);
+        self.fuel = FUEL - used as u32;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        format::{func::FuncDef, instr::ByteCode},
+        text::decode::parser::parse_text_file,
+        vm::{scope::Scope, stack::StackItem, state::ProgramState, thread::FUEL},
+    };
+
+    use super::Jit;
+
+    const PROGRAM: &str = r#"
+        func 1 1 "fib" 1 1 0
+        param 0 push 2 lt jne 6 param 0 return
+        param 0 push 1 sub call 1 param 0 push 2 sub call 1 add return
+
+        func 2 1 "sum" 5 1 0
+        push 0 new 0 push 0 new 1 push 0.0 new 2
+        get 0 param 0 lt jne 30
+        get 1 get 0 get 0 mul push 3 div push 7 mod add set 1
+        get 2 get 0 sqrt add set 2
+        get 0 push 1 add set 0 jmp 6
+        get 2 to_int get 1 add return
+
+        func 3 2 "div" 12 1 0
+        param 0 param 1 div return
+    "#;
+
+    fn program() -> HashMap<u32, FuncDef> {
+        parse_text_file(PROGRAM).unwrap().funcs
+    }
+
+    /// Calls `id` from a stub frame and runs until it returns, giving the stub's stack and the fuel left
+    fn run(
+        funcs: &HashMap<u32, FuncDef>,
+        id: u32,
+        args: &[StackItem],
+        jit: Option<Jit>,
+    ) -> (Vec<StackItem>, u32) {
+        let call = [ByteCode::Call(id)];
+        let mut prog =
+            ProgramState::new(funcs, None, HashMap::new(), HashMap::new(), HashMap::new());
+        prog.jit = jit;
+        let mut scope = Scope::from_code(&call, u32::MAX);
+        scope.stack = args.to_vec();
+        prog.scopes.push(scope);
+        while prog.scopes.len() > 1 || prog.scope().index == 0 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+            if prog.fuel == 0 {
+                prog.fuel = FUEL;
+            }
+        }
+        (prog.scope().stack.clone(), prog.fuel)
+    }
+
+    #[test]
+    fn test_jit_matches_interpreter() {
+        let funcs = program();
+        let cases = [
+            (1, vec![StackItem::Int(1)]),
+            (1, vec![StackItem::Int(18)]),
+            (2, vec![StackItem::Int(0)]),
+            (2, vec![StackItem::Int(1000)]),
+            (3, vec![StackItem::Int(-7), StackItem::Int(2)]),
+            (3, vec![StackItem::Float(1.0), StackItem::Int(3)]),
+        ];
+        for (id, args) in cases {
+            let jit = Jit::new(1).unwrap();
+            assert_eq!(
+                run(&funcs, id, &args, Some(jit)),
+                run(&funcs, id, &args, None),
+                "calling {id} with {args:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_jit_compiles_hot_functions() {
+        let funcs = program();
+        let mut jit = Jit::new(3).unwrap();
+        let args = [StackItem::Int(10)];
+        assert!(jit.call(&funcs, 1, &args).is_none());
+        assert!(jit.call(&funcs, 1, &args).is_none());
+        let call = jit.call(&funcs, 1, &args).unwrap();
+        assert_eq!(call.ret, Some(StackItem::Int(55)));
+    }
+
+    #[test]
+    fn test_jit_leaves_errors_to_interpreter() {
+        let funcs = program();
+        let mut jit = Jit::new(1).unwrap();
+        let zero = [StackItem::Int(1), StackItem::Int(0)];
+        assert!(jit.call(&funcs, 3, &zero).is_none());
+        let overflow = [StackItem::Int(i32::MIN), StackItem::Int(-1)];
+        assert!(jit.call(&funcs, 3, &overflow).is_none());
+        let ok = [StackItem::Int(9), StackItem::Int(2)];
+        assert_eq!(
+            jit.call(&funcs, 3, &ok).unwrap().ret,
+            Some(StackItem::Int(4))
+        );
+    }
+}
@@ -1,6 +1,10 @@
 pub mod exec;
 pub mod heap;
+pub mod jit;
 pub mod scope;
+pub mod snapshot;
 pub mod stack;
 pub mod state;
 pub mod text;
+pub mod thread;
+pub mod trace;
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use broom::Handle;
+
 use crate::format::instr::ByteCode;
 
-use super::stack::StackItem;
+use super::{heap::HeapItem, stack::StackItem};
 
 pub struct Scope<'code> {
     pub args: Vec<StackItem>,
@@ -11,6 +13,9 @@ pub struct Scope<'code> {
     pub code: &'code [ByteCode],
     pub index: usize,
     pub id: u32,
+    pub generator: Option<Handle<HeapItem>>,
+    /// How many frames were replaced by tail calls to get here
+    pub elided: u32,
 }
 
 impl<'code> Scope<'code> {
@@ -22,6 +27,8 @@ impl<'code> Scope<'code> {
             code,
             index: 0,
             id,
+            generator: None,
+            elided: 0,
         }
     }
 
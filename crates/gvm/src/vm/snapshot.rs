@@ -0,0 +1,479 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    fs,
+    path::PathBuf,
+};
+
+use broom::Handle;
+
+use super::{heap::HeapItem, scope::Scope, stack::StackItem, state::ProgramState};
+
+/// Where to write a snapshot of the program when it panics
+#[derive(Default)]
+pub struct DumpOptions {
+    pub json: Option<PathBuf>,
+    pub dot: Option<PathBuf>,
+}
+
+/// A copy of the call stack and every heap object reachable from it
+pub struct Snapshot {
+    pub frames: Vec<FrameSnapshot>,
+    pub heap: Vec<HeapSnapshot>,
+}
+
+pub struct FrameSnapshot {
+    pub thread: u32,
+    pub func: String,
+    pub file: String,
+    pub pos: (u16, u16),
+    pub args: Vec<ValueSnapshot>,
+    pub locals: Vec<(u32, ValueSnapshot)>,
+    pub stack: Vec<ValueSnapshot>,
+    /// Frames replaced by tail calls just before this one
+    pub elided: u32,
+}
+
+#[derive(Clone, Copy)]
+pub enum ValueSnapshot {
+    Int(i32),
+    Float(f32),
+    Char(char),
+    Bool(bool),
+    Ref(usize),
+}
+
+pub struct HeapSnapshot {
+    pub id: usize,
+    pub data: HeapDataSnapshot,
+}
+
+pub enum HeapDataSnapshot {
+    Object {
+        label: String,
+        fields: Vec<ValueSnapshot>,
+    },
+    String(String),
+    Dyn {
+        type_id: u64,
+        value: ValueSnapshot,
+    },
+    Channel(Vec<ValueSnapshot>),
+    Generator {
+        func: String,
+        done: bool,
+        values: Vec<ValueSnapshot>,
+    },
+}
+
+struct HeapWalker<'a, 'code> {
+    state: &'a ProgramState<'code>,
+    ids: HashMap<Handle<HeapItem>, usize>,
+    queue: VecDeque<Handle<HeapItem>>,
+}
+
+impl HeapWalker<'_, '_> {
+    fn value(&mut self, item: &StackItem) -> ValueSnapshot {
+        match item {
+            StackItem::Int(i) => ValueSnapshot::Int(*i),
+            StackItem::Float(f) => ValueSnapshot::Float(*f),
+            StackItem::Char(c) => ValueSnapshot::Char(*c),
+            StackItem::Bool(b) => ValueSnapshot::Bool(*b),
+            StackItem::Heap(handle) => {
+                let next = self.ids.len();
+                let id = *self.ids.entry(*handle).or_insert_with(|| {
+                    self.queue.push_back(*handle);
+                    next
+                });
+                ValueSnapshot::Ref(id)
+            }
+        }
+    }
+
+    fn frame(&mut self, thread: u32, scope: &Scope) -> FrameSnapshot {
+        let func = &self.state.funcs[&scope.id];
+        let mut locals = scope
+            .locals
+            .iter()
+            .map(|(id, item)| (*id, *item))
+            .collect::<Vec<_>>();
+        locals.sort_by_key(|(id, _)| *id);
+        FrameSnapshot {
+            thread,
+            func: func.name.clone(),
+            file: self
+                .state
+                .file_names
+                .get(&func.file)
+                .cloned()
+                .unwrap_or_default(),
+            pos: self.state.scope_pos(scope),
+            args: scope.args.iter().map(|arg| self.value(arg)).collect(),
+            locals: locals
+                .iter()
+                .map(|(id, item)| (*id, self.value(item)))
+                .collect(),
+            stack: scope.stack.iter().map(|item| self.value(item)).collect(),
+            elided: scope.elided,
+        }
+    }
+
+    fn heap(&mut self) -> Vec<HeapSnapshot> {
+        let mut heap = vec![];
+        while let Some(handle) = self.queue.pop_front() {
+            let id = self.ids[&handle];
+            let Some(item) = self.state.heap.get(handle) else {
+                continue;
+            };
+            let data = match item {
+                HeapItem::Object(type_id, fields) => HeapDataSnapshot::Object {
+                    label: self
+                        .state
+                        .type_names
+                        .get(type_id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Object({type_id})")),
+                    fields: fields.iter().map(|field| self.value(field)).collect(),
+                },
+                HeapItem::String(text) => HeapDataSnapshot::String(text.clone()),
+                HeapItem::Dyn(type_id, item) => HeapDataSnapshot::Dyn {
+                    type_id: *type_id,
+                    value: self.value(item),
+                },
+                HeapItem::Channel(items) => {
+                    HeapDataSnapshot::Channel(items.iter().map(|item| self.value(item)).collect())
+                }
+                HeapItem::Generator(frame) => HeapDataSnapshot::Generator {
+                    func: self.state.funcs[&frame.func].name.clone(),
+                    done: frame.done,
+                    values: frame
+                        .args
+                        .iter()
+                        .chain(frame.locals.values())
+                        .chain(&frame.stack)
+                        .map(|item| self.value(item))
+                        .collect(),
+                },
+            };
+            heap.push(HeapSnapshot { id, data });
+        }
+        heap
+    }
+}
+
+impl ProgramState<'_> {
+    pub fn snapshot(&self) -> Snapshot {
+        let mut walker = HeapWalker {
+            state: self,
+            ids: HashMap::new(),
+            queue: VecDeque::new(),
+        };
+        let mut frames = self
+            .scopes
+            .iter()
+            .map(|scope| walker.frame(self.thread_id, scope))
+            .collect::<Vec<_>>();
+        for thread in &self.threads {
+            for scope in &thread.scopes {
+                frames.push(walker.frame(thread.id, scope));
+            }
+        }
+        let heap = walker.heap();
+        Snapshot { frames, heap }
+    }
+
+    /// Writes the snapshot to every path set in `self.dump`
+    pub fn dump_snapshot(&self) {
+        if self.dump.json.is_none() && self.dump.dot.is_none() {
+            return;
+        }
+        let snapshot = self.snapshot();
+        if let Some(path) = &self.dump.json {
+            fs::write(path, snapshot.to_json()).expect("Failed to write snapshot");
+        }
+        if let Some(path) = &self.dump.dot {
+            fs::write(path, snapshot.to_dot()).expect("Failed to write heap graph");
+        }
+    }
+}
+
+impl Snapshot {
+    pub fn to_json(&self) -> String {
+        let frames = self
+            .frames
+            .iter()
+            .map(FrameSnapshot::to_json)
+            .collect::<Vec<_>>()
+            .join(",");
+        let heap = self
+            .heap
+            .iter()
+            .map(HeapSnapshot::to_json)
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{{\"frames\":[{frames}],\"heap\":[{heap}]}}")
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph heap {\n    node [shape=record];\n");
+        for (index, frame) in self.frames.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    frame{index} [label=\"{}\", shape=box];",
+                escape(&format!(
+                    "#{} {} ({}:{}:{})",
+                    frame.thread, frame.func, frame.file, frame.pos.0, frame.pos.1
+                ))
+            );
+            let roots = frame
+                .args
+                .iter()
+                .chain(frame.locals.iter().map(|(_, value)| value))
+                .chain(&frame.stack);
+            for value in roots {
+                if let ValueSnapshot::Ref(id) = value {
+                    let _ = writeln!(dot, "    frame{index} -> obj{id};");
+                }
+            }
+        }
+        for item in &self.heap {
+            let (label, children) = match &item.data {
+                HeapDataSnapshot::Object { label, fields } => {
+                    let text = fields
+                        .iter()
+                        .map(|field| field.to_label())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    (format!("{label}({text})"), fields.clone())
+                }
+                HeapDataSnapshot::String(text) => (format!("{text:?}"), vec![]),
+                HeapDataSnapshot::Dyn { type_id, value } => {
+                    (format!("Dyn({type_id})"), vec![*value])
+                }
+                HeapDataSnapshot::Channel(items) => {
+                    (format!("Channel({} queued)", items.len()), items.clone())
+                }
+                HeapDataSnapshot::Generator { func, done, values } => {
+                    let state = if *done { "done" } else { "suspended" };
+                    (format!("Generator({func}, {state})"), values.clone())
+                }
+            };
+            let _ = writeln!(dot, "    obj{} [label=\"{}\"];", item.id, escape(&label));
+            for child in children {
+                if let ValueSnapshot::Ref(id) = child {
+                    let _ = writeln!(dot, "    obj{} -> obj{id};", item.id);
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FrameSnapshot {
+    fn to_json(&self) -> String {
+        let args = values_to_json(&self.args);
+        let stack = values_to_json(&self.stack);
+        let locals = self
+            .locals
+            .iter()
+            .map(|(id, value)| format!("\"{id}\":{}", value.to_json()))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\"thread\":{},\"func\":\"{}\",\"file\":\"{}\",\"line\":{},\"col\":{},\"args\":{args},\"locals\":{{{locals}}},\"stack\":{stack},\"elided\":{}}}",
+            self.thread,
+            escape(&self.func),
+            escape(&self.file),
+            self.pos.0,
+            self.pos.1,
+            self.elided,
+        )
+    }
+}
+
+impl HeapSnapshot {
+    fn to_json(&self) -> String {
+        match &self.data {
+            HeapDataSnapshot::Object { label, fields } => format!(
+                "{{\"id\":{},\"kind\":\"object\",\"label\":\"{}\",\"fields\":{}}}",
+                self.id,
+                escape(label),
+                values_to_json(fields)
+            ),
+            HeapDataSnapshot::String(text) => format!(
+                "{{\"id\":{},\"kind\":\"string\",\"value\":\"{}\"}}",
+                self.id,
+                escape(text)
+            ),
+            HeapDataSnapshot::Dyn { type_id, value } => format!(
+                "{{\"id\":{},\"kind\":\"dyn\",\"type\":{type_id},\"value\":{}}}",
+                self.id,
+                value.to_json()
+            ),
+            HeapDataSnapshot::Channel(items) => format!(
+                "{{\"id\":{},\"kind\":\"channel\",\"queued\":{}}}",
+                self.id,
+                values_to_json(items)
+            ),
+            HeapDataSnapshot::Generator { func, done, values } => format!(
+                "{{\"id\":{},\"kind\":\"generator\",\"func\":\"{}\",\"done\":{done},\"values\":{}}}",
+                self.id,
+                escape(func),
+                values_to_json(values)
+            ),
+        }
+    }
+}
+
+impl ValueSnapshot {
+    fn to_json(self) -> String {
+        match self {
+            ValueSnapshot::Int(i) => format!("{{\"int\":{i}}}"),
+            ValueSnapshot::Float(f) if f.is_finite() => format!("{{\"float\":{f}}}"),
+            ValueSnapshot::Float(f) => format!("{{\"float\":\"{f}\"}}"),
+            ValueSnapshot::Char(c) => format!("{{\"char\":\"{}\"}}", escape(&c.to_string())),
+            ValueSnapshot::Bool(b) => format!("{{\"bool\":{b}}}"),
+            ValueSnapshot::Ref(id) => format!("{{\"ref\":{id}}}"),
+        }
+    }
+
+    fn to_label(self) -> String {
+        match self {
+            ValueSnapshot::Int(i) => i.to_string(),
+            ValueSnapshot::Float(f) => f.to_string(),
+            ValueSnapshot::Char(c) => format!("{c:?}"),
+            ValueSnapshot::Bool(b) => b.to_string(),
+            ValueSnapshot::Ref(id) => format!("#{id}"),
+        }
+    }
+}
+
+fn values_to_json(values: &[ValueSnapshot]) -> String {
+    let values = values
+        .iter()
+        .map(|value| value.to_json())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("[{values}]")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        panic::{catch_unwind, AssertUnwindSafe},
+        process,
+    };
+
+    use crate::{text::decode::parser::parse_text_file, vm::state::ProgramState};
+
+    const PROGRAM: &str = r#"
+        file 0 "main.gib"
+        name 1 "Point"
+        entry 0
+        func 0 0 "main" 1 1 0
+        push 2 new 0 push "p" push 1 construct 1 2 push "boom" panic
+    "#;
+
+    fn run_until_panic<'code>(prog: &mut ProgramState<'code>) {
+        prog.scopes.push(prog.entry_scope());
+        for _ in 0..6 {
+            let instr = prog.next_instr();
+            prog.execute(instr);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_json() {
+        let file = parse_text_file(PROGRAM).unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        run_until_panic(&mut prog);
+        assert_eq!(
+            prog.snapshot().to_json(),
+            concat!(
+                r#"{"frames":[{"thread":0,"func":"main","file":"main.gib","line":1,"col":1,"#,
+                r#""args":[],"locals":{"0":{"int":2}},"stack":[{"ref":0},{"ref":1}],"elided":0}],"#,
+                r#""heap":[{"id":0,"kind":"object","label":"Point","fields":[{"int":1},{"ref":2}]},"#,
+                r#"{"id":1,"kind":"string","value":"boom"},"#,
+                r#"{"id":2,"kind":"string","value":"p"}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_snapshot_dot() {
+        let file = parse_text_file(PROGRAM).unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        run_until_panic(&mut prog);
+        assert_eq!(
+            prog.snapshot().to_dot(),
+            concat!(
+                "digraph heap {\n",
+                "    node [shape=record];\n",
+                "    frame0 [label=\"#0 main (main.gib:1:1)\", shape=box];\n",
+                "    frame0 -> obj0;\n",
+                "    frame0 -> obj1;\n",
+                "    obj0 [label=\"Point(1, #2)\"];\n",
+                "    obj0 -> obj2;\n",
+                "    obj1 [label=\"\\\"boom\\\"\"];\n",
+                "    obj2 [label=\"\\\"p\\\"\"];\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_panic_dumps_snapshot_with_message() {
+        let file = parse_text_file(PROGRAM).unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        let path = env::temp_dir().join(format!("gvm-snapshot-{}.json", process::id()));
+        prog.dump.json = Some(path.clone());
+        prog.captured = Some(String::new());
+        run_until_panic(&mut prog);
+        let instr = prog.next_instr();
+        let res = catch_unwind(AssertUnwindSafe(|| prog.execute(instr)));
+        let message = *res.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("boom\n"));
+        let json = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(json.contains(r#""stack":[{"ref":0},{"ref":1}]"#));
+        assert!(json.contains(r#"{"id":1,"kind":"string","value":"boom"}"#));
+    }
+}
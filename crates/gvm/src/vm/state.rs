@@ -1,34 +1,74 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    process::exit,
+};
 
-use broom::Heap;
+use broom::{Handle, Heap};
 
 use crate::{
-    format::{func::FuncDef, instr::ByteCode, literal::Literal, table::VTable},
+    format::{func::FuncDef, instr::ByteCode, literal::Literal, span::ByteCodeSpan, table::VTable},
     vm::text::DebugText as _,
 };
 
-use super::{heap::HeapItem, scope::Scope, stack::StackItem};
+use super::{
+    heap::HeapItem,
+    jit::Jit,
+    scope::Scope,
+    snapshot::DumpOptions,
+    stack::StackItem,
+    thread::{Thread, FUEL},
+    trace::Inputs,
+};
 
 pub struct ProgramState<'code> {
     pub funcs: &'code HashMap<u32, FuncDef>,
+    pub entry: Option<u32>,
     pub heap: Heap<HeapItem>,
     pub scopes: Vec<Scope<'code>>,
     pub vtables: HashMap<u64, HashMap<u32, u32>>, // type_id -> (trait_func_id -> impl_func_id)
     pub file_names: HashMap<u32, String>,
+    pub type_names: HashMap<u32, String>,
+    pub dump: DumpOptions,
+    pub threads: VecDeque<Thread<'code>>,
+    pub thread_id: u32,
+    pub thread_count: u32,
+    pub fuel: u32,
+    pub waiting: Option<Handle<HeapItem>>,
+    pub jit: Option<Jit>,
+    pub inputs: Inputs,
+    /// Skips printing, while re-running a program to reach an earlier point
+    pub muted: bool,
+    /// When set, printed text is collected here instead of written to stdout, and failures
+    /// panic instead of exiting
+    pub captured: Option<String>,
 }
 
 impl<'code> ProgramState<'code> {
     pub fn new(
         funcs: &'code HashMap<u32, FuncDef>,
+        entry: Option<u32>,
         vtables: HashMap<u64, VTable>,
         file_names: HashMap<u32, String>,
+        type_names: HashMap<u32, String>,
     ) -> Self {
         Self {
             heap: Heap::default(),
             scopes: vec![],
             vtables,
             file_names,
+            type_names,
             funcs,
+            entry,
+            dump: DumpOptions::default(),
+            threads: VecDeque::new(),
+            thread_id: 0,
+            thread_count: 0,
+            fuel: FUEL,
+            waiting: None,
+            jit: None,
+            inputs: Inputs::default(),
+            muted: false,
+            captured: None,
         }
     }
 
@@ -40,31 +80,46 @@ impl<'code> ProgramState<'code> {
         self.scopes.last_mut().expect("Call stack underflow")
     }
 
+    /// The frame for the program's entry point
+    pub fn entry_scope(&self) -> Scope<'code> {
+        let entry = self.entry.expect("No entry point");
+        let main = self.funcs.get(&entry).expect("No main function");
+        Scope::from_code(&main.body, entry)
+    }
+
     pub fn run_debug(&mut self) {
-        let main = self.funcs.get(&0).expect("No main function");
-        self.scopes.push(Scope::from_code(&main.body, 0));
-        while !self.scopes.is_empty() {
-            let instr = self.next_instr();
-            println!(
-                "{instr:?} : {}:{}",
-                self.stack_trace(),
-                self.scope()
-                    .stack
-                    .iter()
-                    .map(|it| it.get_text(self))
-                    .collect::<Vec<_>>()
-                    .join("|"),
-            );
-            self.execute(instr);
+        self.scopes.push(self.entry_scope());
+        loop {
+            while self.can_continue() {
+                let instr = self.next_instr();
+                println!(
+                    "{instr:?} : {}:{}",
+                    self.stack_trace(),
+                    self.scope()
+                        .stack
+                        .iter()
+                        .map(|it| it.get_text(self))
+                        .collect::<Vec<_>>()
+                        .join("|"),
+                );
+                self.execute(instr);
+            }
+            if !self.schedule() {
+                break;
+            }
         }
     }
 
     pub fn run(&mut self) {
-        let main = self.funcs.get(&0).expect("No main function");
-        self.scopes.push(Scope::from_code(&main.body, 0));
-        while !self.scopes.is_empty() {
-            let instr = self.next_instr();
-            self.execute(instr);
+        self.scopes.push(self.entry_scope());
+        loop {
+            while self.can_continue() {
+                let instr = self.next_instr();
+                self.execute(instr);
+            }
+            if !self.schedule() {
+                break;
+            }
         }
     }
 
@@ -90,23 +145,53 @@ impl<'code> ProgramState<'code> {
             .map(|scope| {
                 let func = &self.funcs[&scope.id];
                 let file_name = &self.file_names[&func.file];
-                let prev = scope.index - 1;
-                let marker = func
-                    .marks
-                    .iter()
-                    .rev()
-                    .find(|mark| mark.0 <= prev)
-                    .map(|mark| mark.1)
-                    .unwrap_or(func.pos);
-                format!(
+                let marker = self.scope_pos(scope);
+                let line = format!(
                     "  -> {}:{}:{} ({})",
                     file_name, marker.0, marker.1, func.name,
-                )
+                );
+                match scope.elided {
+                    0 => line,
+                    1 => format!("  ... 1 frame elided by a tail call\n{line}"),
+                    n => format!("  ... {n} frames elided by tail calls\n{line}"),
+                }
             })
             .collect::<Vec<_>>()
             .join("\n")
     }
 
+    /// Stops the program with an error, the same way a `panic` in the program does
+    pub fn fail(&self, message: &str) -> ! {
+        self.dump_snapshot();
+        if self.captured.is_some() {
+            // Embedded, as in tests, so unwind instead of exiting the whole process
+            panic!("{message}\n{}", self.stack_trace());
+        }
+        println!("{message}");
+        println!("{}", self.stack_trace());
+        exit(1);
+    }
+
+    /// The index into a vec of `len` items, failing when it's out of bounds
+    pub fn checked_index(&self, index: i32, len: usize) -> usize {
+        vec_index(index, len).unwrap_or_else(|| {
+            self.fail(&format!(
+                "Index {index} is out of bounds for a vec of length {len}"
+            ))
+        })
+    }
+
+    /// The source position of the last instruction executed in `scope`
+    pub fn scope_pos(&self, scope: &Scope) -> ByteCodeSpan {
+        let func = &self.funcs[&scope.id];
+        let prev = scope.index.saturating_sub(1);
+        func.marks
+            .iter()
+            .rev()
+            .find(|mark| mark.0 <= prev)
+            .map_or(func.pos, |mark| mark.1)
+    }
+
     pub fn new_local(&mut self, id: u32, refr: StackItem) {
         self.scope_mut().locals.insert(id, refr);
     }
@@ -128,6 +213,7 @@ impl<'code> ProgramState<'code> {
     }
 
     pub fn next_instr(&mut self) -> &'code ByteCode {
+        self.fuel = self.fuel.saturating_sub(1);
         self.scope_mut().next_instr()
     }
 
@@ -135,6 +221,25 @@ impl<'code> ProgramState<'code> {
         self.vtables.get(&type_id)?.get(&func_id).copied()
     }
 
+    pub fn pop_string(&mut self) -> String {
+        let StackItem::Heap(refr) = self.pop() else {
+            panic!("Expected heap obj")
+        };
+        let HeapItem::String(text) = self.heap.get(refr).unwrap() else {
+            panic!("Expected string")
+        };
+        text.clone()
+    }
+
+    pub fn construct_option(&mut self, value: Option<StackItem>, some: u32, none: u32) {
+        let obj = match value {
+            Some(value) => HeapItem::Object(some, vec![value]),
+            None => HeapItem::Object(none, vec![]),
+        };
+        let refr = self.heap.insert(obj);
+        self.push(StackItem::Heap(refr.into()));
+    }
+
     pub fn create(&mut self, literal: &Literal) -> StackItem {
         match literal {
             Literal::Int(num) => StackItem::Int(*num),
@@ -148,3 +253,25 @@ impl<'code> ProgramState<'code> {
         }
     }
 }
+
+/// The index into a vec of `len` items, or `None` when it's out of bounds
+#[must_use]
+pub fn vec_index(index: i32, len: usize) -> Option<usize> {
+    usize::try_from(index).ok().filter(|index| *index < len)
+}
+
+/// `base` raised to `exp`, or the reason it can't be done with ints
+pub fn int_pow(base: i32, exp: i32) -> Result<i32, String> {
+    let Ok(exp) = u32::try_from(exp) else {
+        return Err(format!("Cannot raise an int to a negative power: {exp}"));
+    };
+    base.checked_pow(exp)
+        .ok_or_else(|| format!("Integer overflow raising {base} to the power {exp}"))
+}
+
+/// The absolute value of `value`, or the reason it can't be done with ints
+pub fn int_abs(value: i32) -> Result<i32, String> {
+    value
+        .checked_abs()
+        .ok_or_else(|| format!("Integer overflow taking the absolute value of {value}"))
+}
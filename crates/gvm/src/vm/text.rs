@@ -34,6 +34,8 @@ impl DebugText for HeapItem {
             HeapItem::Dyn(id, item) => {
                 format!("Dyn({}, {})", id, item.get_text(state))
             }
+            HeapItem::Channel(items) => format!("Channel({} queued)", items.len()),
+            HeapItem::Generator(frame) => format!("Generator({})", state.funcs[&frame.func].name),
         }
     }
 }
@@ -0,0 +1,168 @@
+use std::mem::take;
+
+use broom::Handle;
+
+use super::{heap::HeapItem, scope::Scope, stack::StackItem, state::ProgramState};
+
+/// How many instructions a thread may run before the scheduler switches to the next one
+pub const FUEL: u32 = 1000;
+
+/// A parked green thread waiting for its turn on the scheduler
+pub struct Thread<'code> {
+    pub id: u32,
+    pub scopes: Vec<Scope<'code>>,
+    pub waiting: Option<Handle<HeapItem>>,
+}
+
+impl<'code> ProgramState<'code> {
+    /// Whether the current thread can keep running without a context switch
+    pub fn can_continue(&self) -> bool {
+        !self.scopes.is_empty() && self.waiting.is_none() && self.fuel > 0
+    }
+
+    /// Parks the current thread and resumes the next runnable one in round-robin order.
+    /// Returns false once every thread has finished, so returning from `main` waits for
+    /// the threads it spawned.
+    pub fn schedule(&mut self) -> bool {
+        if !self.scopes.is_empty() {
+            let current = Thread {
+                id: self.thread_id,
+                scopes: take(&mut self.scopes),
+                waiting: self.waiting.take(),
+            };
+            self.threads.push_back(current);
+        }
+        if self.threads.is_empty() {
+            return false;
+        }
+        let Some(index) = self.threads.iter().position(|t| self.is_runnable(t)) else {
+            // Resume the last thread that ran, so the trace shows where it's stuck
+            let last = self.threads.pop_back().unwrap();
+            self.thread_id = last.id;
+            self.scopes = last.scopes;
+            self.fail("Deadlock: every thread is waiting on an empty channel")
+        };
+        let next = self.threads.remove(index).unwrap();
+        self.thread_id = next.id;
+        self.scopes = next.scopes;
+        self.fuel = FUEL;
+        true
+    }
+
+    pub fn spawn(&mut self, func_id: u32, args: Vec<StackItem>) {
+        self.thread_count += 1;
+        let mut scope = Scope::from_code(&self.funcs[&func_id].body, func_id);
+        scope.args = args;
+        self.threads.push_back(Thread {
+            id: self.thread_count,
+            scopes: vec![scope],
+            waiting: None,
+        });
+    }
+
+    fn is_runnable(&self, thread: &Thread) -> bool {
+        thread.waiting.as_ref().is_none_or(|chan| {
+            matches!(self.heap.get(chan), Some(HeapItem::Channel(items)) if !items.is_empty())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::{
+        text::decode::parser::parse_text_file,
+        vm::{stack::StackItem, state::ProgramState},
+    };
+
+    // `main` spawns a task that sends 1, 2 and 3 on a channel, then receives them
+    const PROGRAM: &str = r#"
+        file 0 "main.gib"
+        type 5 9 1
+        entry 0
+        func 0 0 "main" 1 1 0
+        channel new 0 get 0 dyn 5 spawn 9
+        get 0 receive get 0 receive get 0 receive return
+
+        func 1 1 "run" 5 1 0
+        param 0 push 1 send param 0 push 2 send param 0 push 3 send return
+    "#;
+
+    #[test]
+    fn test_receive_order() {
+        let file = parse_text_file(PROGRAM).unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.scopes.push(prog.entry_scope());
+        while prog.thread_id != 0 || prog.scope().index < 11 {
+            if prog.can_continue() {
+                let instr = prog.next_instr();
+                prog.execute(instr);
+            } else {
+                assert!(prog.schedule());
+            }
+        }
+        assert_eq!(
+            prog.scope().stack,
+            vec![StackItem::Int(1), StackItem::Int(2), StackItem::Int(3)]
+        );
+    }
+
+    #[test]
+    fn test_main_waits_for_threads() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            type 5 9 1
+            entry 0
+            func 0 0 "main" 1 1 0
+            push 0 dyn 5 spawn 9 return
+
+            func 1 1 "run" 5 1 0
+            push 1 push 2 add return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.run();
+        assert_eq!(prog.thread_id, 1);
+        assert!(prog.threads.is_empty());
+    }
+
+    #[test]
+    fn test_deadlock_fails() {
+        let file = parse_text_file(
+            r#"
+            file 0 "main.gib"
+            entry 0
+            func 0 0 "main" 1 1 0
+            channel receive return
+            "#,
+        )
+        .unwrap();
+        let mut prog = ProgramState::new(
+            &file.funcs,
+            file.entry,
+            file.tables,
+            file.file_names,
+            file.type_names,
+        );
+        prog.captured = Some(String::new());
+        let res = catch_unwind(AssertUnwindSafe(|| prog.run()));
+        let message = *res.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("Deadlock: every thread is waiting on an empty channel\n"));
+        assert!(message.contains("main.gib"));
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{hash_map::RandomState, VecDeque},
+    fs::File,
+    hash::BuildHasher as _,
+    io::{self, stdin, Write as _},
+    path::Path,
+    time::Instant,
+};
+
+use thiserror::Error;
+
+/// Starts every trace file, followed by the length of the program and the program itself
+const MAGIC: [u8; 8] = *b"gibtrace";
+
+/// Something a program read from outside the VM, which could differ between runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Line(Option<String>),
+    Time(i32),
+    Random(i32),
+    Args(Vec<String>),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TraceError {
+    #[error("Not a trace file")]
+    NotATrace,
+
+    #[error("The trace file is truncated")]
+    Truncated,
+
+    #[error("Unknown input kind {0} in trace file")]
+    UnknownInput(u8),
+}
+
+/// Where a program's inputs come from, and where they are recorded.
+///
+/// Inputs are replayed in order until none are left,
+/// after which they are read live unless the replay is `strict`
+pub struct Inputs {
+    /// The arguments passed to the program on the command line
+    pub args: Vec<String>,
+    pub replay: VecDeque<Input>,
+    pub strict: bool,
+    /// Every input read so far
+    pub history: Vec<Input>,
+    pub trace: Option<File>,
+    start: Instant,
+    rng: u64,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl Inputs {
+    pub fn new(args: Vec<String>) -> Self {
+        Self {
+            args,
+            replay: VecDeque::new(),
+            strict: false,
+            history: vec![],
+            trace: None,
+            start: Instant::now(),
+            // xorshift gets stuck at 0
+            rng: RandomState::new().hash_one(0u8) | 1,
+        }
+    }
+
+    /// Starts a trace file for `program`, which every input is appended to as it is read
+    pub fn record(&mut self, path: &Path, program: &[u8]) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(&MAGIC)?;
+        file.write_all(&(program.len() as u64).to_be_bytes())?;
+        file.write_all(program)?;
+        self.trace = Some(file);
+        Ok(())
+    }
+
+    /// Replays every input in `inputs` before reading any live ones
+    pub fn replay(&mut self, inputs: impl IntoIterator<Item = Input>, strict: bool) {
+        self.replay = inputs.into_iter().collect();
+        self.strict = strict;
+    }
+
+    pub fn read_line(&mut self) -> Option<String> {
+        match self.read(|_| {
+            let mut line = String::new();
+            Input::Line(match stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => {
+                    let len = line.trim_end_matches(['\n', '\r']).len();
+                    line.truncate(len);
+                    Some(line)
+                }
+            })
+        }) {
+            Input::Line(line) => line,
+            other => diverged(&other, "a line of input"),
+        }
+    }
+
+    /// Milliseconds since the program started
+    pub fn time(&mut self) -> i32 {
+        match self.read(|inputs| {
+            Input::Time(i32::try_from(inputs.start.elapsed().as_millis()).unwrap_or(i32::MAX))
+        }) {
+            Input::Time(time) => time,
+            other => diverged(&other, "the time"),
+        }
+    }
+
+    pub fn random(&mut self) -> i32 {
+        match self.read(|inputs| {
+            inputs.rng ^= inputs.rng << 13;
+            inputs.rng ^= inputs.rng >> 7;
+            inputs.rng ^= inputs.rng << 17;
+            Input::Random((inputs.rng >> 32) as i32)
+        }) {
+            Input::Random(num) => num,
+            other => diverged(&other, "a random number"),
+        }
+    }
+
+    pub fn args(&mut self) -> Vec<String> {
+        match self.read(|inputs| Input::Args(inputs.args.clone())) {
+            Input::Args(args) => args,
+            other => diverged(&other, "the arguments"),
+        }
+    }
+
+    fn read(&mut self, live: impl FnOnce(&mut Self) -> Input) -> Input {
+        let input = match self.replay.pop_front() {
+            Some(input) => input,
+            None if self.strict => panic!("The program read more input than was recorded"),
+            None => live(self),
+        };
+        if let Some(trace) = &mut self.trace {
+            // Written straight away so the trace survives the program exiting abruptly
+            trace
+                .write_all(&encode_input(&input))
+                .expect("Could not write to the trace file");
+        }
+        self.history.push(input.clone());
+        input
+    }
+}
+
+fn diverged(recorded: &Input, reading: &str) -> ! {
+    panic!("The program read {reading} where the trace has {recorded:?}")
+}
+
+fn encode_input(input: &Input) -> Vec<u8> {
+    fn string(bytes: &mut Vec<u8>, text: &str) {
+        bytes.extend_from_slice(&(text.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(text.as_bytes());
+    }
+    let mut bytes = vec![];
+    match input {
+        Input::Line(None) => bytes.push(0),
+        Input::Line(Some(line)) => {
+            bytes.push(1);
+            string(&mut bytes, line);
+        }
+        Input::Time(time) => {
+            bytes.push(2);
+            bytes.extend_from_slice(&time.to_be_bytes());
+        }
+        Input::Random(num) => {
+            bytes.push(3);
+            bytes.extend_from_slice(&num.to_be_bytes());
+        }
+        Input::Args(args) => {
+            bytes.push(4);
+            bytes.extend_from_slice(&(args.len() as u32).to_be_bytes());
+            for arg in args {
+                string(&mut bytes, arg);
+            }
+        }
+    }
+    bytes
+}
+
+/// Splits a trace file into the program that was run and the inputs it read
+pub fn decode_trace(bytes: &[u8]) -> Result<(&[u8], Vec<Input>), TraceError> {
+    let rest = bytes.strip_prefix(&MAGIC).ok_or(TraceError::NotATrace)?;
+    let mut reader = Reader(rest);
+    let len =
+        usize::try_from(u64::from_be_bytes(reader.array()?)).map_err(|_| TraceError::Truncated)?;
+    let program = reader.take(len)?;
+    let mut inputs = vec![];
+    while let Some(kind) = reader.byte() {
+        let input = match kind {
+            0 => Input::Line(None),
+            1 => Input::Line(Some(reader.string()?)),
+            2 => Input::Time(i32::from_be_bytes(reader.array()?)),
+            3 => Input::Random(i32::from_be_bytes(reader.array()?)),
+            4 => {
+                let count = u32::from_be_bytes(reader.array()?);
+                let args = (0..count)
+                    .map(|_| reader.string())
+                    .collect::<Result<_, _>>()?;
+                Input::Args(args)
+            }
+            kind => return Err(TraceError::UnknownInput(kind)),
+        };
+        inputs.push(input);
+    }
+    Ok((program, inputs))
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Option<u8> {
+        let (first, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(*first)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TraceError> {
+        if self.0.len() < len {
+            return Err(TraceError::Truncated);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], TraceError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn string(&mut self) -> Result<String, TraceError> {
+        let len = u32::from_be_bytes(self.array()?) as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_trace, encode_input, Input, Inputs, TraceError, MAGIC};
+
+    #[test]
+    fn test_trace_round_trip() {
+        let inputs = vec![
+            Input::Args(vec!["-v".to_string(), "é".to_string()]),
+            Input::Line(Some("hello".to_string())),
+            Input::Time(1234),
+            Input::Random(-7),
+            Input::Line(None),
+        ];
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&3u64.to_be_bytes());
+        bytes.extend_from_slice(b"abc");
+        for input in &inputs {
+            bytes.extend(encode_input(input));
+        }
+        assert_eq!(decode_trace(&bytes), Ok((&b"abc"[..], inputs)));
+        assert_eq!(
+            decode_trace(&bytes[..bytes.len() - 2]),
+            Err(TraceError::Truncated)
+        );
+        assert_eq!(decode_trace(b"not a trace"), Err(TraceError::NotATrace));
+    }
+
+    #[test]
+    fn test_replay_then_live() {
+        let mut inputs = Inputs::new(vec!["live".to_string()]);
+        inputs.replay([Input::Args(vec!["recorded".to_string()])], false);
+        assert_eq!(inputs.args(), vec!["recorded".to_string()]);
+        assert_eq!(inputs.args(), vec!["live".to_string()]);
+        assert_eq!(inputs.history.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "where the trace has Time(5)")]
+    fn test_replay_detects_divergence() {
+        let mut inputs = Inputs::default();
+        inputs.replay([Input::Time(5)], true);
+        inputs.random();
+    }
+}
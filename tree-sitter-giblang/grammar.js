@@ -32,8 +32,14 @@ module.exports = grammar({
       "in",
       "break",
       "continue",
+      "yield",
     ),
 
+    // Conditions that match a pattern, whose bindings only exist in the branch or loop.
+    // They're single tokens, as `if` and `while` are also keywords on their own
+    if_let: $ => token(prec(1, seq("if", /\s+/, "let"))),
+    while_let: $ => token(prec(1, seq("while", /\s+/, "let"))),
+
     punctuation: $ => choice(
       "(",
       ")",
@@ -45,6 +51,7 @@ module.exports = grammar({
       ".",
       ":",
       ";",
+      "?",
     ),
 
     op: $ => choice(
@@ -57,6 +64,8 @@ module.exports = grammar({
     ),
 
     _token: $ => choice(
+      $.if_let,
+      $.while_let,
       $.identifier,
       $.number,
       $.string,